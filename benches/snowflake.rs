@@ -0,0 +1,139 @@
+//! Snowflake 生成吞吐量：无锁实现与 `Mutex<twitter_snowflake::Snowflake>` 对照
+//!
+//! 运行：`cargo bench --bench snowflake`。单个 worker 每毫秒最多 4096 个ID，吞吐量达到该上限后两者趋同，
+//! 差异主要体现在多线程下的等待时间。`snowflake_pool` 组用 N 个线程对比单个管理器与 N 个工作者的
+//! [`SnowflakePool`]，后者每毫秒的上限为 N × 4096。`snowflake_metrics` 组对比设置 [`AtomicMetrics`] 前后的开销。
+use clamber_core::snowflake::{AtomicMetrics, SnowflakeConfig, SnowflakeManager, SnowflakePool};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use twitter_snowflake::Snowflake;
+
+/// 每个线程生成的ID数量
+const IDS_PER_THREAD: u64 = 4_096;
+
+fn run_threads(
+    threads: usize,
+    ids_per_thread: u64,
+    generate: impl Fn() -> u64 + Send + Sync + 'static,
+) {
+    let generate = Arc::new(generate);
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let generate = Arc::clone(&generate);
+            thread::spawn(move || {
+                for _ in 0..ids_per_thread {
+                    std::hint::black_box(generate());
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn bench_generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snowflake_generate");
+    for threads in [1, 4, 16] {
+        group.throughput(Throughput::Elements(IDS_PER_THREAD * threads as u64));
+
+        let manager = Arc::new(SnowflakeManager::new(SnowflakeConfig::new(1).unwrap()).unwrap());
+        group.bench_with_input(
+            BenchmarkId::new("atomic", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    let manager = Arc::clone(&manager);
+                    run_threads(threads, IDS_PER_THREAD, move || {
+                        manager.generate_id().unwrap()
+                    })
+                })
+            },
+        );
+
+        let locked = Arc::new(Mutex::new(Snowflake::new(1).unwrap()));
+        group.bench_with_input(
+            BenchmarkId::new("mutex", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    let locked = Arc::clone(&locked);
+                    run_threads(threads, IDS_PER_THREAD, move || {
+                        locked.lock().unwrap().generate().unwrap()
+                    })
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// 池基准中每个线程生成的ID数量，远超单个 worker 每毫秒的上限
+const POOL_IDS_PER_THREAD: u64 = 16 * 4_096;
+
+fn bench_pool(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snowflake_pool");
+    // 线程数与池中的工作者数相同
+    for workers in [4usize, 8] {
+        group.throughput(Throughput::Elements(POOL_IDS_PER_THREAD * workers as u64));
+
+        let manager = Arc::new(SnowflakeManager::new(SnowflakeConfig::new(1).unwrap()).unwrap());
+        group.bench_with_input(
+            BenchmarkId::new("single", workers),
+            &workers,
+            |b, &threads| {
+                b.iter(|| {
+                    let manager = Arc::clone(&manager);
+                    run_threads(threads, POOL_IDS_PER_THREAD, move || {
+                        manager.generate_id().unwrap()
+                    })
+                })
+            },
+        );
+
+        let worker_ids = (1..=workers as u64).collect();
+        let pool = Arc::new(SnowflakePool::new(SnowflakeConfig::default(), worker_ids).unwrap());
+        group.bench_with_input(
+            BenchmarkId::new("pool", workers),
+            &workers,
+            |b, &threads| {
+                b.iter(|| {
+                    let pool = Arc::clone(&pool);
+                    run_threads(threads, POOL_IDS_PER_THREAD, move || {
+                        pool.generate_id().unwrap()
+                    })
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_metrics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snowflake_metrics");
+    for threads in [1, 4] {
+        group.throughput(Throughput::Elements(IDS_PER_THREAD * threads as u64));
+
+        let plain = Arc::new(SnowflakeManager::new(SnowflakeConfig::new(1).unwrap()).unwrap());
+        let metered = Arc::new(
+            SnowflakeManager::new(SnowflakeConfig::new(1).unwrap())
+                .unwrap()
+                .with_metrics(Arc::new(AtomicMetrics::new())),
+        );
+        for (name, manager) in [("none", plain), ("atomic", metered)] {
+            group.bench_with_input(BenchmarkId::new(name, threads), &threads, |b, &threads| {
+                b.iter(|| {
+                    let manager = Arc::clone(&manager);
+                    run_threads(threads, IDS_PER_THREAD, move || {
+                        manager.generate_id().unwrap()
+                    })
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_generate, bench_pool, bench_metrics);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! JWT 签发与校验的吞吐量
+//!
+//! 运行：`cargo bench --bench token`。`is_valid` 对应鉴权热路径上只判断有效性、不解析 payload 的调用；
+//! `key_cache` 组对比复用缓存密钥与每次重新初始化密钥的验证耗时。
+use clamber_core::token::{JwtConfig, JwtManager};
+use criterion::{Criterion, criterion_group, criterion_main};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct User {
+    id: String,
+    name: String,
+    roles: Vec<String>,
+}
+
+fn user() -> User {
+    User {
+        id: "10086".to_string(),
+        name: "alice".to_string(),
+        roles: vec!["admin".to_string(), "user".to_string()],
+    }
+}
+
+fn bench_token(c: &mut Criterion) {
+    let mut group = c.benchmark_group("token");
+    for (name, config) in [
+        ("nested", JwtConfig::new("bench_secret", 7)),
+        ("flat", JwtConfig::new("bench_secret", 7).flat_claims(true)),
+    ] {
+        let manager = JwtManager::new(config);
+        let token = manager.generate_token(&user()).unwrap();
+
+        group.bench_function(format!("generate/{}", name), |b| {
+            b.iter(|| {
+                manager
+                    .generate_token(std::hint::black_box(&user()))
+                    .unwrap()
+            })
+        });
+        group.bench_function(format!("verify/{}", name), |b| {
+            b.iter(|| {
+                manager
+                    .verify_token::<User>(std::hint::black_box(&token))
+                    .unwrap()
+            })
+        });
+        group.bench_function(format!("is_valid/{}", name), |b| {
+            b.iter(|| assert!(manager.is_valid_token(std::hint::black_box(&token))))
+        });
+    }
+    group.finish();
+}
+
+/// 复用管理器中缓存的 HMAC 密钥，与每次调用都重新创建管理器（重新初始化密钥）对比
+fn bench_key_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("key_cache");
+    let config = JwtConfig::new("bench_secret", 7);
+    let manager = JwtManager::new(config.clone());
+    let token = manager.generate_token(&user()).unwrap();
+
+    group.bench_function("verify/cached", |b| {
+        b.iter(|| {
+            manager
+                .verify_token::<User>(std::hint::black_box(&token))
+                .unwrap()
+        })
+    });
+    group.bench_function("verify/per_call", |b| {
+        b.iter(|| {
+            JwtManager::new(config.clone())
+                .verify_token::<User>(std::hint::black_box(&token))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_token, bench_key_cache);
+criterion_main!(benches);
@@ -0,0 +1,38 @@
+//! 异步用法示例：在 tokio 运行时中直接 `.await` JWT 与 Snowflake 的异步方法。
+use clamber_core::{JwtConfig, JwtManager, Result, SnowflakeConfig, SnowflakeManager};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: String,
+    name: String,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    println!("=== 异步 JWT 与 Snowflake 示例 ===\n");
+
+    let manager = JwtManager::new(JwtConfig::new("async_example_secret", 7));
+    let user = User {
+        id: "1".to_string(),
+        name: "张三".to_string(),
+    };
+    let token = manager.generate_token_async(user).await?;
+    println!("1. 生成的token: {}...", &token[..32]);
+
+    let decoded: User = manager.verify_token_async(&token).await?;
+    println!("2. 验证通过: {:?}", decoded);
+
+    // 超过每毫秒序列号容量的批量生成交给后台工作线程，不阻塞运行时
+    let snowflake = Arc::new(SnowflakeManager::new(SnowflakeConfig::new(1)?)?);
+    let ids = snowflake.clone().generate_ids_async(10_000).await?;
+    println!(
+        "3. 批量生成 {} 个ID: {} ..= {}",
+        ids.len(),
+        ids[0],
+        ids[ids.len() - 1]
+    );
+
+    Ok(())
+}
@@ -24,7 +24,7 @@ fn example_custom_logging() -> Result<()> {
         .console_level(LevelFilter::DEBUG) // 控制台显示DEBUG级别
         .file_level(LevelFilter::INFO); // 文件只记录INFO及以上
 
-    let _guards = logger_start_with_config("custom_demo", None, config)?;
+    let logger = logger_start_with_config("custom_demo", None, config)?;
 
     println!("📝 开始记录不同级别的美化日志...\n");
 
@@ -111,8 +111,11 @@ fn example_custom_logging() -> Result<()> {
         "🌐 API调用成功"
     );
 
+    // 退出前确保文件日志全部写入
+    logger.flush();
+
     println!("\n✅ 自定义配置日志记录完成！");
-    println!("📁 日志文件保存在: ./logs/");
+    println!("📁 日志文件保存在: {}", logger.log_directory().display());
     println!("🎨 配置特性:");
     println!("   • 时间格式: yyyy-MM-dd HH:mm:ss.SSS (包含毫秒)");
     println!("   • 控制台: 彩色输出，显示DEBUG级别及以上");
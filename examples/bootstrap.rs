@@ -0,0 +1,56 @@
+use clamber_core::config::ConfigBuilder;
+use clamber_core::{AppContext, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+fn main() -> Result<()> {
+    // 准备一份示例配置；实际项目中通常直接使用 AppContext::init("my-service")
+    let config_path = std::env::temp_dir().join("clamber-bootstrap-example.yaml");
+    fs::write(
+        &config_path,
+        r#"
+log:
+  dir: logs/bootstrap
+  console_level: debug
+  format: compact
+  timezone: "+08:00"
+jwt:
+  secret: bootstrap-example-secret
+  expire_days: 1
+snowflake:
+  worker_id: 7
+"#,
+    )?;
+
+    let ctx = AppContext::init_with(
+        "bootstrap-example",
+        ConfigBuilder::new().add_yaml_file(&config_path),
+    )?;
+    tracing::info!("应用上下文初始化完成");
+
+    // Snowflake
+    let id = ctx.snowflake().generate_id()?;
+    tracing::info!(id, worker_id = ctx.snowflake().worker_id(), "生成ID");
+
+    // JWT
+    let user = User {
+        id,
+        name: "alice".to_string(),
+    };
+    let token = ctx.jwt().generate_token(&user)?;
+    let decoded: User = ctx.jwt().verify_token(&token)?;
+    tracing::info!(?decoded, "token 验证通过");
+
+    // 原始配置
+    let secret: String = ctx.config_raw().get("jwt.secret")?;
+    tracing::debug!(secret_len = secret.len(), "读取原始配置");
+
+    fs::remove_file(&config_path)?;
+    Ok(())
+}
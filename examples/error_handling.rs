@@ -1,5 +1,6 @@
 use clamber_core::{
-    ClamberError, JwtConfig, JwtManager, Result, generate_token, is_valid_token, verify_token,
+    ClamberError, JwtConfig, JwtManager, Result, generate_token_default, is_valid_token,
+    set_default_config, verify_token,
 };
 use serde::{Deserialize, Serialize};
 
@@ -15,6 +16,9 @@ struct User {
 fn main() -> Result<()> {
     println!("=== Clamber Core 错误处理示例 ===\n");
 
+    // 便利函数使用的默认配置，启动时设置一次
+    set_default_config(JwtConfig::new("example_secret_2024", 7))?;
+
     // 示例1：JWT错误处理
     example_jwt_error_handling()?;
 
@@ -43,7 +47,7 @@ fn example_jwt_error_handling() -> Result<()> {
 
     match manager.generate_token(&user) {
         Ok(_) => println!("   意外：空密钥应该失败"),
-        Err(ClamberError::JwtKeyError { details }) => {
+        Err(ClamberError::JwtKeyError { details, .. }) => {
             println!("   ✓ 正确检测到密钥错误: {}", details);
         }
         Err(e) => println!("   ? 其他错误: {}", e),
@@ -55,7 +59,7 @@ fn example_jwt_error_handling() -> Result<()> {
 
     match valid_manager.verify_token::<User>("invalid.jwt.token") {
         Ok(_) => println!("   意外：无效token应该失败"),
-        Err(ClamberError::JwtVerifyError { details }) => {
+        Err(ClamberError::JwtVerifyError { details, .. }) => {
             println!("   ✓ 正确检测到JWT验证错误: {}", details);
         }
         Err(e) => println!("   ? 其他JWT错误: {}", e),
@@ -71,7 +75,7 @@ fn example_jwt_error_handling() -> Result<()> {
 
     match manager2.verify_token::<User>(&token) {
         Ok(_) => println!("   意外：不同密钥应该失败"),
-        Err(ClamberError::JwtVerifyError { details }) => {
+        Err(ClamberError::JwtVerifyError { details, .. }) => {
             println!("   ✓ 正确检测到密钥不匹配: {}", details);
         }
         Err(e) => println!("   ? 其他错误: {}", e),
@@ -92,7 +96,7 @@ fn example_success_case() -> Result<()> {
     };
 
     // 使用便利函数
-    let token = generate_token(&user, JwtConfig::default()).map_err(|e| {
+    let token = generate_token_default(&user).map_err(|e| {
         println!("   生成token失败: {}", e);
         e
     })?;
@@ -129,18 +133,22 @@ fn demonstrate_error_handling_patterns() {
     // 模式1：具体错误类型处理
     println!("\n   模式1：具体错误类型匹配");
     let invalid_token = "invalid.token";
-    match verify_token::<User>(invalid_token) {
+    // root_cause 剥离调用链上附加的上下文，便于按变体匹配
+    match verify_token::<User>(invalid_token)
+        .as_ref()
+        .map_err(ClamberError::root_cause)
+    {
         Ok(user) => println!("   用户验证成功: {:?}", user),
         Err(ClamberError::JwtExpiredError) => {
             println!("   → Token已过期，请重新登录");
         }
-        Err(ClamberError::JwtVerifyError { details }) => {
+        Err(ClamberError::JwtVerifyError { details, .. }) => {
             println!("   → Token验证失败: {}", details);
         }
         Err(ClamberError::JwtMissingFieldError { field }) => {
             println!("   → Token格式错误，缺少字段: {}", field);
         }
-        Err(ClamberError::DeserializationError { details }) => {
+        Err(ClamberError::DeserializationError { details, .. }) => {
             println!("   → 数据格式错误: {}", details);
         }
         Err(e) => {
@@ -150,17 +158,18 @@ fn demonstrate_error_handling_patterns() {
 
     // 模式2：错误传播
     println!("\n   模式2：错误传播（? 操作符）");
-    let result = (|| -> Result<()> {
+    let run_sequence = || -> Result<()> {
         let user = User {
             id: "test".to_string(),
             username: "test".to_string(),
             email: "test@example.com".to_string(),
             role: "user".to_string(),
         };
-        let token = generate_token(&user, JwtConfig::default())?;
+        let token = generate_token_default(&user)?;
         let _decoded: User = verify_token(&token)?;
         Ok(())
-    })();
+    };
+    let result = run_sequence();
 
     match result {
         Ok(()) => println!("   ✓ 操作序列成功完成"),
@@ -169,7 +178,7 @@ fn demonstrate_error_handling_patterns() {
 
     // 模式3：错误转换和上下文
     println!("\n   模式3：错误上下文处理");
-    let result = (|| -> Result<String> {
+    let generate_with_context = || -> Result<String> {
         let config = JwtConfig::new("test_secret", 1);
         let manager = JwtManager::new(config);
 
@@ -181,7 +190,8 @@ fn demonstrate_error_handling_patterns() {
         };
 
         manager.generate_token(&user)
-    })();
+    };
+    let result = generate_with_context();
 
     match result {
         Ok(token) => println!("   ✓ 生成token成功: {}...", &token[..20]),
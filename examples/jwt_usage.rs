@@ -1,5 +1,8 @@
 use clamber_core::Result;
-use clamber_core::token::{JwtConfig, JwtManager, generate_token, is_valid_token, verify_token};
+use clamber_core::token::{
+    JwtConfig, JwtManager, generate_token, generate_token_default, is_valid_token,
+    set_default_config, verify_token,
+};
 use serde::{Deserialize, Serialize};
 
 // 定义你的用户结构体
@@ -22,6 +25,9 @@ struct SessionData {
 fn main() -> Result<()> {
     println!("=== Clamber Core JWT 使用示例 ===\n");
 
+    // 便利函数使用的默认配置，启动时设置一次
+    set_default_config(JwtConfig::new("example_secret_2024", 7))?;
+
     // 示例1：使用默认配置的便利函数
     example_convenience_functions()?;
 
@@ -48,7 +54,7 @@ fn example_convenience_functions() -> Result<()> {
     };
 
     // 生成token
-    let token = generate_token(&user, JwtConfig::default())?;
+    let token = generate_token_default(&user)?;
     println!("   生成的token: {}", token);
 
     // 生成token(自定义secret)
@@ -103,13 +109,13 @@ fn example_different_payloads() -> Result<()> {
 
     // 字符串payload
     let simple_data = "Hello JWT World!";
-    let token1 = generate_token(&simple_data, JwtConfig::default())?;
+    let token1 = generate_token_default(&simple_data)?;
     let decoded_string: String = verify_token(&token1)?;
     println!("   字符串payload: {} -> {}", simple_data, decoded_string);
 
     // 数字payload
     let number_data = 42i32;
-    let token2 = generate_token(&number_data, JwtConfig::default())?;
+    let token2 = generate_token_default(&number_data)?;
     let decoded_number: i32 = verify_token(&token2)?;
     println!("   数字payload: {} -> {}", number_data, decoded_number);
 
@@ -131,7 +137,7 @@ fn example_different_payloads() -> Result<()> {
         metadata,
     };
 
-    let token3 = generate_token(&complex_data, JwtConfig::default())?;
+    let token3 = generate_token_default(&complex_data)?;
     let decoded_complex: ComplexData = verify_token(&token3)?;
     println!("   复杂结构体payload: {:?}", decoded_complex);
 
@@ -13,7 +13,7 @@ fn main() -> Result<()> {
     println!("   时间戳: {}", info.timestamp);
     println!("   工作者ID: {}", info.worker_id);
     println!("   序列号: {}", info.sequence);
-    println!("   生成时间: {}", info.generation_time_string(None));
+    println!("   生成时间: {}", info.generation_time_string());
 
     // 2. 使用自定义配置
     println!("\n2. 使用自定义配置:");
@@ -48,9 +48,10 @@ fn main() -> Result<()> {
     let id3 = manager_with_epoch.generate_id()?;
     let info3 = manager_with_epoch.parse_id(id3);
     println!("   生成的ID: {}", id3);
+    // 管理器解析时记录自身的纪元，无需再传入
     println!(
         "   生成时间（自定义纪元）: {}",
-        info3.generation_time_string(Some(custom_epoch))
+        info3.generation_time_string()
     );
 
     println!("\n=== 示例完成 ===");
@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+# 逐个 feature 组合编译并运行测试，保证按需引入时每种组合都可用
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+combos=(
+    ""
+    "config"
+    "token"
+    "snowflake"
+    "tracing-logs"
+    "logging"
+    "bootstrap"
+    "config,token"
+    "config-encryption"
+    "remote-config"
+    "ini,ron"
+    "cli"
+    "axum,anyhow,backtrace"
+    "axum,token"
+    "testing"
+    "asymmetric"
+    "async,token,snowflake"
+    "config,token,snowflake,tracing-logs,testing"
+)
+
+for features in "${combos[@]}"; do
+    echo "==> --no-default-features --features '${features}'"
+    cargo clippy --all-targets --no-default-features --features "${features}" -- -D warnings
+    cargo test --no-default-features --features "${features}"
+done
+
+echo "==> --all-features"
+cargo clippy --all-targets --all-features -- -D warnings
+cargo test --all-features
@@ -0,0 +1,206 @@
+//! clamber 调试 CLI：解析 token、解析 Snowflake ID、校验配置文件，便于运维排障。
+use clamber_core::config::ConfigBuilder;
+use clamber_core::snowflake::SnowflakeIdInfo;
+use clamber_core::token::{JwtConfig, JwtManager, decode_unverified};
+use clamber_core::{ClamberError, Result};
+use serde_json::{Value, json};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+用法: clamber [--json] <命令>
+
+命令:
+  token inspect <jwt>                    不验证签名，输出 header / claims / 过期状态
+  token verify --secret <secret> <jwt>   使用密钥验证 token 并输出 payload
+  id parse <snowflake_id> [--epoch <ms>] 解析 Snowflake ID 的时间、工作者与序列号
+  config check <file> [--env-prefix <p>] 加载配置文件并报告错误或键统计
+
+选项:
+  --json    以 JSON 格式输出
+  -h, --help  显示帮助";
+
+/// 命令执行结果：文本输出与 JSON 输出
+struct Report {
+    text: String,
+    json: Value,
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let json_output = take_flag(&mut args, "--json");
+
+    if args.is_empty() || take_flag(&mut args, "--help") || take_flag(&mut args, "-h") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match run(args) {
+        Ok(report) => {
+            if json_output {
+                println!("{}", json!({ "ok": true, "result": report.json }));
+            } else {
+                println!("{}", report.text);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            if json_output {
+                println!("{}", json!({ "ok": false, "error": err }));
+            } else {
+                eprintln!("错误: {}", err.display_with_code());
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(mut args: Vec<String>) -> Result<Report> {
+    let command: Vec<&str> = args.iter().take(2).map(String::as_str).collect();
+    match command.as_slice() {
+        ["token", "inspect"] => token_inspect(&positional(&args, 2, "<jwt>")?),
+        ["token", "verify"] => {
+            let secret = take_option(&mut args, "--secret")?
+                .ok_or_else(|| usage_error("token verify 需要 --secret <secret>"))?;
+            token_verify(&secret, &positional(&args, 2, "<jwt>")?)
+        }
+        ["id", "parse"] => {
+            let epoch = take_option(&mut args, "--epoch")?
+                .map(|v| parse_number(&v, "--epoch"))
+                .transpose()?;
+            let id = parse_number(&positional(&args, 2, "<snowflake_id>")?, "<snowflake_id>")?;
+            Ok(id_parse(id, epoch))
+        }
+        ["config", "check"] => {
+            let env_prefix = take_option(&mut args, "--env-prefix")?;
+            config_check(&positional(&args, 2, "<file>")?, env_prefix)
+        }
+        _ => Err(usage_error(&format!(
+            "未知命令: {}\n\n{}",
+            args.join(" "),
+            USAGE
+        ))),
+    }
+}
+
+fn token_inspect(token: &str) -> Result<Report> {
+    let decoded = decode_unverified(token)?;
+    let status = match decoded.is_expired() {
+        Some(true) => "已过期",
+        Some(false) => "未过期",
+        None => "无 exp 声明",
+    };
+
+    let text = format!(
+        "header: {}\nclaims: {}\n过期状态: {}（签名未验证）",
+        decoded.header,
+        serde_json::to_string_pretty(&decoded.claims)?,
+        status
+    );
+    let json = json!({
+        "header": decoded.header,
+        "claims": decoded.claims,
+        "expires_at": decoded.expires_at(),
+        "expired": decoded.is_expired(),
+    });
+    Ok(Report { text, json })
+}
+
+fn token_verify(secret: &str, token: &str) -> Result<Report> {
+    let manager = JwtManager::new(JwtConfig::with_secret(secret));
+    let payload: Value = manager.verify_token(token)?;
+    Ok(Report {
+        text: format!("验证通过\npayload: {}", payload),
+        json: json!({ "valid": true, "payload": payload }),
+    })
+}
+
+fn id_parse(id: u64, epoch: Option<u64>) -> Report {
+    let mut info = SnowflakeIdInfo::from_id(id);
+    if let Some(epoch) = epoch {
+        info.epoch = epoch;
+    }
+    let time = info.generation_time_string();
+
+    Report {
+        text: format!(
+            "ID: {}\n生成时间: {} (UTC)\n工作者ID: {}\n序列号: {}",
+            info.id, time, info.worker_id, info.sequence
+        ),
+        json: json!({
+            "id": info.id,
+            "timestamp_ms": info.generation_time(),
+            "time": time,
+            "worker_id": info.worker_id,
+            "sequence": info.sequence,
+        }),
+    }
+}
+
+fn config_check(file: &str, env_prefix: Option<String>) -> Result<Report> {
+    let mut builder = ConfigBuilder::new().add_file(file, None);
+    if let Some(prefix) = env_prefix {
+        builder = builder.with_env_prefix(prefix);
+    }
+    let value: Value = builder.build()?;
+
+    let top_level = value.as_object().map_or(0, |map| map.len());
+    let leaves = count_leaves(&value);
+    Ok(Report {
+        text: format!(
+            "配置有效: {}\n顶层键: {}\n叶子键: {}",
+            file, top_level, leaves
+        ),
+        json: json!({ "file": file, "top_level_keys": top_level, "leaf_keys": leaves }),
+    })
+}
+
+fn count_leaves(value: &Value) -> usize {
+    match value {
+        Value::Object(map) => map.values().map(count_leaves).sum(),
+        Value::Array(items) => items.iter().map(count_leaves).sum(),
+        _ => 1,
+    }
+}
+
+/// 取出布尔开关，返回是否出现过
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let before = args.len();
+    args.retain(|a| a != flag);
+    args.len() != before
+}
+
+/// 取出 `--name value` 形式的选项
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let Some(pos) = args.iter().position(|a| a == name) else {
+        return Ok(None);
+    };
+    if pos + 1 >= args.len() {
+        return Err(usage_error(&format!("{} 缺少参数值", name)));
+    }
+    let value = args.remove(pos + 1);
+    args.remove(pos);
+    Ok(Some(value))
+}
+
+fn positional(args: &[String], index: usize, name: &str) -> Result<String> {
+    match &args[index.min(args.len())..] {
+        [value] => Ok(value.clone()),
+        [] => Err(usage_error(&format!("缺少参数 {}", name))),
+        extra => Err(usage_error(&format!(
+            "多余的参数: {}",
+            extra[1..].join(" ")
+        ))),
+    }
+}
+
+fn parse_number(value: &str, name: &str) -> Result<u64> {
+    value
+        .parse()
+        .map_err(|e| usage_error(&format!("{} 不是有效的数字 {}: {}", name, value, e)))
+}
+
+fn usage_error(message: &str) -> ClamberError {
+    ClamberError::Other {
+        message: message.to_string(),
+    }
+}
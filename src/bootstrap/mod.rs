@@ -0,0 +1,228 @@
+//! 应用引导模块：从单一配置一次性初始化日志、JWT 与 Snowflake。
+//!
+//! 配置文件中的 `log:`、`jwt:`、`snowflake:` 三个段分别对应各子系统，缺失的段使用默认值。
+//! `jwt.secret` 必须配置：启用 `strict-default`（默认启用）时缺失返回 `JwtKeyError`，
+//! 关闭后回退到内置的不安全密钥并记录 error 日志：
+//!
+//! ```yaml
+//! log:
+//!   dir: logs
+//!   console_level: info
+//!   file_level: info
+//!   format: compact        # text / compact / json
+//!   timezone: "+08:00"     # utc / local / ±HH:MM
+//! jwt:
+//!   secret: change-me
+//!   expire_days: 7         # 或 expire_minutes: 15（优先）
+//!   algorithm: HS256       # HS256 / HS384 / HS512
+//!   issuer: my-service     # 可选：iss / aud / sub / not_before_secs / leeway_secs
+//! snowflake:
+//!   worker_id: 1
+//! ```
+use crate::config::{ConfigBuilder, get_config_paths};
+use crate::error::{ClamberError, Result};
+use crate::snowflake::{SnowflakeConfig, SnowflakeManager};
+use crate::token::{JwtAlgorithm, JwtConfig, JwtManager};
+use crate::tracing_logs::{self, LogConfig, LoggerHandle};
+use chrono::Duration;
+use config::Config;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// 配置中的 `jwt:` 段
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct JwtSection {
+    secret: Option<String>,
+    fallback_secrets: Option<Vec<String>>,
+    expire_days: Option<i64>,
+    expire_minutes: Option<i64>,
+    algorithm: Option<JwtAlgorithm>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    subject: Option<String>,
+    not_before_secs: Option<i64>,
+    leeway_secs: Option<i64>,
+    access_expire_minutes: Option<i64>,
+    refresh_expire_days: Option<i64>,
+    flat_claims: Option<bool>,
+    /// payload 字节数上限，0 表示不限制
+    max_payload_bytes: Option<usize>,
+    compress_payload: Option<bool>,
+    key_id: Option<String>,
+}
+
+impl JwtSection {
+    /// 未配置（或为空）`secret`，将使用内置密钥
+    fn uses_builtin_secret(&self) -> bool {
+        self.secret.as_deref().is_none_or(str::is_empty)
+    }
+
+    fn into_jwt_config(self) -> Result<JwtConfig> {
+        #[cfg(feature = "strict-default")]
+        if self.uses_builtin_secret() {
+            return Err(ClamberError::JwtKeyError {
+                details: "配置中缺少 jwt.secret，拒绝使用内置密钥".to_string(),
+                source: None,
+            });
+        }
+        let default = JwtConfig::default();
+        let config = JwtConfig {
+            secret: self
+                .secret
+                .filter(|secret| !secret.is_empty())
+                .unwrap_or(default.secret),
+            fallback_secrets: self.fallback_secrets.unwrap_or_default(),
+            expire: match (self.expire_minutes, self.expire_days) {
+                (Some(minutes), _) => Duration::minutes(minutes),
+                (None, Some(days)) => Duration::days(days),
+                (None, None) => default.expire,
+            },
+            max_expire: default.max_expire,
+            algorithm: self.algorithm.unwrap_or(default.algorithm),
+            issuer: self.issuer,
+            audience: self.audience,
+            subject: self.subject,
+            not_before_secs: self.not_before_secs,
+            leeway_secs: default.leeway_secs,
+            max_token_age: default.max_token_age,
+            access_expire_minutes: self
+                .access_expire_minutes
+                .unwrap_or(default.access_expire_minutes),
+            refresh_expire_days: self
+                .refresh_expire_days
+                .unwrap_or(default.refresh_expire_days),
+            flat_claims: self.flat_claims.unwrap_or(default.flat_claims),
+            max_payload_bytes: match self.max_payload_bytes {
+                Some(0) => None,
+                Some(limit) => Some(limit),
+                None => default.max_payload_bytes,
+            },
+            compress_payload: self.compress_payload.unwrap_or(default.compress_payload),
+            key_id: self.key_id,
+        };
+        match self.leeway_secs {
+            Some(secs) => config.leeway_secs(secs),
+            None => Ok(config),
+        }
+    }
+}
+
+/// 引导完成后的应用上下文
+///
+/// 持有日志句柄，AppContext 被 drop 后缓冲中的日志会被刷新，应在 main 中保持其存活。
+pub struct AppContext {
+    config: Config,
+    jwt: JwtManager,
+    snowflake: SnowflakeManager,
+    logger: LoggerHandle,
+}
+
+impl AppContext {
+    /// 按 [`crate::config::auto_load_config`] 的路径规则查找 `{app_name}.yaml` 等配置文件并初始化
+    pub fn init(app_name: &str) -> Result<Self> {
+        let builder = get_config_paths(app_name).into_iter().fold(
+            ConfigBuilder::new().ignore_missing_files(true),
+            |b, path| b.add_file(path, None),
+        );
+        Self::init_with(app_name, builder)
+    }
+
+    /// 使用自定义的 ConfigBuilder 初始化
+    ///
+    /// `app_name` 作为日志文件名前缀。全局日志只能初始化一次，重复调用返回 `LoggingError`。
+    pub fn init_with(app_name: &str, builder: ConfigBuilder) -> Result<Self> {
+        let config = builder.build_raw()?;
+        let mut missing = Vec::new();
+
+        // 先校验 JWT 配置：失败时不初始化全局日志，调用方修正配置后可以重试
+        let jwt: JwtSection = section(&config, "jwt", &mut missing)?;
+        let builtin_secret = jwt.uses_builtin_secret();
+        let jwt = JwtManager::new(jwt.into_jwt_config()?);
+
+        let log_config = match config.get::<config::Value>("log") {
+            Err(config::ConfigError::NotFound(_)) => {
+                missing.push("log");
+                LogConfig::default()
+            }
+            _ => LogConfig::from_config_section(&config, "log")?,
+        };
+        let log_dir = log_config
+            .log_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("logs"));
+        let logger = tracing_logs::init_logger(app_name, &log_dir, &log_config)?;
+        if builtin_secret {
+            tracing::error!(
+                "配置中缺少 jwt.secret，使用内置的不安全密钥签发 token；请配置 jwt.secret 或启用 strict-default"
+            );
+        }
+
+        // 工作者ID、位布局与数据中心ID在创建管理器时统一校验
+        let snowflake: Option<SnowflakeConfig> = section(&config, "snowflake", &mut missing)?;
+        let snowflake = SnowflakeManager::new(snowflake.unwrap_or_default())?;
+
+        for key in missing {
+            tracing::warn!(section = key, "配置中缺少该段，使用默认值");
+        }
+
+        Ok(Self {
+            config,
+            jwt,
+            snowflake,
+            logger,
+        })
+    }
+
+    /// 原始配置对象，用于读取业务自定义的配置段
+    pub fn config_raw(&self) -> &Config {
+        &self.config
+    }
+
+    /// 将整个配置反序列化为业务配置类型
+    pub fn config<T>(&self) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.config
+            .clone()
+            .try_deserialize()
+            .map_err(|e| ClamberError::ConfigParseError {
+                details: e.to_string(),
+                source: Some(e.into()),
+            })
+    }
+
+    /// 日志句柄，可在退出前调用 [`LoggerHandle::flush`]
+    pub fn logger(&self) -> &LoggerHandle {
+        &self.logger
+    }
+
+    /// 由 `jwt:` 段构造的 JWT 管理器
+    pub fn jwt(&self) -> &JwtManager {
+        &self.jwt
+    }
+
+    /// 由 `snowflake:` 段构造的 ID 生成器
+    pub fn snowflake(&self) -> &SnowflakeManager {
+        &self.snowflake
+    }
+}
+
+/// 读取配置段；缺失时记录到 `missing` 并返回默认值
+fn section<T>(config: &Config, key: &'static str, missing: &mut Vec<&'static str>) -> Result<T>
+where
+    T: for<'de> Deserialize<'de> + Default,
+{
+    match config.get::<T>(key) {
+        Ok(value) => Ok(value),
+        Err(config::ConfigError::NotFound(_)) => {
+            missing.push(key);
+            Ok(T::default())
+        }
+        Err(e) => Err(ClamberError::ConfigParseError {
+            details: format!("配置段 {} 解析失败: {}", key, e),
+            source: Some(e.into()),
+        }),
+    }
+}
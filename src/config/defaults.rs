@@ -0,0 +1,182 @@
+//! 结构体默认值：把可序列化的默认配置展开为逐个叶子键的默认值，文件与环境变量仍可单独覆盖其中的字段。
+use super::ConfigBuilder;
+use crate::error::{ClamberError, Result, ResultExt};
+use serde::Serialize;
+use serde_json::Value;
+
+impl ConfigBuilder {
+    /// 以结构体（通常是配置类型的 `Default` 值）的每个叶子字段作为默认值
+    ///
+    /// 等价于对每个叶子调用 [`ConfigBuilder::with_default`]，展开规则见 [`ConfigBuilder::with_defaults_from_json`]。
+    pub fn with_default_struct<T: Serialize>(self, value: &T) -> Result<Self> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| ClamberError::SerializationError {
+                details: e.to_string(),
+                source: Some(e.into()),
+            })
+            .with_context(|| format!("序列化 {} 默认值时", std::any::type_name::<T>()))?;
+        self.with_defaults_from_json(value)
+    }
+
+    /// 以 JSON 对象的每个叶子作为默认值
+    ///
+    /// 嵌套对象（结构体、`HashMap`）按 `database.port` 形式的键路径展开，`null`（值为 `None` 的
+    /// `Option`）跳过，数组整体作为一个值；空对象注册为空表，使缺少该段的配置仍能反序列化。
+    /// 映射的键中不能含有 `.`。根不是对象时返回 [`ClamberError::ConfigValidationError`]。
+    pub fn with_defaults_from_json(mut self, value: Value) -> Result<Self> {
+        let Value::Object(object) = value else {
+            return Err(ClamberError::ConfigValidationError {
+                details: format!("默认值必须是对象，实际为 {}", value),
+            });
+        };
+        let mut leaves = Vec::new();
+        flatten(String::new(), Value::Object(object), &mut leaves);
+        for (key, value) in leaves {
+            let value = serde_json::from_value::<config::Value>(value)
+                .map_err(|e| ClamberError::ConfigParseError {
+                    details: e.to_string(),
+                    source: Some(e.into()),
+                })
+                .with_context(|| format!("转换默认值 {} 时", key))?;
+            self.defaults.insert(key, value);
+        }
+        Ok(self)
+    }
+}
+
+/// 展开为 `(键路径, 叶子值)`，根节点的键路径为空
+fn flatten(key: String, value: Value, leaves: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Null => {}
+        Value::Object(object) if !object.is_empty() => {
+            for (name, child) in object {
+                let path = if key.is_empty() {
+                    name
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                flatten(path, child, leaves);
+            }
+        }
+        // 根为空对象时没有需要注册的默认值
+        Value::Object(_) if key.is_empty() => {}
+        value => leaves.push((key, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{ConfigBuilder, ConfigFormat};
+    use crate::error::ClamberError;
+    use crate::testing::{EnvGuard, write_temp_config};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+        tags: Vec<String>,
+        proxy: Option<String>,
+        database: DatabaseConfig,
+        limits: HashMap<String, u32>,
+        extra: HashMap<String, String>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct DatabaseConfig {
+        host: String,
+        port: u16,
+        pool: PoolConfig,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct PoolConfig {
+        min: u32,
+        max: u32,
+    }
+
+    impl Default for AppConfig {
+        fn default() -> Self {
+            Self {
+                name: "app".to_string(),
+                port: 8080,
+                tags: vec!["a".to_string(), "b".to_string()],
+                proxy: None,
+                database: DatabaseConfig {
+                    host: "localhost".to_string(),
+                    port: 5432,
+                    pool: PoolConfig { min: 1, max: 10 },
+                },
+                limits: HashMap::from([("upload".to_string(), 10), ("download".to_string(), 20)]),
+                extra: HashMap::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_struct_overridden_by_file() {
+        let (_dir, path) = write_temp_config(
+            ConfigFormat::Yaml,
+            "database:\n  pool:\n    max: 50\nlimits:\n  upload: 99\n",
+        );
+        let config: AppConfig = ConfigBuilder::new()
+            .with_default_struct(&AppConfig::default())
+            .unwrap()
+            .add_file(&path, None)
+            .build()
+            .unwrap();
+
+        let mut expected = AppConfig::default();
+        expected.database.pool.max = 50;
+        expected.limits.insert("upload".to_string(), 99);
+        assert_eq!(config, expected);
+
+        // 未提供任何来源时与默认值一致，环境变量同样只覆盖单个字段
+        let _env = EnvGuard::new().set("CLAMBER_DEFAULTS_DATABASE__PORT", "6543");
+        let config: AppConfig = ConfigBuilder::new()
+            .with_default_struct(&AppConfig::default())
+            .unwrap()
+            .with_env_prefix("CLAMBER_DEFAULTS")
+            .build()
+            .unwrap();
+        assert_eq!(config.database.port, 6543);
+        assert_eq!(config.database.host, "localhost");
+        assert_eq!(config.tags, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_defaults_from_json() {
+        let config: AppConfig = ConfigBuilder::new()
+            .with_defaults_from_json(json!({
+                "name": "json",
+                "port": 9000,
+                "tags": [],
+                "proxy": "http://proxy:3128",
+                "database": {"host": "db", "port": 3306, "pool": {"min": 2, "max": 4}},
+                "limits": {},
+                "extra": {"region": "cn"},
+            }))
+            .unwrap()
+            .add_yaml_str("port: 9001\n")
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "json");
+        assert_eq!(config.port, 9001);
+        assert!(config.tags.is_empty());
+        assert_eq!(config.proxy.as_deref(), Some("http://proxy:3128"));
+        assert_eq!(config.database.pool.min, 2);
+        assert!(config.limits.is_empty());
+        assert_eq!(config.extra["region"], "cn");
+
+        let err = ConfigBuilder::new()
+            .with_defaults_from_json(json!([1, 2]))
+            .unwrap_err();
+        assert!(
+            matches!(err, ClamberError::ConfigValidationError { .. }),
+            "{:?}",
+            err
+        );
+    }
+}
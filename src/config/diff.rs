@@ -0,0 +1,212 @@
+//! 配置差异：分别构建新旧两组来源，在合并后的原始值树上逐键比对，便于发布前预览配置变更。
+use super::report::collect_leaves;
+use super::{ConfigBuilder, ConfigManager};
+use crate::error::{ClamberError, Result, ResultExt};
+use config::Config;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// 同一键路径的新旧值
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValueChange {
+    pub old: Value,
+    pub new: Value,
+}
+
+/// 两组配置来源之间的差异，见 [`ConfigManager::diff`]
+///
+/// 键路径形如 `database.host`，数组整体视为一个值。差异包含原始值（含密码等敏感信息），
+/// 输出到日志前注意脱敏。
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConfigDiff {
+    /// 仅新配置中存在的键
+    pub added: BTreeMap<String, Value>,
+    /// 仅旧配置中存在的键
+    pub removed: BTreeMap<String, Value>,
+    /// 两侧都存在但值不同的键
+    pub changed: BTreeMap<String, ValueChange>,
+}
+
+impl ConfigDiff {
+    fn between(old: &Config, new: &Config) -> Result<Self> {
+        let mut old = leaves(old)?;
+        let mut diff = ConfigDiff::default();
+        for (key, new_value) in leaves(new)? {
+            match old.remove(&key) {
+                Some(old_value) if old_value == new_value => {}
+                Some(old_value) => {
+                    diff.changed.insert(
+                        key,
+                        ValueChange {
+                            old: old_value,
+                            new: new_value,
+                        },
+                    );
+                }
+                None => {
+                    diff.added.insert(key, new_value);
+                }
+            }
+        }
+        diff.removed = old;
+        Ok(diff)
+    }
+
+    /// 新旧配置完全一致
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// 每行一个键，按键路径排序：`+ 键 = 值`、`- 键 = 值`、`~ 键: 旧值 -> 新值`
+    pub fn to_string_pretty(&self) -> String {
+        let mut lines = BTreeMap::new();
+        for (key, value) in &self.added {
+            lines.insert(key, format!("+ {} = {}", key, value));
+        }
+        for (key, value) in &self.removed {
+            lines.insert(key, format!("- {} = {}", key, value));
+        }
+        for (key, change) in &self.changed {
+            lines.insert(key, format!("~ {}: {} -> {}", key, change.old, change.new));
+        }
+        let mut output = String::new();
+        for line in lines.into_values() {
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+/// 叶子键路径到值的映射
+fn leaves(config: &Config) -> Result<BTreeMap<String, Value>> {
+    let mut values = Vec::new();
+    collect_leaves(&config.cache, String::new(), &mut |key, value| {
+        values.push((key, value.clone()))
+    });
+    values
+        .into_iter()
+        .map(|(key, value)| {
+            let value = value
+                .try_deserialize::<Value>()
+                .map_err(|e| ClamberError::ConfigParseError {
+                    details: e.to_string(),
+                    source: Some(e.into()),
+                })
+                .with_context(|| format!("读取配置键 {} 时", key))?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+impl ConfigManager {
+    /// 比较两组配置来源合并后的结果，用于发布前预览配置变更
+    ///
+    /// 差异在原始值树上计算，`T` 中未声明的键同样会列出；两侧都须能反序列化为 `T`，
+    /// 否则返回对应的解析错误。
+    pub fn diff<T>(old_sources: ConfigBuilder, new_sources: ConfigBuilder) -> Result<ConfigDiff>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let old = old_sources.build_raw().context("构建旧配置")?;
+        let new = new_sources.build_raw().context("构建新配置")?;
+        super::deserialize::<T>(old.clone()).context("校验旧配置")?;
+        super::deserialize::<T>(new.clone()).context("校验新配置")?;
+        ConfigDiff::between(&old, &new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFormat;
+    use crate::testing::write_temp_config;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct AppConfig {
+        name: String,
+        database: DatabaseConfig,
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct DatabaseConfig {
+        host: String,
+        port: u16,
+    }
+
+    const OLD: &str = "name: app\ndatabase:\n  host: db-1\n  port: 5432\nextra: [a, b]\n";
+
+    #[test]
+    fn test_diff_reports_changed_nested_key() {
+        let (_old_dir, old_path) = write_temp_config(ConfigFormat::Yaml, OLD);
+        let (_new_dir, new_path) =
+            write_temp_config(ConfigFormat::Yaml, &OLD.replace("db-1", "db-2"));
+
+        let diff = ConfigManager::diff::<AppConfig>(
+            ConfigBuilder::new().add_file(&old_path, None),
+            ConfigBuilder::new().add_file(&new_path, None),
+        )
+        .unwrap();
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed["database.host"],
+            ValueChange {
+                old: json!("db-1"),
+                new: json!("db-2"),
+            }
+        );
+        assert_eq!(
+            diff.to_string_pretty(),
+            "~ database.host: \"db-1\" -> \"db-2\"\n"
+        );
+        assert_eq!(
+            serde_json::to_value(&diff).unwrap(),
+            json!({
+                "added": {},
+                "removed": {},
+                "changed": {"database.host": {"old": "db-1", "new": "db-2"}},
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_unknown_keys() {
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, OLD);
+        let same = ConfigManager::diff::<AppConfig>(
+            ConfigBuilder::new().add_file(&path, None),
+            ConfigBuilder::new().add_file(&path, None),
+        )
+        .unwrap();
+        assert!(same.is_empty());
+        assert_eq!(same.to_string_pretty(), "");
+
+        let diff = ConfigManager::diff::<AppConfig>(
+            ConfigBuilder::new().add_file(&path, None),
+            ConfigBuilder::new().add_yaml_str(
+                "name: app\ndatabase:\n  host: db-1\n  port: 5432\nfeature:\n  beta: true\n",
+            ),
+        )
+        .unwrap();
+        assert_eq!(diff.added["feature.beta"], json!(true));
+        assert_eq!(diff.removed["extra"], json!(["a", "b"]));
+        assert!(diff.changed.is_empty());
+        assert_eq!(
+            diff.to_string_pretty(),
+            "- extra = [\"a\",\"b\"]\n+ feature.beta = true\n"
+        );
+
+        // 新配置无法反序列化为 T 时返回错误
+        let err = ConfigManager::diff::<AppConfig>(
+            ConfigBuilder::new().add_file(&path, None),
+            ConfigBuilder::new().add_yaml_str("name: app\n"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("校验新配置"), "{}", err);
+    }
+}
@@ -0,0 +1,122 @@
+//! `.env` 文件解析：`KEY=VALUE` 行，支持注释、空行、`export` 前缀与单双引号。
+use crate::error::{ClamberError, Result};
+use config::Map;
+use std::path::Path;
+
+/// 读取 `.env` 文件；文件不存在时 `ignore_missing` 为 true 返回空表
+pub(super) fn load(path: &Path, ignore_missing: bool) -> Result<Map<String, String>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            if ignore_missing {
+                return Ok(Map::new());
+            }
+            return Err(ClamberError::ConfigFileNotFoundError {
+                path: path.display().to_string(),
+                source: Some(e.into()),
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+    parse(&content).map_err(|details| ClamberError::ConfigParseError {
+        details: format!("{}: {}", path.display(), details),
+        source: None,
+    })
+}
+
+/// 解析 `.env` 内容，错误信息带行号
+pub(super) fn parse(content: &str) -> std::result::Result<Map<String, String>, String> {
+    let mut vars = Map::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("第 {} 行缺少 '='", index + 1))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("第 {} 行缺少变量名", index + 1));
+        }
+        let value = parse_value(value.trim()).map_err(|e| format!("第 {} 行{}", index + 1, e))?;
+        vars.insert(key.to_string(), value);
+    }
+    Ok(vars)
+}
+
+fn parse_value(raw: &str) -> std::result::Result<String, String> {
+    let mut chars = raw.chars();
+    match chars.next() {
+        // 双引号：支持 \n \t \" \\ 转义
+        Some('"') => {
+            let mut value = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => return Ok(value),
+                    '\\' => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(other) => value.push(other),
+                        None => break,
+                    },
+                    c => value.push(c),
+                }
+            }
+            Err("双引号未闭合".to_string())
+        }
+        // 单引号：原样保留
+        Some('\'') => chars
+            .as_str()
+            .split_once('\'')
+            .map(|(value, _)| value.to_string())
+            .ok_or_else(|| "单引号未闭合".to_string()),
+        // 无引号：` #` 之后为行尾注释
+        _ => {
+            let value = match raw.find(" #") {
+                Some(pos) => &raw[..pos],
+                None => raw,
+            };
+            Ok(value.trim_end().to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tricky_lines() {
+        let vars = parse(
+            r#"
+# comment
+FOO="bar baz"
+EMPTY=
+export EXPORTED=1
+SINGLE='a "quoted" #value'
+ESCAPED="line\nnext \"q\""
+  SPACED = value with spaces # trailing comment
+URL=http://host/#fragment
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(vars["FOO"], "bar baz");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars["EXPORTED"], "1");
+        assert_eq!(vars["SINGLE"], "a \"quoted\" #value");
+        assert_eq!(vars["ESCAPED"], "line\nnext \"q\"");
+        assert_eq!(vars["SPACED"], "value with spaces");
+        assert_eq!(vars["URL"], "http://host/#fragment");
+        assert_eq!(vars.len(), 7);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("FOO").unwrap_err().contains("第 1 行"));
+        assert!(parse("\nFOO=\"open").unwrap_err().contains("第 2 行"));
+        assert!(parse("=value").is_err());
+    }
+}
@@ -0,0 +1,249 @@
+//! 加密配置值：`enc:v1:<base64(12 字节 nonce + 密文 + 16 字节 tag)>`，算法为 AES-256-GCM。
+//!
+//! 构建时遍历合并后的配置树，将全部加密值替换为明文；配置中出现加密值但未设置密钥时报错。
+use crate::error::{ClamberError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use config::{Value, ValueKind};
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// 加密值前缀
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// 解密使用的密钥来源
+#[derive(Clone)]
+pub(super) enum KeySource {
+    Bytes([u8; 32]),
+    /// 环境变量名，值为 base64 编码的 32 字节密钥，构建时读取
+    Env(String),
+}
+
+impl KeySource {
+    fn resolve(&self) -> Result<[u8; 32]> {
+        match self {
+            KeySource::Bytes(key) => Ok(*key),
+            KeySource::Env(name) => {
+                let invalid = |details: String| ClamberError::ConfigValidationError { details };
+                let value = std::env::var(name)
+                    .map_err(|_| invalid(format!("配置密钥环境变量 {} 未设置", name)))?;
+                STANDARD
+                    .decode(value.trim())
+                    .ok()
+                    .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                    .ok_or_else(|| {
+                        invalid(format!(
+                            "配置密钥环境变量 {} 必须为 base64 编码的 32 字节密钥",
+                            name
+                        ))
+                    })
+            }
+        }
+    }
+}
+
+// 不在 Debug 输出中暴露密钥
+impl std::fmt::Debug for KeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeySource::Bytes(_) => f.write_str("Bytes(..)"),
+            KeySource::Env(name) => f.debug_tuple("Env").field(name).finish(),
+        }
+    }
+}
+
+/// 加密配置值，生成可直接写入配置文件的 `enc:v1:...` 字符串
+///
+/// 每次调用使用随机 nonce，同一明文的结果不同。
+pub fn encrypt_value(plaintext: &str, key: &[u8; 32]) -> Result<String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| ClamberError::Other {
+            message: "生成随机 nonce 失败".to_string(),
+        })?;
+    let mut data = plaintext.as_bytes().to_vec();
+    cipher(key)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| ClamberError::Other {
+            message: "加密配置值失败".to_string(),
+        })?;
+    let mut output = nonce.to_vec();
+    output.extend_from_slice(&data);
+    Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(output)))
+}
+
+fn cipher(key: &[u8; 32]) -> LessSafeKey {
+    // 长度固定为 32 字节，AES-256 密钥必然合法
+    LessSafeKey::new(UnboundKey::new(&AES_256_GCM, key).expect("AES-256 密钥长度为 32 字节"))
+}
+
+/// 遍历配置树，解密全部加密值；`key` 为 `None` 且存在加密值时返回错误
+pub(super) fn apply(value: &mut Value, key: Option<&KeySource>) -> Result<()> {
+    let mut resolved = None;
+    walk(value, "", &mut |path, text| {
+        let key = match &resolved {
+            Some(key) => key,
+            None => {
+                let source = key.ok_or_else(|| ClamberError::ConfigValidationError {
+                    details: format!(
+                        "{} 为加密值，但未设置解密密钥（with_encryption_key / with_encryption_key_from_env）",
+                        path
+                    ),
+                })?;
+                resolved.insert(source.resolve()?)
+            }
+        };
+        decrypt(text, key).ok_or_else(|| ClamberError::ConfigValidationError {
+            details: format!("{} 解密失败：密钥错误或数据被篡改", path),
+        })
+    })
+}
+
+fn walk(
+    value: &mut Value,
+    key: &str,
+    decrypt: &mut dyn FnMut(&str, &str) -> Result<String>,
+) -> Result<()> {
+    match &mut value.kind {
+        ValueKind::String(text) if text.starts_with(ENCRYPTED_PREFIX) => {
+            *text = decrypt(key, &text[ENCRYPTED_PREFIX.len()..])?;
+        }
+        ValueKind::Table(table) => {
+            for (name, child) in table.iter_mut() {
+                let child_key = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                walk(child, &child_key, decrypt)?;
+            }
+        }
+        ValueKind::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                walk(item, &format!("{}[{}]", key, index), decrypt)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn decrypt(encoded: &str, key: &[u8; 32]) -> Option<String> {
+    let data = STANDARD.decode(encoded).ok()?;
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+    let mut buffer = ciphertext.to_vec();
+    let plaintext = cipher(key)
+        .open_in_place(nonce, Aad::empty(), &mut buffer)
+        .ok()?;
+    String::from_utf8(plaintext.to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use crate::testing::EnvGuard;
+    use serde::Deserialize;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    #[derive(Debug, Deserialize)]
+    struct AppConfig {
+        name: String,
+        database: Database,
+        tokens: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Database {
+        password: String,
+    }
+
+    fn encrypted_yaml() -> String {
+        format!(
+            "name: svc\ndatabase:\n  password: \"{}\"\ntokens:\n  - plain\n  - \"{}\"\n",
+            encrypt_value("s3cret", &KEY).unwrap(),
+            encrypt_value("令牌", &KEY).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_round_trip_nested_and_arrays() {
+        let first = encrypt_value("s3cret", &KEY).unwrap();
+        assert!(first.starts_with(ENCRYPTED_PREFIX));
+        assert_ne!(first, encrypt_value("s3cret", &KEY).unwrap());
+
+        let config: AppConfig = ConfigBuilder::new()
+            .add_yaml_str(&encrypted_yaml())
+            .with_encryption_key(&KEY)
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "svc");
+        assert_eq!(config.database.password, "s3cret");
+        assert_eq!(config.tokens, ["plain", "令牌"]);
+
+        let env = EnvGuard::new().set("CLAMBER_TEST_CONFIG_KEY", &STANDARD.encode(KEY));
+        let config: AppConfig = ConfigBuilder::new()
+            .add_yaml_str(&encrypted_yaml())
+            .with_encryption_key_from_env("CLAMBER_TEST_CONFIG_KEY")
+            .build()
+            .unwrap();
+        assert_eq!(config.database.password, "s3cret");
+
+        let _env = env.set("CLAMBER_TEST_CONFIG_KEY", "too-short");
+        let err = ConfigBuilder::new()
+            .add_yaml_str(&encrypted_yaml())
+            .with_encryption_key_from_env("CLAMBER_TEST_CONFIG_KEY")
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("32 字节"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_key_wrong_key_and_tampering() {
+        let err = ConfigBuilder::new()
+            .add_yaml_str(&encrypted_yaml())
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(matches!(err, ClamberError::ConfigValidationError { .. }));
+        assert!(err.to_string().contains("未设置解密密钥"), "{}", err);
+
+        let err = ConfigBuilder::new()
+            .add_yaml_str(&encrypted_yaml())
+            .with_encryption_key(&[8; 32])
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(matches!(err, ClamberError::ConfigValidationError { .. }));
+        assert!(err.to_string().contains("解密失败"), "{}", err);
+
+        let mut tampered = encrypt_value("s3cret", &KEY).unwrap().into_bytes();
+        let last = tampered.len() - 3;
+        tampered[last] = if tampered[last] == b'A' { b'B' } else { b'A' };
+        let yaml = format!(
+            "name: svc\ndatabase:\n  password: \"{}\"\ntokens: []\n",
+            String::from_utf8(tampered).unwrap()
+        );
+        let err = ConfigBuilder::new()
+            .add_yaml_str(&yaml)
+            .with_encryption_key(&KEY)
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("database.password 解密失败"),
+            "{}",
+            err
+        );
+
+        // 没有加密值时无需密钥
+        let config: AppConfig = ConfigBuilder::new()
+            .add_yaml_str("name: svc\ndatabase:\n  password: p\ntokens: []\n")
+            .build()
+            .unwrap();
+        assert_eq!(config.database.password, "p");
+    }
+}
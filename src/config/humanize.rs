@@ -0,0 +1,454 @@
+//! 配置结构体中可读的时长与字节数：`"1h30m"`、`"250ms"`、`"512KB"`、`"2GiB"`
+//!
+//! 通过 serde 的 `with` 属性使用，数值仍按旧格式解析（时长为秒，字节数为字节）：
+//!
+//! ```
+//! use serde::Deserialize;
+//! use std::time::Duration;
+//!
+//! #[derive(Deserialize)]
+//! struct ServerConfig {
+//!     #[serde(with = "clamber_core::config::humanize::duration")]
+//!     timeout: Duration,
+//!     #[serde(with = "clamber_core::config::humanize::bytes")]
+//!     max_body: u64,
+//! }
+//! ```
+use crate::error::{ClamberError, Result};
+use std::time::Duration;
+
+/// 时长单位及对应的纳秒数，按可读格式输出时从大到小尝试
+const DURATION_UNITS: &[(&str, u64)] = &[
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// 字节单位（不区分大小写）；KB/MB 等为 1000 进制，KiB/MiB 等为 1024 进制
+const BYTE_UNITS: &[(&str, u64)] = &[
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+];
+
+/// 解析时长：`"90"`（秒）、`"1.5s"`、`"1h30m"`、`"1h 30m 15s"`，单位为 d / h / m / s / ms / us / ns
+///
+/// 格式错误时返回包含原始字符串的 [`ClamberError::ConfigParseError`]。
+pub fn parse_duration(text: &str) -> Result<Duration> {
+    duration_from_str(text).map_err(parse_error)
+}
+
+fn duration_from_str(text: &str) -> std::result::Result<Duration, String> {
+    let invalid = |reason: &str| format!("无效的时长 \"{}\": {}", text, reason);
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Err(invalid("不能为空"));
+    }
+    if let Ok(secs) = trimmed.parse::<f64>() {
+        return seconds(secs).ok_or_else(|| invalid("必须为非负数"));
+    }
+
+    let mut nanos: u128 = 0;
+    for (number, unit) in
+        segments(trimmed).ok_or_else(|| invalid("格式应为 <数字><单位>，如 1h30m"))?
+    {
+        let (_, factor) = DURATION_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .ok_or_else(|| invalid(&format!("未知单位 {}，可用 d/h/m/s/ms/us/ns", unit)))?;
+        nanos += scale(number, *factor).ok_or_else(|| invalid("数值无效或超出范围"))?;
+    }
+    let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| invalid("超出范围"))?;
+    Ok(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
+
+/// 按最大的整单位输出时长，如 `1h30m`、`250ms`；零为 `0s`
+pub fn format_duration(duration: Duration) -> String {
+    let mut nanos = duration.as_nanos();
+    if nanos == 0 {
+        return "0s".to_string();
+    }
+    let mut output = String::new();
+    for (unit, factor) in DURATION_UNITS.iter().filter(|(unit, _)| *unit != "µs") {
+        let factor = *factor as u128;
+        if nanos >= factor {
+            output.push_str(&format!("{}{}", nanos / factor, unit));
+            nanos %= factor;
+        }
+    }
+    output
+}
+
+/// 解析字节数：`"1024"`、`"512KB"`、`"1.5 GiB"`，单位不区分大小写
+///
+/// 小数结果向下取整；格式错误时返回包含原始字符串的 [`ClamberError::ConfigParseError`]。
+pub fn parse_bytes(text: &str) -> Result<u64> {
+    bytes_from_str(text).map_err(parse_error)
+}
+
+fn bytes_from_str(text: &str) -> std::result::Result<u64, String> {
+    let invalid = |reason: &str| format!("无效的字节数 \"{}\": {}", text, reason);
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Err(invalid("不能为空"));
+    }
+    let split = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+    let unit = unit.trim().to_ascii_lowercase();
+    let factor = if unit.is_empty() {
+        1
+    } else {
+        BYTE_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, factor)| *factor)
+            .ok_or_else(|| {
+                invalid(&format!(
+                    "未知单位 {}，可用 B/KB/MB/GB/TB/KiB/MiB/GiB/TiB",
+                    unit
+                ))
+            })?
+    };
+    let bytes = scale(number, factor).ok_or_else(|| invalid("数值无效或超出范围"))?;
+    u64::try_from(bytes).map_err(|_| invalid("超出范围"))
+}
+
+/// 能整除时使用最大的 1024 进制单位，如 `2GiB`；否则输出字节数
+pub fn format_bytes(bytes: u64) -> String {
+    BYTE_UNITS
+        .iter()
+        .rev()
+        .filter(|(unit, _)| unit.ends_with("ib"))
+        .find(|(_, factor)| bytes != 0 && bytes.is_multiple_of(*factor))
+        .map(|(unit, factor)| {
+            let unit = format!("{}iB", unit[..1].to_ascii_uppercase());
+            format!("{}{}", bytes / factor, unit)
+        })
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+/// `Duration` 字段的 serde 适配：`#[serde(with = "clamber_core::config::humanize::duration")]`
+///
+/// 接受整数或小数（秒）以及 [`parse_duration`] 支持的字符串，序列化为 [`format_duration`] 的格式。
+pub mod duration {
+    use super::{duration_from_str, format_duration, seconds};
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_duration(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        deserializer.deserialize_any(DurationVisitor)
+    }
+
+    struct DurationVisitor;
+
+    impl Visitor<'_> for DurationVisitor {
+        type Value = Duration;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("秒数或时长字符串（如 \"1h30m\"、\"250ms\"）")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Duration, E> {
+            Ok(Duration::from_secs(value))
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Duration, E> {
+            u64::try_from(value)
+                .map(Duration::from_secs)
+                .map_err(|_| E::custom(format!("无效的时长 {}: 必须为非负数", value)))
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<Duration, E> {
+            seconds(value).ok_or_else(|| E::custom(format!("无效的时长 {}: 必须为非负数", value)))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Duration, E> {
+            duration_from_str(value).map_err(E::custom)
+        }
+    }
+}
+
+/// 字节数（`u64`）字段的 serde 适配：`#[serde(with = "clamber_core::config::humanize::bytes")]`
+///
+/// 接受整数以及 [`parse_bytes`] 支持的字符串，序列化为 [`format_bytes`] 的格式。
+pub mod bytes {
+    use super::{bytes_from_str, format_bytes};
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_bytes(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_any(BytesVisitor)
+    }
+
+    struct BytesVisitor;
+
+    impl Visitor<'_> for BytesVisitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("字节数或带单位的字符串（如 \"512KB\"、\"2GiB\"）")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+            u64::try_from(value)
+                .map_err(|_| E::custom(format!("无效的字节数 {}: 必须为非负数", value)))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+            bytes_from_str(value).map_err(E::custom)
+        }
+    }
+}
+
+fn parse_error(details: String) -> ClamberError {
+    ClamberError::ConfigParseError {
+        details,
+        source: None,
+    }
+}
+
+fn seconds(secs: f64) -> Option<Duration> {
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// 拆分 `1h 30.5m` 为 `[("1", "h"), ("30.5", "m")]`
+fn segments(text: &str) -> Option<Vec<(&str, &str)>> {
+    let mut parts = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        let number_end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+        let (number, tail) = rest.split_at(number_end);
+        let unit_end = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.' || c.is_whitespace())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_end);
+        if number.is_empty() || unit.is_empty() {
+            return None;
+        }
+        parts.push((number, unit));
+        rest = tail.trim_start();
+    }
+    Some(parts)
+}
+
+/// 十进制数（可含小数）乘以 `factor`，小数部分向下取整；不使用浮点，避免精度误差
+fn scale(number: &str, factor: u64) -> Option<u128> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let mut result = whole.checked_mul(factor as u128)?;
+    let mut denominator: u128 = 1;
+    let mut numerator: u128 = 0;
+    // 超过 19 位的小数对结果没有影响
+    for digit in fraction.chars().take(19) {
+        denominator *= 10;
+        numerator = numerator * 10 + digit.to_digit(10)? as u128;
+    }
+    result += numerator * factor as u128 / denominator;
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn test_parse_duration() {
+        let cases = [
+            ("30", Duration::from_secs(30)),
+            ("1.5", Duration::from_millis(1500)),
+            ("250ms", Duration::from_millis(250)),
+            ("30s", Duration::from_secs(30)),
+            ("5m", Duration::from_secs(300)),
+            ("1h30m", Duration::from_secs(5400)),
+            ("1h 30m 15s", Duration::from_secs(5415)),
+            ("1.5h", Duration::from_secs(5400)),
+            ("0.25s", Duration::from_millis(250)),
+            ("2d", Duration::from_secs(172_800)),
+            ("1s500ms", Duration::from_millis(1500)),
+            ("10us", Duration::from_micros(10)),
+            ("10µs", Duration::from_micros(10)),
+            ("7ns", Duration::from_nanos(7)),
+            (" 0s ", Duration::ZERO),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_duration(text).unwrap(), expected, "{}", text);
+        }
+
+        for text in [
+            "", "abc", "5x", "1h30", "h", "-5s", "-1", "1..5s", "1.2.3s", "ms5",
+        ] {
+            let err = parse_duration(text).unwrap_err();
+            assert!(
+                matches!(err, ClamberError::ConfigParseError { .. }),
+                "{}",
+                text
+            );
+            assert!(
+                err.to_string().contains(&format!("\"{}\"", text)),
+                "{}",
+                err
+            );
+        }
+        assert!(parse_duration("99999999999999999999d").is_err());
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        let cases = [
+            ("1024", 1024),
+            ("0", 0),
+            ("512B", 512),
+            ("512KB", 512_000),
+            ("256MB", 256_000_000),
+            ("2GiB", 2 << 30),
+            ("1.5 KiB", 1536),
+            ("1.5kib", 1536),
+            ("0.5MiB", 512 << 10),
+            ("1tb", 1_000_000_000_000),
+            ("1.0009KB", 1000),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_bytes(text).unwrap(), expected, "{}", text);
+        }
+
+        for text in ["", "KB", "12XB", "-1", "1.2.3MB", "1 2MB", "99999999PiB"] {
+            let err = parse_bytes(text).unwrap_err();
+            assert!(
+                matches!(err, ClamberError::ConfigParseError { .. }),
+                "{}",
+                text
+            );
+            assert!(err.to_string().contains(text), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1s500ms");
+        assert_eq!(format_duration(Duration::ZERO), "0s");
+        assert_eq!(format_bytes(2 << 30), "2GiB");
+        assert_eq!(format_bytes(1536), "1536");
+        assert_eq!(format_bytes(0), "0");
+        for duration in [
+            Duration::from_nanos(93_784_005_006_007),
+            Duration::from_secs(59),
+        ] {
+            assert_eq!(
+                parse_duration(&format_duration(duration)).unwrap(),
+                duration
+            );
+        }
+        for bytes in [1, 1 << 20, 3 << 40, 1_000_001] {
+            assert_eq!(parse_bytes(&format_bytes(bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct ServerConfig {
+        #[serde(with = "duration")]
+        timeout: Duration,
+        #[serde(with = "duration")]
+        idle: Duration,
+        #[serde(with = "bytes")]
+        max_body: u64,
+        #[serde(with = "bytes")]
+        buffer: u64,
+    }
+
+    #[test]
+    fn test_yaml_file_end_to_end() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.yaml");
+        std::fs::write(
+            &path,
+            "timeout: 1h30m\nidle: 45\nmax_body: 256MB\nbuffer: 4096\n",
+        )
+        .unwrap();
+
+        let config: ServerConfig = ConfigBuilder::new().add_file(&path, None).build().unwrap();
+        assert_eq!(
+            config,
+            ServerConfig {
+                timeout: Duration::from_secs(5400),
+                idle: Duration::from_secs(45),
+                max_body: 256_000_000,
+                buffer: 4096,
+            }
+        );
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["timeout"], "1h30m");
+        assert_eq!(json["buffer"], "4KiB");
+        assert_eq!(
+            serde_json::from_value::<ServerConfig>(json).unwrap(),
+            config
+        );
+
+        std::fs::write(
+            &path,
+            "timeout: 10 minutes\nidle: 1\nmax_body: 1MB\nbuffer: 1\n",
+        )
+        .unwrap();
+        let err = ConfigBuilder::new()
+            .add_file(&path, None)
+            .build::<ServerConfig>()
+            .unwrap_err();
+        assert!(
+            matches!(err.root_cause(), ClamberError::ConfigParseError { .. }),
+            "{:?}",
+            err
+        );
+        assert!(
+            err.to_string().contains("无效的时长 \"10 minutes\""),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! 配置值插值：将字符串中的 `${env:NAME}`、`${file:path}` 替换为环境变量与文件内容。
+//!
+//! `$${...}` 转义为字面量 `${...}`；其他形式的 `${...}` 原样保留。
+use crate::error::{ClamberError, Result};
+use config::{Value, ValueKind};
+
+/// 遍历配置树，替换全部字符串值中的引用
+pub(super) fn apply(value: &mut Value) -> Result<()> {
+    walk(value, "")
+}
+
+fn walk(value: &mut Value, key: &str) -> Result<()> {
+    match &mut value.kind {
+        ValueKind::String(text) if text.contains("${") => {
+            *text = interpolate(text, key)?;
+        }
+        ValueKind::Table(table) => {
+            for (name, child) in table.iter_mut() {
+                let child_key = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                walk(child, &child_key)?;
+            }
+        }
+        ValueKind::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                walk(item, &format!("{}[{}]", key, index))?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate(text: &str, key: &str) -> Result<String> {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find("${") {
+        // `$${` 为转义
+        if pos > 0 && rest.as_bytes()[pos - 1] == b'$' {
+            output.push_str(&rest[..pos - 1]);
+            output.push_str("${");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        output.push_str(&rest[..pos]);
+        let Some(end) = rest[pos..].find('}') else {
+            output.push_str(&rest[pos..]);
+            return Ok(output);
+        };
+        let reference = &rest[pos + 2..pos + end];
+        match resolve(reference, key)? {
+            Some(resolved) => output.push_str(&resolved),
+            None => output.push_str(&rest[pos..=pos + end]),
+        }
+        rest = &rest[pos + end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// 解析单个引用；不认识的前缀返回 `None`，保持原样
+fn resolve(reference: &str, key: &str) -> Result<Option<String>> {
+    if let Some(name) = reference.strip_prefix("env:") {
+        return std::env::var(name)
+            .map(Some)
+            .map_err(|_| ClamberError::ConfigValidationError {
+                details: format!("{} 引用的环境变量 {} 不存在", key, name),
+            });
+    }
+    if let Some(path) = reference.strip_prefix("file:") {
+        return std::fs::read_to_string(path)
+            .map(|content| Some(content.trim().to_string()))
+            .map_err(|e| ClamberError::ConfigValidationError {
+                details: format!("{} 引用的文件 {} 无法读取: {}", key, path, e),
+            });
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{ConfigBuilder, ConfigFormat};
+    use crate::error::ClamberError;
+    use crate::testing::{EnvGuard, write_temp_config};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Secrets {
+        database: Database,
+        hosts: Vec<String>,
+        literal: String,
+        untouched: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Database {
+        user: String,
+        password: String,
+    }
+
+    fn build(yaml: &str, enabled: bool) -> crate::error::Result<Secrets> {
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, yaml);
+        ConfigBuilder::new()
+            .add_file(&path, None)
+            .enable_interpolation(enabled)
+            .build()
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret = dir.path().join("db_pass");
+        std::fs::write(&secret, "s3cret\n").unwrap();
+        let _env = EnvGuard::new()
+            .set("CLAMBER_INTERP_USER", "admin")
+            .set("CLAMBER_INTERP_HOST", "db-1");
+
+        let yaml = format!(
+            r#"
+database:
+  user: "${{env:CLAMBER_INTERP_USER}}"
+  password: "${{file:{}}}"
+hosts: ["${{env:CLAMBER_INTERP_HOST}}:5432", "static"]
+literal: "$${{env:CLAMBER_INTERP_USER}}"
+untouched: "${{other}}"
+"#,
+            secret.display()
+        );
+        let secrets = build(&yaml, true).unwrap();
+        assert_eq!(secrets.database.user, "admin");
+        assert_eq!(secrets.database.password, "s3cret");
+        assert_eq!(secrets.hosts, ["db-1:5432", "static"]);
+        assert_eq!(secrets.literal, "${env:CLAMBER_INTERP_USER}");
+        assert_eq!(secrets.untouched, "${other}");
+
+        // 未开启时原样保留
+        let raw = build(&yaml, false).unwrap();
+        assert_eq!(raw.database.user, "${env:CLAMBER_INTERP_USER}");
+    }
+
+    #[test]
+    fn test_interpolation_missing_reference() {
+        let _env = EnvGuard::new().remove("CLAMBER_INTERP_MISSING");
+        let yaml = r#"
+database:
+  user: a
+  password: "${env:CLAMBER_INTERP_MISSING}"
+hosts: []
+literal: ""
+untouched: ""
+"#;
+        match build(yaml, true) {
+            Err(ClamberError::ConfigValidationError { details }) => {
+                assert!(details.contains("database.password"));
+                assert!(details.contains("CLAMBER_INTERP_MISSING"));
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+
+        let yaml = yaml.replace("env:CLAMBER_INTERP_MISSING", "file:/nonexistent/secret");
+        assert!(matches!(
+            build(&yaml, true),
+            Err(ClamberError::ConfigValidationError { .. })
+        ));
+    }
+}
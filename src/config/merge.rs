@@ -0,0 +1,167 @@
+//! 多来源合并：逐个来源收集为值树后按顺序深度合并，数组可按键选择替换或追加。
+//!
+//! 表按键递归合并，其余值（标量、空值）由后出现的来源覆盖，与 config crate 自身的合并规则一致。
+use crate::error::{ClamberError, Result};
+use config::{Map, Source, Value, ValueKind};
+use std::collections::HashMap;
+
+/// 多个来源中同一键都是数组时的合并方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayMergeStrategy {
+    /// 后出现的来源整体替换数组（config crate 的默认行为）
+    #[default]
+    Replace,
+    /// 后出现的来源的元素追加到已有数组之后
+    Append,
+}
+
+/// 全局与按键路径指定的数组合并方式
+#[derive(Debug, Clone, Default)]
+pub(super) struct ArrayMergeRules {
+    pub(super) default: ArrayMergeStrategy,
+    /// 键路径（如 `cors.allowed_origins`）到合并方式
+    pub(super) keys: HashMap<String, ArrayMergeStrategy>,
+}
+
+impl ArrayMergeRules {
+    fn strategy(&self, key: &str) -> ArrayMergeStrategy {
+        self.keys.get(key).copied().unwrap_or(self.default)
+    }
+
+    /// 按优先级从低到高合并全部来源
+    pub(super) fn merge<'a>(
+        &self,
+        sources: impl IntoIterator<Item = &'a (dyn Source + Send + Sync)>,
+    ) -> Result<MergedSource> {
+        let mut merged = Value::new(None, ValueKind::Table(Map::new()));
+        for source in sources {
+            let mut cache = Value::new(None, ValueKind::Table(Map::new()));
+            source.collect_to(&mut cache).map_err(ClamberError::from)?;
+            self.merge_value(&mut merged, cache, String::new());
+        }
+        match merged.kind {
+            ValueKind::Table(table) => Ok(MergedSource(table)),
+            _ => unreachable!("合并结果的根始终为表"),
+        }
+    }
+
+    fn merge_value(&self, target: &mut Value, incoming: Value, key: String) {
+        let append = matches!(
+            (&target.kind, &incoming.kind),
+            (ValueKind::Array(_), ValueKind::Array(_))
+        ) && self.strategy(&key) == ArrayMergeStrategy::Append;
+        let origin = incoming.origin().map(str::to_string);
+        match (&mut target.kind, incoming.kind) {
+            (ValueKind::Table(target_table), ValueKind::Table(table)) => {
+                for (name, value) in table {
+                    let path = join(&key, &name);
+                    match target_table.get_mut(&name) {
+                        Some(child) => self.merge_value(child, value, path),
+                        None => {
+                            target_table.insert(name, value);
+                        }
+                    }
+                }
+            }
+            (ValueKind::Array(existing), ValueKind::Array(items)) if append => {
+                existing.extend(items);
+            }
+            // 其余情况整体覆盖，保留来源信息以便错误提示指出文件
+            (_, kind) => *target = Value::new(origin.as_ref(), kind),
+        }
+    }
+}
+
+fn join(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+/// 已合并的来源，作为单个来源交给 config crate
+#[derive(Debug, Clone)]
+pub(super) struct MergedSource(Map<String, Value>);
+
+impl Source for MergedSource {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> std::result::Result<Map<String, Value>, config::ConfigError> {
+        Ok(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use crate::testing::EnvGuard;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct AppConfig {
+        name: String,
+        tags: Vec<String>,
+        cors: CorsConfig,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct CorsConfig {
+        allowed_origins: Vec<String>,
+        max_age: u32,
+    }
+
+    const BASE: &str = "name: base\ntags: [x]\ncors:\n  allowed_origins: [a, b]\n  max_age: 60\n";
+    const OVERRIDE: &str = "name: override\ntags: [y]\ncors:\n  allowed_origins: [c]\n";
+
+    fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+            .add_yaml_str(BASE)
+            .add_yaml_str(OVERRIDE)
+    }
+
+    #[test]
+    fn test_replace_is_default() {
+        let config: AppConfig = builder().build().unwrap();
+        assert_eq!(config.name, "override");
+        assert_eq!(config.tags, ["y"]);
+        assert_eq!(config.cors.allowed_origins, ["c"]);
+        assert_eq!(config.cors.max_age, 60);
+
+        let config: AppConfig = builder()
+            .array_merge_strategy(ArrayMergeStrategy::Replace)
+            .build()
+            .unwrap();
+        assert_eq!(config.cors.allowed_origins, ["c"]);
+    }
+
+    #[test]
+    fn test_append_for_key() {
+        let config: AppConfig = builder()
+            .array_merge_for_key("cors.allowed_origins", ArrayMergeStrategy::Append)
+            .build()
+            .unwrap();
+        assert_eq!(config.cors.allowed_origins, ["a", "b", "c"]);
+        assert_eq!(config.tags, ["y"]);
+        assert_eq!(config.cors.max_age, 60);
+        assert_eq!(config.name, "override");
+    }
+
+    #[test]
+    fn test_global_append_with_key_override() {
+        let _env = EnvGuard::new().set("CLAMBER_MERGE_CORS__MAX_AGE", "120");
+        let config: AppConfig = builder()
+            .with_env_prefix("CLAMBER_MERGE")
+            .array_merge_strategy(ArrayMergeStrategy::Append)
+            .array_merge_for_key("tags", ArrayMergeStrategy::Replace)
+            .build()
+            .unwrap();
+        assert_eq!(config.cors.allowed_origins, ["a", "b", "c"]);
+        assert_eq!(config.tags, ["y"]);
+        assert_eq!(config.cors.max_age, 120);
+        assert_eq!(config.name, "override");
+    }
+}
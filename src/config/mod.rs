@@ -1,11 +1,42 @@
 //! 配置管理模块：支持多格式配置文件（YAML/TOML/JSON）、环境变量覆盖（可自定义前缀与分隔符）、多文件合并与默认值。
 //! 参见项目根目录的 CONFIG.md 获取更完整的使用指南与示例。
-use crate::error::{ClamberError, Result};
-use config::{Config, Environment, File, FileFormat};
-use serde::Deserialize;
+use crate::error::{ClamberError, ErrorCollector, Result, ResultExt};
+use config::{Config, Environment, File, FileFormat, FileSourceFile, ValueKind};
+use merge::ArrayMergeRules;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use unknown::{UnknownKeyCheck, UnknownKeys};
+
+mod defaults;
+mod diff;
+mod dotenv;
+#[cfg(feature = "config-encryption")]
+mod encryption;
+pub mod humanize;
+mod interpolate;
+mod merge;
+#[cfg(feature = "remote-config")]
+mod remote;
+mod report;
+mod unknown;
+mod view;
+mod watch;
+
+pub use diff::{ConfigDiff, ValueChange};
+#[cfg(feature = "config-encryption")]
+pub use encryption::{ENCRYPTED_PREFIX, encrypt_value};
+pub use merge::ArrayMergeStrategy;
+pub use report::{ConfigReport, SourceInfo, ValueSource};
+pub use view::ConfigView;
+pub use watch::WatchedConfig;
+
+/// 未显式指定 profile 时读取的环境变量
+pub const PROFILE_ENV_VAR: &str = "CLAMBER_PROFILE";
 
 /// 配置文件格式枚举
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +47,12 @@ pub enum ConfigFormat {
     Toml,
     /// JSON 格式
     Json,
+    /// INI 格式，`[section]` 对应嵌套的表（`ini` feature）
+    #[cfg(feature = "ini")]
+    Ini,
+    /// RON 格式（`ron` feature）
+    #[cfg(feature = "ron")]
+    Ron,
 }
 
 impl ConfigFormat {
@@ -25,6 +62,10 @@ impl ConfigFormat {
             "yaml" | "yml" => Some(ConfigFormat::Yaml),
             "toml" => Some(ConfigFormat::Toml),
             "json" => Some(ConfigFormat::Json),
+            #[cfg(feature = "ini")]
+            "ini" => Some(ConfigFormat::Ini),
+            #[cfg(feature = "ron")]
+            "ron" => Some(ConfigFormat::Ron),
             _ => None,
         }
     }
@@ -35,33 +76,131 @@ impl ConfigFormat {
             ConfigFormat::Yaml => FileFormat::Yaml,
             ConfigFormat::Toml => FileFormat::Toml,
             ConfigFormat::Json => FileFormat::Json,
+            #[cfg(feature = "ini")]
+            ConfigFormat::Ini => FileFormat::Ini,
+            #[cfg(feature = "ron")]
+            ConfigFormat::Ron => FileFormat::Ron,
         }
     }
 }
 
+/// 配置校验规则，由结构体作者集中实现，配合 [`ConfigBuilder::build_validated`] 使用
+pub trait Validate {
+    /// 校验配置，不通过时一般返回 [`ClamberError::ConfigValidationError`]
+    fn validate(&self) -> Result<()>;
+}
+
+type ValidatorFn = Arc<dyn Fn(&Config) -> Result<()> + Send + Sync>;
+
+/// 通过 [`ConfigBuilder::with_validator`] 注册的校验函数
+#[derive(Clone, Default)]
+struct Validators(Vec<ValidatorFn>);
+
+impl Validators {
+    /// 反序列化并依次执行校验
+    fn finish<T>(&self, config: Config, unknown: &UnknownKeyCheck) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if self.0.is_empty() {
+            return unknown.deserialize(config);
+        }
+
+        let value = unknown.deserialize(config.clone())?;
+        for validator in &self.0 {
+            validator(&config)?;
+        }
+        Ok(value)
+    }
+}
+
+impl fmt::Debug for Validators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Validators({})", self.0.len())
+    }
+}
+
+/// 配置来源，按添加顺序合并
+#[derive(Debug, Clone)]
+enum ConfigSource {
+    /// 配置文件，格式为 `None` 时从扩展名推断
+    File(PathBuf, Option<ConfigFormat>),
+    /// 内存中的配置文本
+    Inline(String, ConfigFormat),
+    /// 构建时通过 HTTP(S) 获取的配置文档
+    #[cfg(feature = "remote-config")]
+    Url(remote::RemoteSource),
+}
+
 /// 配置构建器
 #[derive(Debug, Clone)]
 pub struct ConfigBuilder {
-    /// 配置文件路径列表
-    files: Vec<(PathBuf, Option<ConfigFormat>)>,
+    /// 配置来源列表（文件与内联文本）
+    sources: Vec<ConfigSource>,
+    /// `.env` 文件路径列表
+    dotenv_files: Vec<PathBuf>,
+    /// 配置 profile（如 dev / prod）
+    profile: Option<String>,
+    /// 是否替换 `${env:...}` / `${file:...}` 引用
+    interpolation: bool,
     /// 环境变量前缀
     env_prefix: Option<String>,
     /// 环境变量分隔符
     env_separator: String,
+    /// 环境变量列表值的分隔符
+    env_list_separator: Option<String>,
+    /// 按列表解析的键路径（如 `database.replicas`）
+    env_list_keys: Vec<String>,
     /// 是否忽略缺失的配置文件
     ignore_missing: bool,
     /// 默认值
     defaults: HashMap<String, config::Value>,
+    /// 热加载时检查配置文件的间隔
+    watch_interval: Duration,
+    /// 反序列化后执行的校验函数
+    validators: Validators,
+    /// 必须存在的键路径
+    required_keys: Vec<String>,
+    /// 目标结构体未使用的键的处理方式
+    unknown_keys: UnknownKeys,
+    /// 多个来源中数组的合并方式
+    array_merge: ArrayMergeRules,
+    /// 解密 `enc:v1:` 加密值的密钥
+    #[cfg(feature = "config-encryption")]
+    encryption_key: Option<encryption::KeySource>,
+    /// 获取远程配置的超时
+    #[cfg(feature = "remote-config")]
+    remote_timeout: Duration,
 }
 
+// 构建器可移入 spawn_blocking 等其他线程中构建
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ConfigBuilder>();
+};
+
 impl Default for ConfigBuilder {
     fn default() -> Self {
         Self {
-            files: Vec::new(),
+            sources: Vec::new(),
+            dotenv_files: Vec::new(),
+            profile: None,
+            interpolation: false,
             env_prefix: None,
             env_separator: "__".to_string(),
+            env_list_separator: None,
+            env_list_keys: Vec::new(),
             ignore_missing: false,
             defaults: HashMap::new(),
+            watch_interval: Duration::from_secs(1),
+            validators: Validators::default(),
+            required_keys: Vec::new(),
+            unknown_keys: UnknownKeys::default(),
+            array_merge: ArrayMergeRules::default(),
+            #[cfg(feature = "config-encryption")]
+            encryption_key: None,
+            #[cfg(feature = "remote-config")]
+            remote_timeout: remote::DEFAULT_TIMEOUT,
         }
     }
 }
@@ -78,7 +217,62 @@ impl ConfigBuilder {
     /// * `path` - 配置文件路径
     /// * `format` - 可选的文件格式，如果不指定则从文件扩展名推断
     pub fn add_file<P: AsRef<Path>>(mut self, path: P, format: Option<ConfigFormat>) -> Self {
-        self.files.push((path.as_ref().to_path_buf(), format));
+        self.sources
+            .push(ConfigSource::File(path.as_ref().to_path_buf(), format));
+        self
+    }
+
+    /// 添加内存中的配置文本，与配置文件按添加顺序合并，同样可被环境变量覆盖
+    pub fn add_str(mut self, content: &str, format: ConfigFormat) -> Self {
+        self.sources
+            .push(ConfigSource::Inline(content.to_string(), format));
+        self
+    }
+
+    /// 添加 YAML 配置文本
+    pub fn add_yaml_str(self, content: &str) -> Self {
+        self.add_str(content, ConfigFormat::Yaml)
+    }
+
+    /// 添加 TOML 配置文本
+    pub fn add_toml_str(self, content: &str) -> Self {
+        self.add_str(content, ConfigFormat::Toml)
+    }
+
+    /// 添加 JSON 配置文本
+    pub fn add_json_str(self, content: &str) -> Self {
+        self.add_str(content, ConfigFormat::Json)
+    }
+
+    /// 添加远程配置地址（`http://` 或 `https://`），构建时 GET 获取，与其他来源按添加顺序合并
+    ///
+    /// 网络错误与非 2xx 响应返回 [`ClamberError::ConfigLoadError`]；开启
+    /// [`ConfigBuilder::ignore_missing_files`] 时跳过返回 404 的地址。远程配置不参与热加载。
+    #[cfg(feature = "remote-config")]
+    pub fn add_url(mut self, url: &str, format: ConfigFormat) -> Self {
+        self.sources.push(ConfigSource::Url(remote::RemoteSource {
+            url: url.to_string(),
+            format,
+            token: None,
+        }));
+        self
+    }
+
+    /// 添加远程配置地址，请求时携带 `Authorization: Bearer <token>`
+    #[cfg(feature = "remote-config")]
+    pub fn add_url_with_auth(mut self, url: &str, format: ConfigFormat, token: &str) -> Self {
+        self.sources.push(ConfigSource::Url(remote::RemoteSource {
+            url: url.to_string(),
+            format,
+            token: Some(token.to_string()),
+        }));
+        self
+    }
+
+    /// 设置获取每个远程配置的总超时（从解析域名到读完响应体，含重定向），默认 10 秒
+    #[cfg(feature = "remote-config")]
+    pub fn with_remote_timeout(mut self, timeout: Duration) -> Self {
+        self.remote_timeout = timeout;
         self
     }
 
@@ -97,6 +291,50 @@ impl ConfigBuilder {
         self.add_file(path, Some(ConfigFormat::Json))
     }
 
+    /// 添加 INI 配置文件，值均按字符串读取，反序列化为数值等类型时自动转换
+    #[cfg(feature = "ini")]
+    pub fn add_ini_file<P: AsRef<Path>>(self, path: P) -> Self {
+        self.add_file(path, Some(ConfigFormat::Ini))
+    }
+
+    /// 添加 RON 配置文件
+    ///
+    /// config crate 转换时会丢弃枚举变体名，枚举值需写成外部标记形式：
+    /// 单元变体写作字符串 `"Standalone"`，带数据的变体写作单键映射 `{"Cluster": (nodes: 3)}`。
+    #[cfg(feature = "ron")]
+    pub fn add_ron_file<P: AsRef<Path>>(self, path: P) -> Self {
+        self.add_file(path, Some(ConfigFormat::Ron))
+    }
+
+    /// 设置 profile：每个配置文件 `app.yaml` 额外叠加同目录下的 `app.{profile}.yaml`
+    ///
+    /// profile 文件在全部基础文件之后加载，缺失时忽略。
+    pub fn with_profile<S: Into<String>>(mut self, profile: S) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// 开启插值：合并后的字符串值中 `${env:NAME}` 替换为环境变量，`${file:path}` 替换为去掉首尾空白的文件内容
+    ///
+    /// `$${...}` 表示字面量 `${...}`；引用的环境变量或文件不存在时返回 [`ClamberError::ConfigValidationError`]。
+    pub fn enable_interpolation(mut self, enable: bool) -> Self {
+        self.interpolation = enable;
+        self
+    }
+
+    /// 添加 `.env` 文件，其中的变量按环境变量规则（前缀、分隔符）覆盖配置文件
+    ///
+    /// 优先级：默认值 < 配置文件 < `.env` < 真实环境变量；多个 `.env` 文件时后添加的优先。
+    pub fn add_dotenv_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.dotenv_files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// 添加当前目录下的 `.env` 文件
+    pub fn add_dotenv(self) -> Self {
+        self.add_dotenv_file(".env")
+    }
+
     /// 设置环境变量前缀
     ///
     /// # 参数
@@ -115,6 +353,23 @@ impl ConfigBuilder {
         self
     }
 
+    /// 设置环境变量列表值的分隔符，默认 `,`
+    ///
+    /// 只对 [`ConfigBuilder::env_list_keys`] 指定的键生效，其他值即使包含分隔符也保持为字符串。
+    pub fn with_env_list_separator<S: Into<String>>(mut self, separator: S) -> Self {
+        self.env_list_separator = Some(separator.into());
+        self
+    }
+
+    /// 指定按列表解析的键路径，如 `APP_DATABASE__REPLICAS=h1,h2` 对应 `database.replicas`
+    ///
+    /// 数字元素在反序列化为数值类型时自动转换。
+    pub fn env_list_keys(mut self, keys: &[&str]) -> Self {
+        self.env_list_keys
+            .extend(keys.iter().map(|key| key.to_lowercase()));
+        self
+    }
+
     /// 设置是否忽略缺失的配置文件
     pub fn ignore_missing_files(mut self, ignore: bool) -> Self {
         self.ignore_missing = ignore;
@@ -135,6 +390,84 @@ impl ConfigBuilder {
         Ok(self)
     }
 
+    /// 添加校验函数，在 [`ConfigBuilder::build`] 反序列化之后执行
+    ///
+    /// 校验函数按 `T` 独立反序列化合并后的配置，可与 `build` 的目标类型不同（如只校验某个子结构）。
+    /// 返回的错误信息包装为 [`ClamberError::ConfigValidationError`]。
+    pub fn with_validator<T, F>(mut self, validator: F) -> Self
+    where
+        T: for<'de> Deserialize<'de>,
+        F: Fn(&T) -> std::result::Result<(), String> + Send + Sync + 'static,
+    {
+        self.validators.0.push(Arc::new(move |config: &Config| {
+            let value = deserialize::<T>(config.clone())?;
+            validator(&value).map_err(|details| ClamberError::ConfigValidationError { details })
+        }));
+        self
+    }
+
+    /// 声明必须存在的键路径（如 `database.host`），构建时一次性列出全部缺失的键
+    ///
+    /// 任一键缺失（或值为空）时 `build` 等方法返回 [`ClamberError::ConfigValidationError`]。
+    pub fn require_keys(mut self, keys: &[&str]) -> Self {
+        self.required_keys
+            .extend(keys.iter().map(|key| key.to_string()));
+        self
+    }
+
+    /// 设置多个来源中同一键都是数组时的合并方式，默认 [`ArrayMergeStrategy::Replace`]
+    ///
+    /// 表仍按键深度合并，标量仍由后添加的来源覆盖；只有两侧都是数组时才按此方式合并。
+    pub fn array_merge_strategy(mut self, strategy: ArrayMergeStrategy) -> Self {
+        self.array_merge.default = strategy;
+        self
+    }
+
+    /// 为指定键路径（如 `cors.allowed_origins`）单独设置数组合并方式，优先于全局设置
+    pub fn array_merge_for_key(mut self, key: &str, strategy: ArrayMergeStrategy) -> Self {
+        self.array_merge.keys.insert(key.to_string(), strategy);
+        self
+    }
+
+    /// 设置解密 `enc:v1:` 加密值的 AES-256-GCM 密钥
+    #[cfg(feature = "config-encryption")]
+    pub fn with_encryption_key(mut self, key: &[u8; 32]) -> Self {
+        self.encryption_key = Some(encryption::KeySource::Bytes(*key));
+        self
+    }
+
+    /// 构建时从环境变量读取解密密钥（base64 编码的 32 字节）
+    ///
+    /// 只有配置中存在加密值时才读取，变量缺失或格式错误时返回 [`ClamberError::ConfigValidationError`]。
+    #[cfg(feature = "config-encryption")]
+    pub fn with_encryption_key_from_env<S: Into<String>>(mut self, name: S) -> Self {
+        self.encryption_key = Some(encryption::KeySource::Env(name.into()));
+        self
+    }
+
+    /// 构建时目标结构体未使用的键视为错误，返回列出这些键的 [`ClamberError::ConfigValidationError`]
+    ///
+    /// 用于发现 `databse:` 之类的拼写错误（serde 默认忽略多余的键，字段落回默认值）。
+    /// 设置了环境变量前缀时，带前缀的环境变量提供的键不检查。
+    pub fn deny_unknown_keys(mut self, deny: bool) -> Self {
+        self.unknown_keys = if deny {
+            UnknownKeys::Deny
+        } else {
+            UnknownKeys::Allow
+        };
+        self
+    }
+
+    /// 与 [`ConfigBuilder::deny_unknown_keys`] 相同，但只通过 `tracing::warn!` 记录，构建照常成功
+    pub fn warn_unknown_keys(mut self, warn: bool) -> Self {
+        self.unknown_keys = if warn {
+            UnknownKeys::Warn
+        } else {
+            UnknownKeys::Allow
+        };
+        self
+    }
+
     /// 构建配置并反序列化为指定类型
     ///
     /// # 返回值
@@ -143,102 +476,338 @@ impl ConfigBuilder {
     where
         T: for<'de> Deserialize<'de>,
     {
-        let mut config_builder = Config::builder();
+        let validators = self.validators.clone();
+        let unknown = UnknownKeyCheck::new(&self)?;
+        let config = self.build_raw()?;
+        validators.finish(config, &unknown)
+    }
 
-        // 添加默认值
-        for (key, value) in self.defaults {
-            config_builder = config_builder.set_default(&key, value).map_err(|e| {
-                ClamberError::ConfigLoadError {
-                    details: format!("设置默认值失败: {}", e),
-                }
-            })?;
-        }
+    /// 构建配置，并返回每个键的最终值与来源（默认值、文件或环境变量），用于排查多层配置
+    pub fn build_with_report<T>(self) -> Result<(T, ConfigReport)>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let validators = self.validators.clone();
+        let unknown = UnknownKeyCheck::new(&self)?;
+        // 只读取一次各来源，报告与合并结果基于同一份内容（远程配置不会被请求两次）
+        let layers = self.layers()?;
+        let config = self.build_from_layers(&layers)?;
+        let report = ConfigReport::collect(&self, &config, &layers)?;
+        Ok((validators.finish(config, &unknown)?, report))
+    }
 
-        // 添加配置文件
-        for (path, format) in self.files {
-            let format = format
-                .or_else(|| ConfigFormat::from_extension(&path))
-                .ok_or_else(|| ClamberError::ConfigLoadError {
-                    details: format!("无法推断配置文件格式: {:?}", path),
-                })?;
+    /// 构建配置并汇总全部问题：缺失的必需键、反序列化错误与各校验函数的错误
+    ///
+    /// 反序列化错误带有出错字段的完整路径（如 `database.port: invalid type`）；反序列化成功后才执行校验函数。
+    /// 只有一个问题时原样返回，多个时返回 [`ClamberError::Multiple`]。
+    pub fn build_with_all_errors<T>(mut self) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let validators = self.validators.clone();
+        let required_keys = std::mem::take(&mut self.required_keys);
+        let unknown = UnknownKeyCheck::new(&self)?;
+        let config = self.build_raw()?;
+
+        let mut errors = ErrorCollector::new();
+        errors.check(check_required_keys(&config, &required_keys));
+        let value = errors.check(deserialize_with_path::<T>(config.clone()));
+        // 反序列化失败时校验函数多半因同样的原因失败，不再重复报告
+        if value.is_some() {
+            errors.check(unknown.check::<T>(config.clone()));
+            for validator in &validators.0 {
+                errors.check(validator(&config));
+            }
+        }
+        match (errors.into_result(), value) {
+            (Ok(()), Some(value)) => Ok(value),
+            (Err(err), _) => Err(err),
+            (Ok(()), None) => unreachable!("反序列化失败时已记录错误"),
+        }
+    }
 
-            let file_config = File::from(path.clone())
-                .format(format.to_file_format())
-                .required(!self.ignore_missing);
+    /// 构建配置并只反序列化 `key_path` 指向的子结构（如 `database`）
+    pub fn build_section<T>(self, key_path: &str) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.build_view()?.get_section(key_path)
+    }
 
-            config_builder = config_builder.add_source(file_config);
-        }
+    /// 构建配置并返回按键路径读取的视图
+    pub fn build_view(self) -> Result<ConfigView> {
+        self.build_raw().map(ConfigView::new)
+    }
 
-        // 添加环境变量
-        if let Some(prefix) = self.env_prefix {
-            let env_config = Environment::with_prefix(&prefix)
-                .separator(&self.env_separator)
-                .try_parsing(true)
-                .ignore_empty(true);
-            config_builder = config_builder.add_source(env_config);
-        }
+    /// 构建配置并调用 [`Validate::validate`] 校验
+    pub fn build_validated<T>(self) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de> + Validate,
+    {
+        let value: T = self.build()?;
+        value
+            .validate()
+            .with_context(|| format!("校验配置 {} 时", std::any::type_name::<T>()))?;
+        Ok(value)
+    }
 
-        // 构建配置
-        let config = config_builder
-            .build()
-            .map_err(|e| ClamberError::ConfigLoadError {
-                details: e.to_string(),
-            })?;
+    /// 设置热加载检查配置文件的间隔，默认 1 秒
+    pub fn with_watch_interval(mut self, interval: Duration) -> Self {
+        self.watch_interval = interval;
+        self
+    }
 
-        // 反序列化
-        config
-            .try_deserialize::<T>()
-            .map_err(|e| ClamberError::ConfigParseError {
-                details: e.to_string(),
-            })
+    /// 构建支持热加载的配置
+    ///
+    /// 首次构建失败直接返回错误；之后配置文件变化时在后台重新构建，详见 [`WatchedConfig`]。
+    pub fn build_watched<T>(self) -> Result<WatchedConfig<T>>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync + 'static,
+    {
+        WatchedConfig::spawn(self)
     }
 
     /// 构建配置并返回原始 Config 对象
     pub fn build_raw(self) -> Result<Config> {
+        let layers = self.layers()?;
+        self.build_from_layers(&layers)
+    }
+
+    /// 以默认值加上已读取的来源构建配置
+    fn build_from_layers(&self, layers: &[report::LayerSource]) -> Result<Config> {
         let mut config_builder = Config::builder();
 
         // 添加默认值
-        for (key, value) in self.defaults {
-            config_builder = config_builder.set_default(&key, value).map_err(|e| {
-                ClamberError::ConfigLoadError {
+        for (key, value) in &self.defaults {
+            config_builder = config_builder
+                .set_default(key, value.clone())
+                .map_err(|e| ClamberError::ConfigLoadError {
                     details: format!("设置默认值失败: {}", e),
-                }
-            })?;
+                    source: Some(e.into()),
+                })?;
         }
 
-        // 添加配置文件
-        for (path, format) in self.files {
-            let format = format
-                .or_else(|| ConfigFormat::from_extension(&path))
-                .ok_or_else(|| ClamberError::ConfigLoadError {
-                    details: format!("无法推断配置文件格式: {:?}", path),
-                })?;
+        let sources = layers.iter().map(|(_, source)| source.as_ref());
+        config_builder = config_builder.add_source(self.array_merge.merge(sources)?);
+
+        // 构建配置；必需的文件缺失时返回 ConfigFileNotFoundError
+        let mut config = config_builder.build().map_err(ClamberError::from)?;
+        if self.interpolation {
+            interpolate::apply(&mut config.cache)?;
+        }
+        #[cfg(feature = "config-encryption")]
+        encryption::apply(&mut config.cache, self.encryption_key.as_ref())?;
+        check_required_keys(&config, &self.required_keys)?;
+        Ok(config)
+    }
 
-            let file_config = File::from(path.clone())
-                .format(format.to_file_format())
-                .required(!self.ignore_missing);
+    /// 默认值之上的全部来源，按优先级从低到高排列
+    fn layers(&self) -> Result<Vec<report::LayerSource>> {
+        let mut layers: Vec<report::LayerSource> = Vec::new();
+
+        // 配置文件与内联配置
+        let mut inline = 0;
+        for source in &self.sources {
+            match source {
+                ConfigSource::File(path, format) => layers.push((
+                    report::Layer::File(path.clone()),
+                    Box::new(file_source(path, *format, !self.ignore_missing)?),
+                )),
+                ConfigSource::Inline(content, format) => {
+                    layers.push((
+                        report::Layer::Inline(inline),
+                        Box::new(File::from_str(content, format.to_file_format())),
+                    ));
+                    inline += 1;
+                }
+                #[cfg(feature = "remote-config")]
+                ConfigSource::Url(remote) => {
+                    if let Some(content) = remote.fetch(self.remote_timeout, self.ignore_missing)? {
+                        layers.push((
+                            report::Layer::Url(remote.url.clone()),
+                            Box::new(File::from_str(&content, remote.format.to_file_format())),
+                        ));
+                    }
+                }
+            }
+        }
 
-            config_builder = config_builder.add_source(file_config);
+        // profile 文件叠加在全部基础来源之后，缺失时忽略
+        for (path, format) in self.profile_files() {
+            let source = file_source(&path, format, false)?;
+            layers.push((report::Layer::File(path), Box::new(source)));
         }
 
-        // 添加环境变量
-        if let Some(prefix) = self.env_prefix {
-            let env_config = Environment::with_prefix(&prefix)
-                .separator(&self.env_separator)
-                .try_parsing(true)
-                .ignore_empty(true);
-            config_builder = config_builder.add_source(env_config);
+        // .env 文件，优先级介于配置文件与真实环境变量之间
+        for path in &self.dotenv_files {
+            let vars = dotenv::load(path, self.ignore_missing)?;
+            let source = self.environment().source(Some(vars));
+            layers.push((report::Layer::File(path.clone()), Box::new(source)));
         }
 
-        // 构建配置
-        config_builder
-            .build()
-            .map_err(|e| ClamberError::ConfigLoadError {
-                details: e.to_string(),
+        // 环境变量
+        if self.env_prefix.is_some() {
+            layers.push((report::Layer::Env, Box::new(self.environment())));
+        }
+        Ok(layers)
+    }
+
+    /// 基础配置文件对应的 profile 文件
+    fn profile_files(&self) -> Vec<(PathBuf, Option<ConfigFormat>)> {
+        let Some(profile) = &self.profile else {
+            return Vec::new();
+        };
+        self.sources
+            .iter()
+            .filter_map(|source| match source {
+                ConfigSource::File(path, format) => Some((profile_path(path, profile), *format)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 引用的全部文件：配置文件、profile 文件与 `.env`
+    fn file_paths(&self) -> Vec<PathBuf> {
+        self.sources
+            .iter()
+            .filter_map(|source| match source {
+                ConfigSource::File(path, _) => Some(path.clone()),
+                _ => None,
             })
+            .chain(self.profile_files().into_iter().map(|(path, _)| path))
+            .chain(self.dotenv_files.iter().cloned())
+            .collect()
+    }
+
+    /// 按前缀与分隔符配置的环境变量源；未设置前缀时不过滤变量名
+    fn environment(&self) -> Environment {
+        let env = match &self.env_prefix {
+            Some(prefix) => Environment::with_prefix(prefix).prefix_separator("_"),
+            None => Environment::default(),
+        };
+        let mut env = env
+            .separator(&self.env_separator)
+            .try_parsing(true)
+            .ignore_empty(true);
+        // 未指定键时 config crate 会拆分所有字符串，因此只在有键时开启
+        if !self.env_list_keys.is_empty() {
+            env = env.list_separator(self.env_list_separator.as_deref().unwrap_or(","));
+            for key in &self.env_list_keys {
+                env = env.with_list_parse_key(key);
+            }
+        }
+        env
+    }
+}
+
+/// `app.yaml` + `prod` -> `app.prod.yaml`
+fn profile_path(path: &Path, profile: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{}.{}.{}", stem, profile, ext.to_string_lossy()),
+        None => format!("{}.{}", stem, profile),
+    };
+    path.with_file_name(name)
+}
+
+/// 显式传入的 profile 优先，否则读取 [`PROFILE_ENV_VAR`]；均为空时返回 `None`
+fn resolve_profile(profile: Option<&str>) -> Option<String> {
+    profile
+        .map(str::to_string)
+        .or_else(|| env::var(PROFILE_ENV_VAR).ok())
+        .filter(|profile| !profile.is_empty())
+}
+
+fn file_source(
+    path: &Path,
+    format: Option<ConfigFormat>,
+    required: bool,
+) -> Result<File<FileSourceFile, FileFormat>> {
+    let format = format
+        .or_else(|| ConfigFormat::from_extension(path))
+        .ok_or_else(|| ClamberError::ConfigLoadError {
+            details: format!("无法推断配置文件格式: {:?}", path),
+            source: None,
+        })?;
+    Ok(File::from(path)
+        .format(format.to_file_format())
+        .required(required))
+}
+
+/// 用于忽略缺失文件的自动发现：一个文件都不存在且构建失败时，
+/// 返回列出全部候选路径（换行分隔）的 [`ClamberError::ConfigFileNotFoundError`]，而不是字段缺失的反序列化错误
+fn build_probed<T>(builder: ConfigBuilder) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let probed = builder.file_paths();
+    let found = probed.iter().any(|path| path.is_file());
+    match builder.build() {
+        Err(_) if !found => Err(ClamberError::ConfigFileNotFoundError {
+            path: probed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            source: None,
+        }),
+        result => result,
     }
 }
 
+fn check_required_keys(config: &Config, keys: &[String]) -> Result<()> {
+    let missing: Vec<&str> = keys
+        .iter()
+        .filter(|key| {
+            config
+                .get::<config::Value>(key)
+                .map_or(true, |value| matches!(value.kind, ValueKind::Nil))
+        })
+        .map(String::as_str)
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(ClamberError::ConfigValidationError {
+        details: format!("缺少必需的配置项: {}", missing.join(", ")),
+    })
+}
+
+/// 反序列化并在错误信息前加上出错字段的完整路径
+fn deserialize_with_path<T>(config: Config) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    serde_path_to_error::deserialize(config)
+        .map_err(|e| {
+            let path = e.path().to_string();
+            let inner = e.into_inner();
+            let details = if path == "." {
+                inner.to_string()
+            } else {
+                format!("{}: {}", path, inner)
+            };
+            ClamberError::ConfigParseError {
+                details,
+                source: Some(inner.into()),
+            }
+        })
+        .with_context(|| format!("反序列化配置为 {} 时", std::any::type_name::<T>()))
+}
+
+fn deserialize<T>(config: Config) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    config
+        .try_deserialize::<T>()
+        .map_err(|e| ClamberError::ConfigParseError {
+            details: e.to_string(),
+            source: Some(e.into()),
+        })
+        .with_context(|| format!("反序列化配置为 {} 时", std::any::type_name::<T>()))
+}
+
 /// 配置管理器
 pub struct ConfigManager;
 
@@ -258,6 +827,14 @@ impl ConfigManager {
         ConfigBuilder::new().add_file(path, None).build()
     }
 
+    /// 从配置文本加载配置
+    pub fn load_from_str<T>(content: &str, format: ConfigFormat) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        ConfigBuilder::new().add_str(content, format).build()
+    }
+
     /// 从配置文件和环境变量加载配置
     ///
     /// # 参数
@@ -302,7 +879,47 @@ impl ConfigManager {
             builder = builder.with_env_prefix(prefix);
         }
 
-        builder.build()
+        build_probed(builder)
+    }
+
+    /// 将配置写入文件，`format` 为 `None` 时从扩展名推断
+    ///
+    /// 先写入同目录下的临时文件再重命名，写入中途失败不会破坏原文件；父目录不存在时自动创建。
+    pub fn save_to_file<T, P>(value: &T, path: P, format: Option<ConfigFormat>) -> Result<()>
+    where
+        T: Serialize,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let format = format
+            .or_else(|| ConfigFormat::from_extension(path))
+            .ok_or_else(|| ClamberError::ConfigLoadError {
+                details: format!("无法推断配置文件格式: {:?}", path),
+                source: None,
+            })?;
+        let content = config_to_string(value, format)?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| ClamberError::ConfigLoadError {
+                details: format!("无效的配置文件路径: {:?}", path),
+                source: None,
+            })?;
+        let tmp = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| {
+                let _ = std::fs::remove_file(&tmp);
+                ClamberError::from(e)
+            })
+            .with_context(|| format!("写入配置文件 {:?}", path))
     }
 
     /// 创建配置构建器
@@ -330,20 +947,162 @@ where
     ConfigManager::load_with_env(config_path, env_prefix)
 }
 
+/// 便利函数：将配置写入文件，格式从扩展名推断
+pub fn save_config<T, P>(value: &T, path: P) -> Result<()>
+where
+    T: Serialize,
+    P: AsRef<Path>,
+{
+    ConfigManager::save_to_file(value, path, None)
+}
+
+/// 将配置序列化为指定格式的文本
+pub fn config_to_string<T>(value: &T, format: ConfigFormat) -> Result<String>
+where
+    T: Serialize,
+{
+    let serialization_error =
+        |e: Box<dyn std::error::Error + Send + Sync>| ClamberError::SerializationError {
+            details: e.to_string(),
+            source: Some(e),
+        };
+    match format {
+        ConfigFormat::Yaml => {
+            serde_yaml::to_string(value).map_err(|e| serialization_error(e.into()))
+        }
+        ConfigFormat::Toml => {
+            toml::to_string_pretty(value).map_err(|e| serialization_error(e.into()))
+        }
+        ConfigFormat::Json => serde_json::to_string_pretty(value)
+            .map(|json| json + "\n")
+            .map_err(|e| serialization_error(e.into())),
+        // INI 无法表达嵌套结构与类型，只支持读取
+        #[cfg(feature = "ini")]
+        ConfigFormat::Ini => Err(ClamberError::SerializationError {
+            details: "不支持将配置写为 INI 格式".to_string(),
+            source: None,
+        }),
+        // 经 JSON 值中转，枚举写成字符串或单键映射，保证能被 config crate 重新读取
+        #[cfg(feature = "ron")]
+        ConfigFormat::Ron => serde_json::to_value(value)
+            .map_err(|e| serialization_error(e.into()))
+            .and_then(|value| {
+                ron::ser::to_string_pretty(&value, ron::ser::PrettyConfig::default())
+                    .map_err(|e| serialization_error(e.into()))
+            })
+            .map(|ron| ron + "\n"),
+    }
+}
+
+/// 自动发现时依次尝试的扩展名
+const CONFIG_EXTENSIONS: &[&str] = &[
+    "yaml",
+    "yml",
+    "toml",
+    "json",
+    #[cfg(feature = "ini")]
+    "ini",
+    #[cfg(feature = "ron")]
+    "ron",
+];
+
+/// 配置文件搜索范围，见 [`get_config_paths_extended`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigPathOptions {
+    /// 搜索用户配置目录：Unix 为 `$XDG_CONFIG_HOME/{name}`（未设置时为 `~/.config/{name}`），
+    /// Windows 为 `%APPDATA%\{name}`
+    pub user_dir: bool,
+    /// 搜索系统配置目录：Unix 为 `/etc/{name}`，其他平台没有系统目录
+    pub system_dir: bool,
+}
+
+impl Default for ConfigPathOptions {
+    fn default() -> Self {
+        Self {
+            user_dir: true,
+            system_dir: true,
+        }
+    }
+}
+
+impl ConfigPathOptions {
+    /// 只搜索当前工作目录，与 [`get_config_paths`] 相同
+    pub fn local_only() -> Self {
+        Self {
+            user_dir: false,
+            system_dir: false,
+        }
+    }
+}
+
 /// 便利函数：获取当前工作目录下的配置文件路径
 pub fn get_config_paths(name: &str) -> Vec<PathBuf> {
+    get_config_paths_extended(name, ConfigPathOptions::local_only())
+}
+
+/// 获取候选配置文件路径，按优先级从低到高排列（配合 [`ConfigManager::load_multiple`]，后面的覆盖前面的）：
+///
+/// 1. 系统配置目录 `/etc/{name}/`
+/// 2. 用户配置目录 `$XDG_CONFIG_HOME/{name}/`、`~/.config/{name}/` 或 `%APPDATA%\{name}\`
+/// 3. 当前工作目录 `./`
+/// 4. 当前工作目录下的 `./config/`
+///
+/// 每个目录依次尝试 `{name}.yaml`、`{name}.yml`、`{name}.toml`、`{name}.json`，
+/// 启用 `ini` / `ron` feature 时再尝试 `{name}.ini` / `{name}.ron`。
+pub fn get_config_paths_extended(name: &str, options: ConfigPathOptions) -> Vec<PathBuf> {
     let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
 
-    vec![
-        current_dir.join(format!("{}.yaml", name)),
-        current_dir.join(format!("{}.yml", name)),
-        current_dir.join(format!("{}.toml", name)),
-        current_dir.join(format!("{}.json", name)),
-        current_dir.join("config").join(format!("{}.yaml", name)),
-        current_dir.join("config").join(format!("{}.yml", name)),
-        current_dir.join("config").join(format!("{}.toml", name)),
-        current_dir.join("config").join(format!("{}.json", name)),
-    ]
+    let mut dirs = Vec::new();
+    if options.system_dir {
+        dirs.extend(system_config_dir(name));
+    }
+    if options.user_dir {
+        dirs.extend(user_config_dir(name));
+    }
+    dirs.push(current_dir.clone());
+    dirs.push(current_dir.join("config"));
+
+    dirs.iter()
+        .flat_map(|dir| {
+            CONFIG_EXTENSIONS
+                .iter()
+                .map(move |ext| dir.join(format!("{}.{}", name, ext)))
+        })
+        .collect()
+}
+
+/// 非空的绝对路径环境变量（XDG 规范要求忽略相对路径）
+fn env_dir(key: &str) -> Option<PathBuf> {
+    env::var_os(key)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+}
+
+#[cfg(unix)]
+fn user_config_dir(name: &str) -> Option<PathBuf> {
+    env_dir("XDG_CONFIG_HOME")
+        .or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+        .map(|dir| dir.join(name))
+}
+
+#[cfg(windows)]
+fn user_config_dir(name: &str) -> Option<PathBuf> {
+    env_dir("APPDATA").map(|dir| dir.join(name))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn user_config_dir(_name: &str) -> Option<PathBuf> {
+    None
+}
+
+#[cfg(unix)]
+fn system_config_dir(name: &str) -> Option<PathBuf> {
+    Some(Path::new("/etc").join(name))
+}
+
+#[cfg(not(unix))]
+fn system_config_dir(_name: &str) -> Option<PathBuf> {
+    None
 }
 
 /// 便利函数：自动发现并加载配置文件
@@ -351,13 +1110,52 @@ pub fn auto_load_config<T>(name: &str, env_prefix: Option<&str>) -> Result<T>
 where
     T: for<'de> Deserialize<'de>,
 {
-    let config_paths = get_config_paths(name);
+    auto_load_config_with_options(name, env_prefix, ConfigPathOptions::local_only())
+}
+
+/// 便利函数：按 `options` 搜索用户与系统配置目录，自动发现并合并配置文件
+///
+/// 搜索顺序见 [`get_config_paths_extended`]；一个文件都没找到时返回列出全部候选路径的错误。
+pub fn auto_load_config_with_options<T>(
+    name: &str,
+    env_prefix: Option<&str>,
+    options: ConfigPathOptions,
+) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let config_paths = get_config_paths_extended(name, options);
     ConfigManager::load_multiple(config_paths, env_prefix)
 }
 
+/// 便利函数：自动发现并加载配置文件，叠加 profile 对应的文件
+///
+/// `profile` 为 `None` 时读取环境变量 [`PROFILE_ENV_VAR`]；与 [`auto_load_config`] 一样忽略缺失的文件。
+pub fn auto_load_config_with_profile<T>(
+    name: &str,
+    profile: Option<&str>,
+    env_prefix: Option<&str>,
+) -> Result<T>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let mut builder = ConfigBuilder::new().ignore_missing_files(true);
+    for path in get_config_paths(name) {
+        builder = builder.add_file(path, None);
+    }
+    if let Some(profile) = resolve_profile(profile) {
+        builder = builder.with_profile(profile);
+    }
+    if let Some(prefix) = env_prefix {
+        builder = builder.with_env_prefix(prefix);
+    }
+    build_probed(builder)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{EnvGuard, write_temp_config};
     use serde::{Deserialize, Serialize};
     use std::fs;
     use tempfile::tempdir;
@@ -437,7 +1235,7 @@ database:
 
         assert_eq!(config.name, "test-service");
         assert_eq!(config.port, 3000);
-        assert_eq!(config.debug, true);
+        assert!(config.debug);
         assert_eq!(config.database.host, "db.example.com");
     }
 
@@ -464,7 +1262,7 @@ password = "testpass"
 
         assert_eq!(config.name, "test-service");
         assert_eq!(config.port, 3000);
-        assert_eq!(config.debug, true);
+        assert!(config.debug);
         assert_eq!(config.database.host, "db.example.com");
     }
 
@@ -491,10 +1289,96 @@ password = "testpass"
 
         assert_eq!(config.name, "test-service");
         assert_eq!(config.port, 3000);
-        assert_eq!(config.debug, true);
+        assert!(config.debug);
         assert_eq!(config.database.host, "db.example.com");
     }
 
+    #[cfg(feature = "ini")]
+    #[test]
+    fn test_load_ini_config() {
+        let (_dir, config_path) = write_temp_config(
+            ConfigFormat::Ini,
+            "name = test-service\nport = 3000\ndebug = true\n\n[database]\nhost = db.example.com\nport = 5432\nusername = testuser\npassword = testpass\n",
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(&config_path),
+            Some(ConfigFormat::Ini)
+        );
+
+        let config: TestConfig = ConfigManager::load_from_file(&config_path).unwrap();
+        assert_eq!(config.name, "test-service");
+        assert_eq!(config.port, 3000);
+        assert!(config.debug);
+        assert_eq!(config.database.host, "db.example.com");
+        assert_eq!(config.database.port, 5432);
+
+        let config: TestConfig = ConfigBuilder::new()
+            .add_ini_file(&config_path)
+            .build()
+            .unwrap();
+        assert_eq!(config.database.username, "testuser");
+
+        // INI 只支持读取
+        let err =
+            ConfigManager::save_to_file(&TestConfig::default(), &config_path, None).unwrap_err();
+        assert!(
+            matches!(err, ClamberError::SerializationError { .. }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn test_load_ron_config() {
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        enum Mode {
+            Standalone,
+            Cluster { nodes: u8 },
+        }
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct RonConfig {
+            name: String,
+            mode: Mode,
+            fallback: Mode,
+            bind: (String, u16),
+            weights: Vec<(String, u8)>,
+        }
+
+        let (_dir, config_path) = write_temp_config(
+            ConfigFormat::Ron,
+            r#"(
+    name: "test-service",
+    mode: {"Cluster": (nodes: 3)},
+    fallback: "Standalone",
+    bind: ("0.0.0.0", 8080),
+    weights: [("primary", 3), ("replica", 1)],
+)"#,
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(&config_path),
+            Some(ConfigFormat::Ron)
+        );
+
+        let config: RonConfig = ConfigBuilder::new()
+            .add_ron_file(&config_path)
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "test-service");
+        assert_eq!(config.mode, Mode::Cluster { nodes: 3 });
+        assert_eq!(config.fallback, Mode::Standalone);
+        assert_eq!(config.bind, ("0.0.0.0".to_string(), 8080));
+        assert_eq!(
+            config.weights,
+            [("primary".to_string(), 3), ("replica".to_string(), 1)]
+        );
+
+        ConfigManager::save_to_file(&config, &config_path, None).unwrap();
+        let reloaded: RonConfig = ConfigManager::load_from_file(&config_path).unwrap();
+        assert_eq!(reloaded, config);
+    }
+
     #[test]
     fn test_config_builder_with_defaults() {
         let config: TestConfig = ConfigBuilder::new()
@@ -517,16 +1401,13 @@ password = "testpass"
 
         assert_eq!(config.name, "default-app");
         assert_eq!(config.port, 9000);
-        assert_eq!(config.debug, false);
+        assert!(!config.debug);
         assert_eq!(config.database.host, "default-host");
         assert_eq!(config.database.port, 3306);
     }
 
     #[test]
     fn test_config_with_env_override() {
-        let dir = tempdir().unwrap();
-        let config_path = dir.path().join("config.yaml");
-
         let yaml_content = r#"
 name: "test-service"
 port: 3000
@@ -537,29 +1418,20 @@ database:
   username: "user"
   password: "password"
 "#;
+        let (_dir, config_path) = write_temp_config(ConfigFormat::Yaml, yaml_content);
 
-        fs::write(&config_path, yaml_content).unwrap();
-
-        // 设置环境变量
-        unsafe {
-            env::set_var("TEST_PORT", "8080");
-            env::set_var("TEST_DEBUG", "true");
-            env::set_var("TEST_DATABASE__HOST", "env-db-host");
-        }
+        // 设置环境变量，guard drop 时恢复
+        let _env = EnvGuard::new()
+            .set("TEST_PORT", "8080")
+            .set("TEST_DEBUG", "true")
+            .set("TEST_DATABASE__HOST", "env-db-host");
 
         let config: TestConfig = ConfigManager::load_with_env(&config_path, "TEST").unwrap();
 
         assert_eq!(config.name, "test-service"); // 从文件
         assert_eq!(config.port, 8080); // 从环境变量覆盖
-        assert_eq!(config.debug, true); // 从环境变量覆盖
+        assert!(config.debug); // 从环境变量覆盖
         assert_eq!(config.database.host, "env-db-host"); // 从环境变量覆盖
-
-        // 清理环境变量
-        unsafe {
-            env::remove_var("TEST_PORT");
-            env::remove_var("TEST_DEBUG");
-            env::remove_var("TEST_DATABASE__HOST");
-        }
     }
 
     #[test]
@@ -598,7 +1470,7 @@ database:
 
         assert_eq!(config.name, "base-service"); // 从基础配置
         assert_eq!(config.port, 9000); // 被覆盖
-        assert_eq!(config.debug, true); // 被覆盖
+        assert!(config.debug); // 被覆盖
         assert_eq!(config.database.host, "override-host"); // 被覆盖
         assert_eq!(config.database.username, "base-user"); // 从基础配置
     }
@@ -631,6 +1503,390 @@ database:
             && p.to_string_lossy().ends_with("myapp.yaml")));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_get_config_paths_extended() {
+        let dir = tempdir().unwrap();
+        let xdg = dir.path().join("xdg");
+        let home = dir.path().join("home");
+        let local = get_config_paths("myapp");
+
+        let env = EnvGuard::new()
+            .set("XDG_CONFIG_HOME", xdg.to_str().unwrap())
+            .set("HOME", home.to_str().unwrap());
+        let paths = get_config_paths_extended("myapp", ConfigPathOptions::default());
+        assert_eq!(paths[0], Path::new("/etc/myapp/myapp.yaml"));
+        let per_dir = CONFIG_EXTENSIONS.len();
+        assert_eq!(paths[per_dir], xdg.join("myapp").join("myapp.yaml"));
+        assert_eq!(paths[per_dir * 2..], local[..]);
+        assert_eq!(paths.len(), per_dir * 4);
+
+        // 未设置 XDG_CONFIG_HOME（或为相对路径）时回退到 ~/.config
+        let env = env.set("XDG_CONFIG_HOME", "relative");
+        let options = ConfigPathOptions {
+            system_dir: false,
+            ..Default::default()
+        };
+        let paths = get_config_paths_extended("myapp", options);
+        assert_eq!(paths[0], home.join(".config/myapp/myapp.yaml"));
+        assert_eq!(paths.len(), per_dir * 3);
+
+        // 自动发现用户目录中的配置文件
+        let user_dir = home.join(".config").join("myapp");
+        fs::create_dir_all(&user_dir).unwrap();
+        fs::write(
+            user_dir.join("myapp.yaml"),
+            test_config_yaml(7000, "user-host"),
+        )
+        .unwrap();
+        let _env = env.remove("XDG_CONFIG_HOME");
+        let config: TestConfig = auto_load_config_with_options("myapp", None, options).unwrap();
+        assert_eq!(config.port, 7000);
+        assert_eq!(config.database.host, "user-host");
+    }
+
+    impl Validate for TestConfig {
+        fn validate(&self) -> Result<()> {
+            if self.database.host.is_empty() {
+                return Err(ClamberError::ConfigValidationError {
+                    details: "database.host 不能为空".to_string(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    fn test_config_yaml(port: u16, host: &str) -> String {
+        format!(
+            "name: svc\nport: {}\ndebug: false\ndatabase:\n  host: \"{}\"\n  port: 5432\n  username: u\n  password: p\n",
+            port, host
+        )
+    }
+
+    #[test]
+    fn test_with_validator() {
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, &test_config_yaml(8080, "db"));
+        let check_port = |config: &TestConfig| {
+            if config.port == 0 {
+                Err("port 不能为 0".to_string())
+            } else {
+                Ok(())
+            }
+        };
+
+        let config: TestConfig = ConfigBuilder::new()
+            .add_file(&path, None)
+            .with_validator(check_port)
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 8080);
+
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, &test_config_yaml(0, "db"));
+        match ConfigBuilder::new()
+            .add_file(&path, None)
+            .with_validator(check_port)
+            .build::<TestConfig>()
+        {
+            Err(ClamberError::ConfigValidationError { details }) => {
+                assert_eq!(details, "port 不能为 0")
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_require_keys_reports_all_missing() {
+        let builder = ConfigBuilder::new()
+            .add_yaml_str("name: svc\ndatabase:\n  username: u\n  password: ~\n")
+            .require_keys(&["name", "database.host", "database.password", "port"]);
+
+        let err = builder.clone().build_view().unwrap_err();
+        assert!(matches!(err, ClamberError::ConfigValidationError { .. }));
+        assert_eq!(
+            err.to_string(),
+            "配置验证失败: 缺少必需的配置项: database.host, database.password, port"
+        );
+
+        let view = ConfigBuilder::new()
+            .add_yaml_str("name: svc\nport: 80\n")
+            .require_keys(&["name", "port"])
+            .build_view()
+            .unwrap();
+        assert_eq!(view.get_int("port").unwrap(), 80);
+
+        // 类型错误带出字段的完整路径，与缺失的键一起返回
+        let builder = builder
+            .add_yaml_str("port: 80\ndebug: true\ndatabase:\n  host: db\n  password: p\n")
+            .require_keys(&["cache.url"]);
+        let err = builder
+            .clone()
+            .add_yaml_str("database:\n  port: [1]\n")
+            .build_with_all_errors::<TestConfig>()
+            .unwrap_err();
+        let ClamberError::Multiple(errors) = &err else {
+            panic!("expected multiple errors, got {:?}", err);
+        };
+        assert_eq!(errors.len(), 2);
+        assert!(
+            errors[0]
+                .to_string()
+                .contains("缺少必需的配置项: cache.url")
+        );
+        assert!(
+            errors[1]
+                .to_string()
+                .contains("database.port: invalid type")
+        );
+
+        let err = builder
+            .add_yaml_str("database:\n  port: 5432\n")
+            .with_validator(|config: &TestConfig| Err(format!("{} 未通过校验", config.name)))
+            .build_with_all_errors::<TestConfig>()
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("cache.url"), "{}", message);
+        assert!(message.contains("svc 未通过校验"), "{}", message);
+    }
+
+    #[test]
+    fn test_build_validated() {
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, &test_config_yaml(8080, "db"));
+        let config: TestConfig = ConfigBuilder::new()
+            .add_file(&path, None)
+            .build_validated()
+            .unwrap();
+        assert_eq!(config.database.host, "db");
+
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, &test_config_yaml(8080, ""));
+        let err = ConfigBuilder::new()
+            .add_file(&path, None)
+            .build_validated::<TestConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("database.host 不能为空"));
+        assert_eq!(err.code(), "CFG-004");
+    }
+
+    #[test]
+    fn test_dotenv_precedence() {
+        let (dir, path) =
+            write_temp_config(ConfigFormat::Yaml, &test_config_yaml(3000, "file-host"));
+        let dotenv_path = dir.path().join(".env");
+        fs::write(
+            &dotenv_path,
+            "# 部署环境\nDOTENV_PORT=7000\nDOTENV_DATABASE__HOST=\"dotenv host\"\nOTHER_NAME=ignored\n",
+        )
+        .unwrap();
+
+        let build = || {
+            ConfigBuilder::new()
+                .add_file(&path, None)
+                .add_dotenv_file(&dotenv_path)
+                .with_env_prefix("DOTENV")
+                .build::<TestConfig>()
+                .unwrap()
+        };
+
+        let env = EnvGuard::new().remove("DOTENV_PORT");
+        let config = build();
+        assert_eq!(config.port, 7000);
+        assert_eq!(config.database.host, "dotenv host");
+        assert_eq!(config.name, "svc");
+
+        // 真实环境变量优先于 .env
+        drop(env);
+        let _env = EnvGuard::new().set("DOTENV_PORT", "9000");
+        let config = build();
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.database.host, "dotenv host");
+    }
+
+    #[test]
+    fn test_missing_dotenv() {
+        let (dir, path) = write_temp_config(ConfigFormat::Yaml, &test_config_yaml(3000, "db"));
+        let missing = dir.path().join("missing.env");
+
+        let result = ConfigBuilder::new()
+            .add_file(&path, None)
+            .add_dotenv_file(&missing)
+            .build::<TestConfig>();
+        assert!(matches!(
+            result,
+            Err(ClamberError::ConfigFileNotFoundError { .. })
+        ));
+
+        let config: TestConfig = ConfigBuilder::new()
+            .add_file(&path, None)
+            .add_dotenv_file(&missing)
+            .ignore_missing_files(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 3000);
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct NestedConfig {
+        name: String,
+        tags: Vec<String>,
+        limits: std::collections::BTreeMap<String, u32>,
+        database: DatabaseConfig,
+        replicas: Vec<DatabaseConfig>,
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let value = NestedConfig {
+            name: "svc".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+            limits: [("cpu".to_string(), 2), ("memory".to_string(), 512)].into(),
+            database: TestConfig::default().database,
+            replicas: vec![
+                TestConfig::default().database,
+                TestConfig::default().database,
+            ],
+        };
+
+        let dir = tempdir().unwrap();
+        for ext in ["yaml", "toml", "json"] {
+            let path = dir.path().join("nested").join(format!("config.{}", ext));
+            save_config(&value, &path).unwrap();
+            let loaded: NestedConfig = load_config(&path).unwrap();
+            assert_eq!(loaded, value, "{}", ext);
+        }
+        // 只留下目标文件，没有残留的临时文件
+        assert_eq!(fs::read_dir(dir.path().join("nested")).unwrap().count(), 3);
+
+        // 显式指定格式时不依赖扩展名
+        let path = dir.path().join("config.conf");
+        ConfigManager::save_to_file(&value, &path, Some(ConfigFormat::Toml)).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text, config_to_string(&value, ConfigFormat::Toml).unwrap());
+        assert!(ConfigManager::save_to_file(&value, &path, None).is_err());
+    }
+
+    #[test]
+    fn test_profile_overrides_base() {
+        let (dir, base) = write_temp_config(ConfigFormat::Yaml, &test_config_yaml(3000, "base-db"));
+        fs::write(
+            dir.path().join("config.prod.yaml"),
+            "port: 9000\ndatabase:\n  host: prod-db\n",
+        )
+        .unwrap();
+
+        let config: TestConfig = ConfigBuilder::new()
+            .add_file(&base, None)
+            .with_profile("prod")
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.database.host, "prod-db");
+        assert_eq!(config.database.username, "u"); // 来自基础文件
+
+        // profile 文件缺失时只用基础文件
+        let config: TestConfig = ConfigBuilder::new()
+            .add_file(&base, None)
+            .with_profile("test")
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 3000);
+
+        // 基础文件缺失仍按 ignore_missing 处理
+        let result = ConfigBuilder::new()
+            .add_file(dir.path().join("missing.yaml"), None)
+            .with_profile("prod")
+            .build::<TestConfig>();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_resolve_profile() {
+        assert_eq!(
+            profile_path(Path::new("/etc/app.yaml"), "prod"),
+            Path::new("/etc/app.prod.yaml")
+        );
+
+        let env = EnvGuard::new().set(PROFILE_ENV_VAR, "staging");
+        assert_eq!(resolve_profile(None).as_deref(), Some("staging"));
+        assert_eq!(resolve_profile(Some("prod")).as_deref(), Some("prod"));
+        drop(env);
+
+        let _env = EnvGuard::new().remove(PROFILE_ENV_VAR);
+        assert_eq!(resolve_profile(None), None);
+    }
+
+    #[test]
+    fn test_inline_sources_merge() {
+        let (_dir, path) =
+            write_temp_config(ConfigFormat::Yaml, &test_config_yaml(3000, "file-db"));
+
+        let _env = EnvGuard::new().set("INLINE_DATABASE__USERNAME", "env-user");
+        let config: TestConfig = ConfigBuilder::new()
+            .add_file(&path, None)
+            .add_toml_str("port = 4000\n[database]\nhost = \"inline-db\"\nusername = \"inline\"\n")
+            .with_env_prefix("INLINE")
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "svc"); // 来自文件
+        assert_eq!(config.port, 4000); // 内联覆盖文件
+        assert_eq!(config.database.host, "inline-db");
+        assert_eq!(config.database.username, "env-user"); // 环境变量覆盖内联
+
+        // 先内联后文件：文件覆盖内联
+        let raw = ConfigBuilder::new()
+            .add_json_str(r#"{"port": 1, "extra": true}"#)
+            .add_file(&path, None)
+            .build_raw()
+            .unwrap();
+        assert_eq!(raw.get_int("port").unwrap(), 3000);
+        assert!(raw.get_bool("extra").unwrap());
+
+        let config: TestConfig =
+            ConfigManager::load_from_str(&test_config_yaml(5000, "db"), ConfigFormat::Yaml)
+                .unwrap();
+        assert_eq!(config.port, 5000);
+        assert!(ConfigManager::load_from_str::<TestConfig>("port: [", ConfigFormat::Yaml).is_err());
+    }
+
+    #[test]
+    fn test_env_list_values() {
+        #[derive(Debug, Deserialize)]
+        struct ListConfig {
+            replicas: Vec<String>,
+            ports: Vec<u16>,
+            motto: String,
+        }
+
+        let base = "replicas: []\nports: []\nmotto: base\n";
+        {
+            let _env = EnvGuard::new()
+                .set("LIST_REPLICAS", "host1;host2;host3")
+                .set("LIST_PORTS", "8080;8081")
+                .set("LIST_MOTTO", "fast;cheap");
+            let config: ListConfig = ConfigBuilder::new()
+                .add_yaml_str(base)
+                .with_env_prefix("LIST")
+                .with_env_list_separator(";")
+                .env_list_keys(&["replicas", "ports"])
+                .build()
+                .unwrap();
+            assert_eq!(config.replicas, ["host1", "host2", "host3"]);
+            assert_eq!(config.ports, [8080, 8081]);
+            assert_eq!(config.motto, "fast;cheap"); // 未指定的键不拆分
+        }
+
+        // 默认分隔符为逗号
+        let _env = EnvGuard::new()
+            .set("LIST_REPLICAS", "a,b")
+            .set("LIST_MOTTO", "x,y");
+        let config: ListConfig = ConfigBuilder::new()
+            .add_yaml_str(base)
+            .with_env_prefix("LIST")
+            .env_list_keys(&["replicas"])
+            .build()
+            .unwrap();
+        assert_eq!(config.replicas, ["a", "b"]);
+        assert_eq!(config.motto, "x,y");
+    }
+
     #[test]
     fn test_ignore_missing_files() {
         let dir = tempdir().unwrap();
@@ -656,7 +1912,18 @@ database:
             .add_file(&missing_config, None)
             .ignore_missing_files(false)
             .build();
-        assert!(result.is_err());
+        match result {
+            Err(ClamberError::ConfigFileNotFoundError { path, source }) => {
+                assert_eq!(path, missing_config.display().to_string());
+                // 底层 IO 错误通过 source 链保留
+                let io = source.unwrap();
+                assert_eq!(
+                    io.downcast_ref::<std::io::Error>().unwrap().kind(),
+                    std::io::ErrorKind::NotFound
+                );
+            }
+            other => panic!("expected file not found, got {:?}", other),
+        }
 
         // 忽略缺失文件时应该成功
         let config: TestConfig = ConfigBuilder::new()
@@ -667,5 +1934,17 @@ database:
             .unwrap();
 
         assert_eq!(config.name, "test-service");
+
+        // 候选文件全部缺失：列出全部候选路径
+        let other_missing = dir.path().join("other.yaml");
+        let result: Result<TestConfig> =
+            ConfigManager::load_multiple(vec![&missing_config, &other_missing], None::<&str>);
+        match result {
+            Err(ClamberError::ConfigFileNotFoundError { path, .. }) => assert_eq!(
+                path,
+                format!("{}\n{}", missing_config.display(), other_missing.display())
+            ),
+            other => panic!("expected file not found, got {:?}", other),
+        }
     }
 }
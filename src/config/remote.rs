@@ -0,0 +1,388 @@
+//! 远程配置来源：构建时通过 HTTP(S) GET 获取配置文档
+//!
+//! 基于 ureq 的阻塞客户端，最多跟随 5 次重定向（跨域名时不转发 `Authorization`）；HTTPS 使用系统 OpenSSL 与系统根证书。
+use super::ConfigFormat;
+use crate::error::{ClamberError, Result};
+use std::fmt;
+use std::time::Duration;
+use ureq::Agent;
+use ureq::config::RedirectAuthHeaders;
+use ureq::http::Uri;
+use ureq::tls::{RootCerts, TlsConfig, TlsProvider};
+
+/// 远程配置的默认超时（从解析域名到读完响应体，含重定向）
+pub(super) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// 响应体上限（不含），防止误配的地址返回超大内容
+const MAX_RESPONSE_BYTES: u64 = 16 * 1024 * 1024;
+/// 最多跟随的重定向次数
+const MAX_REDIRECTS: u32 = 5;
+
+/// 通过 [`super::ConfigBuilder::add_url`] 添加的远程配置
+#[derive(Clone)]
+pub(super) struct RemoteSource {
+    pub(super) url: String,
+    pub(super) format: ConfigFormat,
+    pub(super) token: Option<String>,
+}
+
+// 不在 Debug 输出中暴露 token
+impl fmt::Debug for RemoteSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSource")
+            .field("url", &self.url)
+            .field("format", &self.format)
+            .field("token", &self.token.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl RemoteSource {
+    /// 获取配置文档；返回 404 且 `ignore_missing` 时为 `None`
+    pub(super) fn fetch(&self, timeout: Duration, ignore_missing: bool) -> Result<Option<String>> {
+        self.fetch_with_limit(timeout, ignore_missing, MAX_RESPONSE_BYTES)
+    }
+
+    fn fetch_with_limit(
+        &self,
+        timeout: Duration,
+        ignore_missing: bool,
+        limit: u64,
+    ) -> Result<Option<String>> {
+        let uri: Uri = self
+            .url
+            .parse()
+            .ok()
+            .filter(|uri: &Uri| matches!(uri.scheme_str(), Some("http" | "https")))
+            .filter(|uri| uri.host().is_some_and(|host| !host.is_empty()))
+            .ok_or_else(|| {
+                self.error(
+                    "地址格式无效，应为 http(s)://host[:port]/path".to_string(),
+                    None,
+                )
+            })?;
+        let mut request = agent(timeout).get(uri);
+        if let Some(token) = &self.token {
+            // 拒绝 CR/LF 等控制字符，防止注入额外的请求头
+            if token.is_empty() || token.chars().any(|c| c.is_control()) {
+                return Err(self.error("token 为空或含有控制字符".to_string(), None));
+            }
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+
+        let mut response = request.call().map_err(|e| self.http_error(e))?;
+        let status = response.status().as_u16();
+        match status {
+            200..=299 => response
+                .body_mut()
+                .with_config()
+                .limit(limit)
+                .read_to_string()
+                .map(Some)
+                .map_err(|e| self.http_error(e)),
+            404 if ignore_missing => Ok(None),
+            _ => Err(self.error(format!("服务器返回 HTTP {}", status), None)),
+        }
+    }
+
+    fn http_error(&self, e: ureq::Error) -> ClamberError {
+        let reason = match &e {
+            ureq::Error::BodyExceedsLimit(limit) => format!("响应体超过 {} 字节", limit),
+            ureq::Error::TooManyRedirects => format!("重定向超过 {} 次", MAX_REDIRECTS),
+            e => e.to_string(),
+        };
+        self.error(reason, Some(Box::new(e)))
+    }
+
+    fn error(&self, reason: String, source: Option<crate::error::BoxError>) -> ClamberError {
+        ClamberError::ConfigLoadError {
+            details: format!("获取远程配置 {} 失败: {}", self.url, reason),
+            source,
+        }
+    }
+}
+
+fn agent(timeout: Duration) -> Agent {
+    Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .http_status_as_error(false)
+        .max_redirects(MAX_REDIRECTS)
+        .redirect_auth_headers(RedirectAuthHeaders::SameHost)
+        .user_agent(format!("clamber-core/{}", env!("CARGO_PKG_VERSION")))
+        .tls_config(
+            TlsConfig::builder()
+                .provider(TlsProvider::NativeTls)
+                .root_certs(RootCerts::PlatformVerifier)
+                .build(),
+        )
+        .build()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+    use serde::Deserialize;
+    use std::io::{BufRead, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[derive(Debug, Deserialize)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+        debug: bool,
+    }
+
+    /// 在后台线程中响应请求：`/app.yaml` 需要 Bearer token，`/open.yaml` 以 chunked 返回，
+    /// `/moved.yaml` 与 `/secure-moved.yaml` 分别重定向到 `/open.yaml` 与 `/app.yaml`，`/loop.yaml` 重定向到自身，`/stream.yaml` 不带长度直到关闭连接，
+    /// `/hits.yaml` 返回累计请求次数，其他路径 404
+    fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let mut hits = 0;
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut authorized = false;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    authorized |= line
+                        .trim()
+                        .eq_ignore_ascii_case("authorization: Bearer t0ken");
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let response = match path {
+                    "/app.yaml" if authorized => {
+                        let body = "name: remote\nport: 9090\n";
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    }
+                    "/app.yaml" => "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\n\r\n".into(),
+                    "/open.yaml" => "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                                     6\r\nname: \r\n5\r\nopen\n\r\nb\r\nport: 7070\n\r\n0\r\n\r\n"
+                        .into(),
+                    "/moved.yaml" => {
+                        "HTTP/1.1 302 Found\r\nLocation: /open.yaml\r\nContent-Length: 0\r\n\r\n"
+                            .into()
+                    }
+                    "/secure-moved.yaml" => "HTTP/1.1 301 Moved Permanently\r\nLocation: /app.yaml\r\nContent-Length: 0\r\n\r\n".into(),
+                    "/loop.yaml" => {
+                        "HTTP/1.1 302 Found\r\nLocation: /loop.yaml\r\nContent-Length: 0\r\n\r\n"
+                            .into()
+                    }
+                    "/hits.yaml" => {
+                        hits += 1;
+                        let body = format!("hits: {}\n", hits);
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        )
+                    }
+                    "/stream.yaml" => {
+                        "HTTP/1.1 200 OK\r\nConnection: close\r\n\r\nname: streamed\nport: 6060\n"
+                            .into()
+                    }
+                    _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".into(),
+                };
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_url_precedence_and_auth() {
+        let base = serve();
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("local.yaml");
+        std::fs::write(&local, "name: local\nport: 8080\ndebug: true\n").unwrap();
+
+        // 按声明顺序合并：后添加的覆盖先添加的
+        let config: AppConfig = ConfigBuilder::new()
+            .add_file(&local, None)
+            .add_url_with_auth(&format!("{}/app.yaml", base), ConfigFormat::Yaml, "t0ken")
+            .build()
+            .unwrap();
+        assert_eq!(
+            (config.name.as_str(), config.port, config.debug),
+            ("remote", 9090, true)
+        );
+
+        let config: AppConfig = ConfigBuilder::new()
+            .add_url(&format!("{}/open.yaml", base), ConfigFormat::Yaml)
+            .add_file(&local, None)
+            .build()
+            .unwrap();
+        assert_eq!((config.name.as_str(), config.port), ("local", 8080));
+
+        let config: AppConfig = ConfigBuilder::new()
+            .add_file(&local, None)
+            .add_url(&format!("{}/open.yaml", base), ConfigFormat::Yaml)
+            .build()
+            .unwrap();
+        assert_eq!((config.name.as_str(), config.port), ("open", 7070));
+
+        let err = ConfigBuilder::new()
+            .add_url(&format!("{}/app.yaml", base), ConfigFormat::Yaml)
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(matches!(err, ClamberError::ConfigLoadError { .. }));
+        assert!(err.to_string().contains("HTTP 401"), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_and_unreachable() {
+        let base = serve();
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("local.yaml");
+        std::fs::write(&local, "name: local\nport: 8080\ndebug: false\n").unwrap();
+        let missing = format!("{}/missing.yaml", base);
+
+        let err = ConfigBuilder::new()
+            .add_file(&local, None)
+            .add_url(&missing, ConfigFormat::Yaml)
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("HTTP 404"), "{}", err);
+
+        let config: AppConfig = ConfigBuilder::new()
+            .add_file(&local, None)
+            .add_url(&missing, ConfigFormat::Yaml)
+            .ignore_missing_files(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "local");
+
+        // 接受连接但不响应的服务器触发读取超时
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let err = ConfigBuilder::new()
+            .add_url(
+                &format!("http://{}/app.yaml", silent.local_addr().unwrap()),
+                ConfigFormat::Yaml,
+            )
+            .with_remote_timeout(Duration::from_millis(200))
+            .ignore_missing_files(true)
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(
+            matches!(err, ClamberError::ConfigLoadError { .. }),
+            "{}",
+            err
+        );
+
+        let err = ConfigBuilder::new()
+            .add_url("ftp://example.com/app.yaml", ConfigFormat::Yaml)
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("地址格式无效"), "{}", err);
+    }
+
+    #[test]
+    fn test_redirect_and_body_limit() {
+        let base = serve();
+        let remote = |path: &str| RemoteSource {
+            url: format!("{}{}", base, path),
+            format: ConfigFormat::Yaml,
+            token: None,
+        };
+
+        let config: AppConfig = ConfigBuilder::new()
+            .add_url(&format!("{}/moved.yaml", base), ConfigFormat::Yaml)
+            .add_yaml_str("debug: false\n")
+            .build()
+            .unwrap();
+        assert_eq!((config.name.as_str(), config.port), ("open", 7070));
+        // 同一主机内的重定向保留 token
+        let secure = RemoteSource {
+            token: Some("t0ken".to_string()),
+            ..remote("/secure-moved.yaml")
+        };
+        assert_eq!(
+            secure.fetch(DEFAULT_TIMEOUT, false).unwrap().as_deref(),
+            Some("name: remote\nport: 9090\n")
+        );
+        let err = remote("/loop.yaml")
+            .fetch(DEFAULT_TIMEOUT, false)
+            .unwrap_err();
+        assert!(err.to_string().contains("重定向超过"), "{}", err);
+
+        // 没有 Content-Length 的响应体读到连接关闭为止，超出上限时报错而不是截断
+        let body = "name: streamed\nport: 6060\n";
+        assert_eq!(
+            remote("/stream.yaml")
+                .fetch_with_limit(DEFAULT_TIMEOUT, false, body.len() as u64 + 1)
+                .unwrap()
+                .as_deref(),
+            Some(body)
+        );
+        let err = remote("/stream.yaml")
+            .fetch_with_limit(DEFAULT_TIMEOUT, false, body.len() as u64)
+            .unwrap_err();
+        assert!(err.to_string().contains("响应体超过"), "{}", err);
+    }
+
+    #[test]
+    fn test_report_fetches_once() {
+        let base = serve();
+        let url = format!("{}/hits.yaml", base);
+        let (config, report) = ConfigBuilder::new()
+            .add_url(&url, ConfigFormat::Yaml)
+            .build_with_report::<std::collections::HashMap<String, u32>>()
+            .unwrap();
+        assert_eq!(config["hits"], 1);
+        let info = report.lookup("hits").unwrap();
+        assert_eq!(info.value.clone().into_int().unwrap(), 1);
+        assert_eq!(info.source, crate::config::ValueSource::Url(url.clone()));
+
+        // 构建与报告共用一次请求
+        let remote = RemoteSource {
+            url,
+            format: ConfigFormat::Yaml,
+            token: None,
+        };
+        assert_eq!(
+            remote.fetch(DEFAULT_TIMEOUT, false).unwrap().as_deref(),
+            Some("hits: 2\n")
+        );
+    }
+
+    #[test]
+    fn test_rejects_header_injection() {
+        let base = serve();
+        let err = ConfigBuilder::new()
+            .add_url_with_auth(
+                &format!("{}/app.yaml", base),
+                ConfigFormat::Yaml,
+                "t0ken\r\nX-Injected: 1",
+            )
+            .build::<AppConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("控制字符"), "{}", err);
+        // token 不出现在错误信息中
+        assert!(!err.to_string().contains("t0ken"), "{}", err);
+
+        for url in [
+            format!("{}/app.yaml\r\nX-Injected: 1", base),
+            format!("{}/app yaml", base),
+            "http:///app.yaml".to_string(),
+        ] {
+            let err = ConfigBuilder::new()
+                .add_url(&url, ConfigFormat::Yaml)
+                .build::<AppConfig>()
+                .unwrap_err();
+            assert!(err.to_string().contains("地址格式无效"), "{}", err);
+        }
+    }
+}
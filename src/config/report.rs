@@ -0,0 +1,271 @@
+//! 配置来源报告：逐个来源单独收集键路径，按合并顺序比对出每个最终值来自哪里。
+use super::ConfigBuilder;
+use crate::error::{ClamberError, Result};
+use config::{Config, Map, Source, Value, ValueKind};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+/// 配置值的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueSource {
+    /// [`ConfigBuilder::with_default`] 设置的默认值
+    Default,
+    /// 配置文件（含 profile 文件与 `.env` 文件）
+    File(PathBuf),
+    /// 内联配置文本，值为其在内联来源中的序号（从 0 开始）
+    Inline(usize),
+    /// 远程配置，值为地址
+    #[cfg(feature = "remote-config")]
+    Url(String),
+    /// 环境变量，值为变量名
+    Env(String),
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Default => write!(f, "默认值"),
+            ValueSource::File(path) => write!(f, "文件 {}", path.display()),
+            ValueSource::Inline(index) => write!(f, "内联配置 #{}", index),
+            #[cfg(feature = "remote-config")]
+            ValueSource::Url(url) => write!(f, "远程配置 {}", url),
+            ValueSource::Env(name) => write!(f, "环境变量 {}", name),
+        }
+    }
+}
+
+/// 单个键的最终值与来源
+#[derive(Debug, Clone)]
+pub struct SourceInfo {
+    /// 合并（及插值）后的最终值
+    pub value: Value,
+    /// 提供最终值的来源
+    pub source: ValueSource,
+}
+
+/// 默认值之上的合并来源
+pub(super) enum Layer {
+    File(PathBuf),
+    Inline(usize),
+    #[cfg(feature = "remote-config")]
+    Url(String),
+    /// 带前缀的真实环境变量，变量名在生成报告时逐键补全
+    Env,
+}
+
+/// 来源标识与已读取的内容
+pub(super) type LayerSource = (Layer, Box<dyn Source + Send + Sync>);
+
+/// 配置来源报告，见 [`ConfigBuilder::build_with_report`]
+///
+/// 报告包含原始值（含密码等敏感信息），输出到日志前注意脱敏。
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    entries: BTreeMap<String, SourceInfo>,
+}
+
+impl ConfigReport {
+    pub(super) fn collect(
+        builder: &ConfigBuilder,
+        merged: &Config,
+        layers: &[LayerSource],
+    ) -> Result<Self> {
+        let mut sources = BTreeMap::new();
+
+        let mut defaults = Config::builder();
+        for (key, value) in &builder.defaults {
+            defaults = defaults.set_default(key, value.clone())?;
+        }
+        for key in leaf_paths(&defaults.build()?.cache) {
+            sources.insert(key, ValueSource::Default);
+        }
+
+        // 后出现的来源覆盖先出现的
+        for (layer, source) in layers {
+            let mut cache = Value::new(None, ValueKind::Table(Map::new()));
+            source.collect_to(&mut cache).map_err(ClamberError::from)?;
+            for key in leaf_paths(&cache) {
+                let source = match layer {
+                    Layer::File(path) => ValueSource::File(path.clone()),
+                    Layer::Inline(index) => ValueSource::Inline(*index),
+                    #[cfg(feature = "remote-config")]
+                    Layer::Url(url) => ValueSource::Url(url.clone()),
+                    Layer::Env => ValueSource::Env(env_var_name(builder, &key)),
+                };
+                sources.insert(key, source);
+            }
+        }
+
+        let mut entries = BTreeMap::new();
+        collect_leaves(&merged.cache, String::new(), &mut |key, value| {
+            if let Some(source) = sources.remove(&key) {
+                entries.insert(
+                    key,
+                    SourceInfo {
+                        value: value.clone(),
+                        source,
+                    },
+                );
+            }
+        });
+        Ok(Self { entries })
+    }
+
+    /// 查询键路径（如 `database.host`）的最终值与来源
+    pub fn lookup(&self, key: &str) -> Option<&SourceInfo> {
+        self.entries.get(key)
+    }
+
+    /// 按键路径排序遍历
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SourceInfo)> {
+        self.entries.iter().map(|(key, info)| (key.as_str(), info))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 每行一个键：`键 = 值  (来源)`，键按宽度对齐
+    pub fn to_string_pretty(&self) -> String {
+        let width = self.entries.keys().map(|key| key.len()).max().unwrap_or(0);
+        let mut output = String::new();
+        for (key, info) in &self.entries {
+            output.push_str(&format!(
+                "{:width$} = {}  ({})\n",
+                key,
+                render(&info.value),
+                info.source,
+                width = width
+            ));
+        }
+        output
+    }
+}
+
+/// 叶子节点的键路径；数组整体视为一个值
+pub(super) fn leaf_paths(value: &Value) -> Vec<String> {
+    let mut keys = Vec::new();
+    collect_leaves(value, String::new(), &mut |key, _| keys.push(key));
+    keys
+}
+
+pub(super) fn collect_leaves(value: &Value, key: String, visit: &mut dyn FnMut(String, &Value)) {
+    match &value.kind {
+        ValueKind::Table(table) => {
+            for (name, child) in table {
+                let child_key = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", key, name)
+                };
+                collect_leaves(child, child_key, visit);
+            }
+        }
+        _ if !key.is_empty() => visit(key, value),
+        _ => {}
+    }
+}
+
+fn render(value: &Value) -> String {
+    match &value.kind {
+        ValueKind::String(text) => format!("{:?}", text),
+        ValueKind::Array(items) => {
+            let items: Vec<String> = items.iter().map(render).collect();
+            format!("[{}]", items.join(", "))
+        }
+        ValueKind::Table(table) => {
+            let fields: Vec<String> = table
+                .iter()
+                .map(|(name, child)| format!("{}: {}", name, render(child)))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        kind => kind.to_string(),
+    }
+}
+
+/// 找回提供该键的环境变量名，规则与 config crate 一致：去掉前缀后转小写，分隔符换成 `.`
+fn env_var_name(builder: &ConfigBuilder, key: &str) -> String {
+    let prefix = format!("{}_", builder.env_prefix.as_deref().unwrap_or_default());
+    let separator = builder.env_separator.to_lowercase();
+    std::env::vars()
+        .find(|(name, _)| {
+            let name = name.to_lowercase();
+            name.strip_prefix(&prefix.to_lowercase())
+                .is_some_and(|rest| rest.replace(&separator, ".") == key)
+        })
+        .map(|(name, _)| name)
+        .unwrap_or_else(|| {
+            format!("{}{}", prefix, key.replace('.', &builder.env_separator)).to_uppercase()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFormat;
+    use crate::testing::{EnvGuard, write_temp_config};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+        debug: bool,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn test_report_provenance() {
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, "name: from-file\nport: 8000\n");
+        let _env = EnvGuard::new().set("CLAMBER_REPORT_PORT", "9000");
+
+        let (config, report): (AppConfig, _) = ConfigBuilder::new()
+            .with_default("name", "default-name")
+            .unwrap()
+            .with_default("port", 3000)
+            .unwrap()
+            .with_default("debug", false)
+            .unwrap()
+            .add_file(&path, None)
+            .add_yaml_str("tags: [a, b]\n")
+            .with_env_prefix("CLAMBER_REPORT")
+            .build_with_report()
+            .unwrap();
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.tags, ["a", "b"]);
+        assert!(!config.debug);
+
+        let debug = report.lookup("debug").unwrap();
+        assert_eq!(debug.source, ValueSource::Default);
+        assert!(!debug.value.clone().into_bool().unwrap());
+
+        let name = report.lookup("name").unwrap();
+        assert_eq!(name.source, ValueSource::File(path.clone()));
+        assert_eq!(config.name, "from-file");
+
+        let port = report.lookup("port").unwrap();
+        assert_eq!(
+            port.source,
+            ValueSource::Env("CLAMBER_REPORT_PORT".to_string())
+        );
+        assert_eq!(port.value.clone().into_int().unwrap(), 9000);
+
+        assert_eq!(
+            report.lookup("tags").unwrap().source,
+            ValueSource::Inline(0)
+        );
+        assert!(report.lookup("missing").is_none());
+        assert_eq!(report.len(), 4);
+
+        let pretty = report.to_string_pretty();
+        assert!(pretty.contains("port  = 9000  (环境变量 CLAMBER_REPORT_PORT)"));
+        assert!(pretty.contains("tags  = [\"a\", \"b\"]  (内联配置 #0)"));
+        assert_eq!(pretty.lines().count(), 4);
+    }
+}
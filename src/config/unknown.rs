@@ -0,0 +1,231 @@
+//! 未使用键检查：反序列化时记录目标结构体忽略的键，用于发现 `databse:` 之类的拼写错误。
+use super::ConfigBuilder;
+use super::report::leaf_paths;
+use crate::error::{ClamberError, Result, ResultExt};
+use config::{Config, Map, Source, Value, ValueKind};
+use serde::Deserialize;
+
+/// 目标结构体未使用的键如何处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum UnknownKeys {
+    /// 不检查（serde 默认行为）
+    #[default]
+    Allow,
+    /// 通过 `tracing::warn!` 记录，构建照常成功
+    Warn,
+    /// 返回 [`ClamberError::ConfigValidationError`]
+    Deny,
+}
+
+/// 一次构建的未使用键检查
+pub(super) struct UnknownKeyCheck {
+    policy: UnknownKeys,
+    /// 带前缀的环境变量提供的键路径，不参与检查
+    env_keys: Vec<String>,
+}
+
+impl UnknownKeyCheck {
+    pub(super) fn new(builder: &ConfigBuilder) -> Result<Self> {
+        let mut env_keys = Vec::new();
+        if builder.unknown_keys != UnknownKeys::Allow && builder.env_prefix.is_some() {
+            let mut cache = Value::new(None, ValueKind::Table(Map::new()));
+            builder
+                .environment()
+                .collect_to(&mut cache)
+                .map_err(ClamberError::from)?;
+            env_keys = leaf_paths(&cache);
+        }
+        Ok(Self {
+            policy: builder.unknown_keys,
+            env_keys,
+        })
+    }
+
+    /// 反序列化为 `T`，并按策略处理未使用的键
+    pub(super) fn deserialize<T>(&self, config: Config) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if self.policy == UnknownKeys::Allow {
+            return super::deserialize(config);
+        }
+
+        let mut unknown = Vec::new();
+        let value = serde_ignored::deserialize(config, |path| unknown.push(key_path(&path)))
+            .map_err(|e| ClamberError::ConfigParseError {
+                details: e.to_string(),
+                source: Some(e.into()),
+            })
+            .with_context(|| format!("反序列化配置为 {} 时", std::any::type_name::<T>()))?;
+        unknown.retain(|key| !self.provided_by_env(key));
+        if unknown.is_empty() {
+            return Ok(value);
+        }
+
+        let keys = unknown.join(", ");
+        match self.policy {
+            UnknownKeys::Allow => {}
+            UnknownKeys::Warn => {
+                tracing::warn!(keys = %keys, "配置中存在未使用的键，可能是拼写错误");
+            }
+            UnknownKeys::Deny => {
+                return Err(ClamberError::ConfigValidationError {
+                    details: format!(
+                        "配置中存在 {} 未使用的键: {}",
+                        std::any::type_name::<T>(),
+                        keys
+                    ),
+                });
+            }
+        }
+        Ok(value)
+    }
+
+    /// 只检查未使用的键，不需要反序列化结果时使用
+    pub(super) fn check<T>(&self, config: Config) -> Result<()>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        if self.policy == UnknownKeys::Allow {
+            return Ok(());
+        }
+        self.deserialize::<T>(config).map(|_| ())
+    }
+
+    /// 键（或其下的键）来自环境变量
+    fn provided_by_env(&self, key: &str) -> bool {
+        self.env_keys.iter().any(|env_key| {
+            env_key
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+}
+
+/// `database.databse`、`servers.0.hots`，跳过 `Option` 与 newtype 层级
+fn key_path(path: &serde_ignored::Path) -> String {
+    use serde_ignored::Path;
+
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => join(key_path(parent), &index.to_string()),
+        Path::Map { parent, key } => join(key_path(parent), key),
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => key_path(parent),
+    }
+}
+
+fn join(parent: String, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ConfigBuilder;
+    use crate::error::ClamberError;
+    use crate::testing::EnvGuard;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct AppConfig {
+        name: String,
+        #[serde(default)]
+        database: DatabaseConfig,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct DatabaseConfig {
+        #[serde(default = "default_host")]
+        host: String,
+    }
+
+    impl Default for DatabaseConfig {
+        fn default() -> Self {
+            Self {
+                host: default_host(),
+            }
+        }
+    }
+
+    fn default_host() -> String {
+        "localhost".to_string()
+    }
+
+    const TYPO: &str = "name: app\ndatabase:\n  hots: db-1\n";
+
+    #[test]
+    fn test_deny_unknown_nested_key() {
+        // 默认忽略拼写错误的键，字段落回默认值
+        let config: AppConfig = ConfigBuilder::new().add_yaml_str(TYPO).build().unwrap();
+        assert_eq!(config.database.host, "localhost");
+
+        let err = ConfigBuilder::new()
+            .add_yaml_str(TYPO)
+            .deny_unknown_keys(true)
+            .build::<AppConfig>()
+            .unwrap_err();
+        match &err {
+            ClamberError::ConfigValidationError { details } => {
+                assert!(details.contains("database.hots"), "{}", details)
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+
+        let err = ConfigBuilder::new()
+            .add_yaml_str(TYPO)
+            .deny_unknown_keys(true)
+            .build_with_all_errors::<AppConfig>()
+            .unwrap_err();
+        assert!(err.to_string().contains("database.hots"), "{}", err);
+
+        let config: AppConfig = ConfigBuilder::new()
+            .add_yaml_str("name: app\ndatabase:\n  host: db-1\n")
+            .deny_unknown_keys(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.database.host, "db-1");
+    }
+
+    #[test]
+    fn test_unknown_env_keys_ignored() {
+        let _env = EnvGuard::new()
+            .set("CLAMBER_UNKNOWN_NAME", "from-env")
+            .set("CLAMBER_UNKNOWN_LOG_LEVEL", "debug");
+        let config: AppConfig = ConfigBuilder::new()
+            .add_yaml_str("name: app\n")
+            .with_env_prefix("CLAMBER_UNKNOWN")
+            .deny_unknown_keys(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "from-env");
+    }
+
+    #[cfg(feature = "tracing-logs")]
+    #[test]
+    fn test_warn_unknown_nested_key() {
+        use crate::tracing_logs::RingBufferWriter;
+
+        let ring = RingBufferWriter::new(10);
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(ring.clone())
+            .with_ansi(false)
+            .finish();
+        let config: AppConfig = tracing::subscriber::with_default(subscriber, || {
+            ConfigBuilder::new()
+                .add_yaml_str(TYPO)
+                .warn_unknown_keys(true)
+                .build()
+                .unwrap()
+        });
+        assert_eq!(config.database.host, "localhost");
+        let logs = ring.snapshot();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("WARN"), "{}", logs[0]);
+        assert!(logs[0].contains("database.hots"), "{}", logs[0]);
+    }
+}
@@ -0,0 +1,161 @@
+//! 按键路径读取配置：键路径使用点号访问嵌套表，如 `database.host`。
+use crate::error::{ClamberError, Result};
+use config::{Config, ConfigError};
+use serde::Deserialize;
+
+/// 合并后的配置视图，见 [`super::ConfigBuilder::build_view`]
+///
+/// 错误信息均包含出错的键路径：键不存在返回 [`ClamberError::ConfigValidationError`]，
+/// 类型不符返回 [`ClamberError::ConfigParseError`]。
+#[derive(Debug, Clone)]
+pub struct ConfigView {
+    config: Config,
+}
+
+impl ConfigView {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// 读取子结构并反序列化
+    pub fn get_section<T>(&self, key: &str) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.get(key)
+    }
+
+    pub fn get_string(&self, key: &str) -> Result<String> {
+        self.config.get_string(key).map_err(|e| map_error(key, e))
+    }
+
+    pub fn get_int(&self, key: &str) -> Result<i64> {
+        self.config.get_int(key).map_err(|e| map_error(key, e))
+    }
+
+    pub fn get_bool(&self, key: &str) -> Result<bool> {
+        self.config.get_bool(key).map_err(|e| map_error(key, e))
+    }
+
+    /// 读取数组并逐项反序列化
+    pub fn get_array<T>(&self, key: &str) -> Result<Vec<T>>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.get(key)
+    }
+
+    /// 键是否存在
+    pub fn contains(&self, key: &str) -> bool {
+        !matches!(
+            self.config.get::<config::Value>(key),
+            Err(ConfigError::NotFound(_))
+        )
+    }
+
+    /// 原始 Config 对象
+    pub fn into_inner(self) -> Config {
+        self.config
+    }
+
+    fn get<T>(&self, key: &str) -> Result<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.config.get::<T>(key).map_err(|e| map_error(key, e))
+    }
+}
+
+impl From<Config> for ConfigView {
+    fn from(config: Config) -> Self {
+        Self::new(config)
+    }
+}
+
+fn map_error(key: &str, err: ConfigError) -> ClamberError {
+    match err {
+        ConfigError::NotFound(_) => ClamberError::ConfigValidationError {
+            details: format!("缺少配置项 {}", key),
+        },
+        err => ClamberError::ConfigParseError {
+            details: format!("配置项 {} 解析失败: {}", key, err),
+            source: Some(err.into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Database {
+        host: String,
+        port: u16,
+    }
+
+    fn view() -> ConfigView {
+        ConfigBuilder::new()
+            .add_yaml_str(
+                r#"
+name: svc
+debug: true
+database:
+  host: db
+  port: 5432
+  replicas: ["r1", "r2"]
+"#,
+            )
+            .build_view()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_view_present_keys() {
+        let view = view();
+        assert_eq!(
+            view.get_section::<Database>("database").unwrap(),
+            Database {
+                host: "db".to_string(),
+                port: 5432,
+            }
+        );
+        assert_eq!(view.get_string("database.host").unwrap(), "db");
+        assert_eq!(view.get_int("database.port").unwrap(), 5432);
+        assert!(view.get_bool("debug").unwrap());
+        assert_eq!(
+            view.get_array::<String>("database.replicas").unwrap(),
+            ["r1", "r2"]
+        );
+        assert!(view.contains("database.host"));
+        assert!(!view.contains("cache"));
+
+        let database: Database = ConfigBuilder::new()
+            .add_yaml_str("database:\n  host: db\n  port: 5432\n")
+            .build_section("database")
+            .unwrap();
+        assert_eq!(database.port, 5432);
+    }
+
+    #[test]
+    fn test_view_errors_name_key() {
+        let view = view();
+        match view.get_section::<Database>("cache") {
+            Err(ClamberError::ConfigValidationError { details }) => {
+                assert!(details.contains("cache"))
+            }
+            other => panic!("expected missing key, got {:?}", other),
+        }
+        match view.get_int("database.host") {
+            Err(ClamberError::ConfigParseError { details, .. }) => {
+                assert!(details.contains("database.host"))
+            }
+            other => panic!("expected type mismatch, got {:?}", other),
+        }
+        assert!(matches!(
+            view.get_section::<Database>("name"),
+            Err(ClamberError::ConfigParseError { .. })
+        ));
+    }
+}
@@ -0,0 +1,200 @@
+//! 配置热加载：轮询配置文件的修改时间与大小，变化后重新合并并反序列化。
+use super::ConfigBuilder;
+use crate::error::{ClamberError, Result};
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::SystemTime;
+
+type ErrorCallback = Box<dyn Fn(&ClamberError) + Send + Sync>;
+
+/// 文件指纹：不存在的文件记为 `None`
+type Fingerprint = Vec<Option<(SystemTime, u64)>>;
+
+struct Shared<T> {
+    current: RwLock<Arc<T>>,
+    subscribers: Mutex<Vec<Sender<Arc<T>>>>,
+    on_error: Mutex<Option<ErrorCallback>>,
+}
+
+impl<T> Shared<T> {
+    fn publish(&self, value: Arc<T>) {
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::clone(&value);
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        // 顺带移除已 drop 的订阅者
+        subscribers.retain(|tx| tx.send(Arc::clone(&value)).is_ok());
+    }
+
+    fn report(&self, err: &ClamberError) {
+        if let Some(callback) = &*self.on_error.lock().unwrap_or_else(|e| e.into_inner()) {
+            callback(err);
+        }
+    }
+}
+
+/// 支持热加载的配置，见 [`ConfigBuilder::build_watched`]
+///
+/// 后台线程按 `watch_interval` 检查构建器中的全部配置文件（含 `.env`），变化后重新构建；
+/// 重新构建失败时保留上一次成功的值，错误交给 [`WatchedConfig::on_reload_error`] 注册的回调。
+/// drop 时停止后台线程。
+pub struct WatchedConfig<T> {
+    shared: Arc<Shared<T>>,
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<T> WatchedConfig<T>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+{
+    pub(super) fn spawn(builder: ConfigBuilder) -> Result<Self> {
+        let paths = builder.file_paths();
+        let interval = builder.watch_interval;
+        // 先取指纹再构建：构建期间的修改会在下一轮被发现
+        let mut last = fingerprint(&paths);
+        let initial: T = builder.clone().build()?;
+
+        let shared = Arc::new(Shared {
+            current: RwLock::new(Arc::new(initial)),
+            subscribers: Mutex::new(Vec::new()),
+            on_error: Mutex::new(None),
+        });
+        let (stop, stop_rx) = mpsc::channel::<()>();
+        let worker = Arc::clone(&shared);
+        let handle = std::thread::Builder::new()
+            .name("clamber-config-watch".to_string())
+            .spawn(move || {
+                // 发送端 drop 后 recv_timeout 返回 Disconnected，线程退出
+                while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                    let current = fingerprint(&paths);
+                    if current == last {
+                        continue;
+                    }
+                    last = current;
+                    match builder.clone().build::<T>() {
+                        Ok(value) => worker.publish(Arc::new(value)),
+                        Err(err) => worker.report(&err),
+                    }
+                }
+            })
+            .map_err(|e| ClamberError::ConfigLoadError {
+                details: format!("启动配置监听线程失败: {}", e),
+                source: Some(e.into()),
+            })?;
+
+        Ok(Self {
+            shared,
+            stop: Some(stop),
+            handle: Some(handle),
+        })
+    }
+}
+
+impl<T> WatchedConfig<T> {
+    /// 当前配置（最近一次成功构建的值）
+    pub fn get(&self) -> Arc<T> {
+        Arc::clone(
+            &self
+                .shared
+                .current
+                .read()
+                .unwrap_or_else(|e| e.into_inner()),
+        )
+    }
+
+    /// 订阅配置更新，每次重新构建成功后收到新值
+    pub fn subscribe(&self) -> Receiver<Arc<T>> {
+        let (tx, rx) = mpsc::channel();
+        self.shared
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(tx);
+        rx
+    }
+
+    /// 注册重新构建失败时的回调（如记录日志），覆盖之前注册的回调
+    pub fn on_reload_error<F>(&self, callback: F)
+    where
+        F: Fn(&ClamberError) + Send + Sync + 'static,
+    {
+        *self
+            .shared
+            .on_error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(Box::new(callback));
+    }
+}
+
+impl<T> Drop for WatchedConfig<T> {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn fingerprint(paths: &[PathBuf]) -> Fingerprint {
+    paths
+        .iter()
+        .map(|path| {
+            let meta = std::fs::metadata(path).ok()?;
+            Some((meta.modified().ok()?, meta.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{ConfigBuilder, ConfigFormat};
+    use crate::testing::write_temp_config;
+    use serde::Deserialize;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+    }
+
+    #[test]
+    fn test_watched_config_reloads() {
+        let (_dir, path) = write_temp_config(ConfigFormat::Yaml, "name: svc\nport: 3000\n");
+        let watched = ConfigBuilder::new()
+            .add_file(&path, None)
+            .with_watch_interval(Duration::from_millis(20))
+            .build_watched::<AppConfig>()
+            .unwrap();
+        assert_eq!(watched.get().port, 3000);
+
+        let updates = watched.subscribe();
+        let (err_tx, errors) = mpsc::channel();
+        watched.on_reload_error(move |err| {
+            let _ = err_tx.send(err.to_string());
+        });
+
+        std::fs::write(&path, "name: renamed\nport: 4000\n").unwrap();
+        let updated = updates.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            *updated,
+            AppConfig {
+                name: "renamed".to_string(),
+                port: 4000,
+            }
+        );
+        assert_eq!(watched.get().port, 4000);
+
+        // 写入过程中可能读到不完整的文件，先清空之前的错误
+        while errors.try_recv().is_ok() {}
+
+        // 解析失败：报告错误，保留上一次成功的值
+        std::fs::write(&path, "name: [broken\n").unwrap();
+        errors.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(watched.get().port, 4000);
+        assert!(updates.try_recv().is_err());
+    }
+}
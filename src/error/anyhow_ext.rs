@@ -0,0 +1,55 @@
+//! 与 anyhow 的互操作：从 `anyhow::Error` 中找回 ClamberError 并做常用判断。
+use super::ClamberError;
+
+impl ClamberError {
+    /// 从 `anyhow::Error` 的错误链中找到最外层的 ClamberError
+    pub fn from_anyhow(err: &anyhow::Error) -> Option<&ClamberError> {
+        err.chain().find_map(|e| e.downcast_ref::<ClamberError>())
+    }
+}
+
+/// 是否为 JWT 过期错误（会穿透 anyhow 与 ClamberError 附加的上下文）
+pub fn is_jwt_expired(err: &anyhow::Error) -> bool {
+    ClamberError::from_anyhow(err)
+        .is_some_and(|e| matches!(e.root_cause(), ClamberError::JwtExpiredError))
+}
+
+/// 是否为值得重试的 ClamberError，见 [`ClamberError::is_retryable`]
+pub fn is_retryable(err: &anyhow::Error) -> bool {
+    ClamberError::from_anyhow(err).is_some_and(ClamberError::is_retryable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ResultExt;
+
+    #[test]
+    fn test_anyhow_round_trip() {
+        let err: anyhow::Error = ClamberError::JwtExpiredError.into();
+        assert!(is_jwt_expired(&err));
+        assert!(!is_retryable(&err));
+        assert_eq!(ClamberError::from_anyhow(&err).unwrap().code(), "JWT-003");
+
+        // anyhow 与 ClamberError 两层上下文
+        let result: crate::Result<()> = Err(ClamberError::JwtExpiredError).context("校验登录态");
+        let result = anyhow::Context::context(result, "处理请求");
+        let err = result.unwrap_err();
+        assert!(is_jwt_expired(&err));
+        assert_eq!(err.to_string(), "处理请求");
+
+        let err = anyhow::anyhow!("plain");
+        assert!(ClamberError::from_anyhow(&err).is_none());
+        assert!(!is_jwt_expired(&err));
+    }
+
+    #[test]
+    fn test_boxed_error_downcast() {
+        let boxed: Box<dyn std::error::Error + Send + Sync> = ClamberError::JwtExpiredError.into();
+        let err = boxed.downcast::<ClamberError>().unwrap();
+        assert_eq!(err.code(), "JWT-003");
+
+        let err = anyhow::Error::from(*err);
+        assert!(is_jwt_expired(&err));
+    }
+}
@@ -0,0 +1,114 @@
+//! 错误收集器：累积多个错误后一次性返回，避免 fail-fast。
+use super::{ClamberError, Result};
+
+/// 收集多个错误，最终汇总为 [`ClamberError::Multiple`]
+///
+/// 嵌套的 `Multiple` 会被展开，保证结果只有一层。
+#[derive(Debug, Default)]
+pub struct ErrorCollector {
+    errors: Vec<ClamberError>,
+}
+
+impl ErrorCollector {
+    /// 创建空的收集器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一个错误
+    pub fn push(&mut self, err: ClamberError) {
+        match err {
+            ClamberError::Multiple(errors) => errors.into_iter().for_each(|e| self.push(e)),
+            other => self.errors.push(other),
+        }
+    }
+
+    /// 记录结果中的错误，成功时返回其值
+    pub fn check<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.push(err);
+                None
+            }
+        }
+    }
+
+    /// 已收集的错误数量
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// 是否尚未收集到错误
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// 汇总结果：无错误返回 `Ok(())`，只有一个时原样返回，多个时返回 `Multiple`
+    pub fn into_result(mut self) -> Result<()> {
+        match self.errors.len() {
+            0 => Ok(()),
+            1 => Err(self.errors.remove(0)),
+            _ => Err(ClamberError::Multiple(self.errors)),
+        }
+    }
+}
+
+impl Extend<ClamberError> for ErrorCollector {
+    fn extend<I: IntoIterator<Item = ClamberError>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|e| self.push(e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(key: &str) -> ClamberError {
+        ClamberError::ConfigValidationError {
+            details: format!("缺少 {}", key),
+        }
+    }
+
+    #[test]
+    fn test_collector_flattens_nested_multiple() {
+        let mut collector = ErrorCollector::new();
+        assert!(collector.check(Ok::<_, ClamberError>(1)).is_some());
+        collector.push(invalid("a"));
+        collector.push(ClamberError::Multiple(vec![
+            invalid("b"),
+            ClamberError::Multiple(vec![invalid("c")]),
+        ]));
+        assert_eq!(collector.len(), 3);
+
+        let err = collector.into_result().unwrap_err();
+        let ClamberError::Multiple(errors) = &err else {
+            panic!("expected Multiple, got {:?}", err);
+        };
+        assert!(
+            errors
+                .iter()
+                .all(|e| matches!(e, ClamberError::ConfigValidationError { .. }))
+        );
+        assert_eq!(
+            err.to_string(),
+            "发现多个错误 (3):\n  1. 配置验证失败: 缺少 a\n  2. 配置验证失败: 缺少 b\n  3. 配置验证失败: 缺少 c"
+        );
+        assert_eq!(err.http_status(), 500);
+        assert!(err.is_user_error());
+        assert_eq!(
+            err.to_error_response().details.unwrap()["codes"],
+            "CFG-004,CFG-004,CFG-004"
+        );
+    }
+
+    #[test]
+    fn test_collector_single_and_empty() {
+        assert!(ErrorCollector::new().into_result().is_ok());
+
+        let mut collector = ErrorCollector::new();
+        collector.extend([invalid("a")]);
+        let err = collector.into_result().unwrap_err();
+        assert!(matches!(err, ClamberError::ConfigValidationError { .. }));
+    }
+}
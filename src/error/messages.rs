@@ -0,0 +1,98 @@
+//! 错误文案表：按变体维护中英文两套文案，运行时可切换输出语言。
+use super::ClamberError;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 错误信息的输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    /// 中文（默认）
+    #[default]
+    Zh,
+    /// 英文
+    En,
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(Language::Zh as u8);
+
+impl Language {
+    /// 当前全局使用的语言
+    pub fn current() -> Self {
+        match CURRENT.load(Ordering::Relaxed) {
+            x if x == Language::En as u8 => Language::En,
+            _ => Language::Zh,
+        }
+    }
+
+    pub(crate) fn set_current(language: Language) {
+        CURRENT.store(language as u8, Ordering::Relaxed);
+    }
+}
+
+/// 变体的文案标签
+///
+/// Display 输出为 `标签: 值`（JwtExpiredError / JwtRevokedError 只有标签；Multiple 为 `标签 (数量):` 加编号列表；
+/// Contextual 使用调用方提供的上下文，不经过该表）。
+pub(crate) fn label(err: &ClamberError, language: Language) -> &'static str {
+    let (zh, en) = match err {
+        ClamberError::LoggingError { .. } => ("日志系统错误", "Logging error"),
+        ClamberError::DirectoryCreationError { .. } => {
+            ("创建目录失败", "Failed to create directory")
+        }
+        ClamberError::JwtError { .. } => ("JWT错误", "JWT error"),
+        ClamberError::JwtKeyError { .. } => ("JWT密钥无效", "Invalid JWT key"),
+        ClamberError::JwtSignError { .. } => ("JWT签名失败", "Failed to sign JWT"),
+        ClamberError::JwtVerifyError { .. } => ("JWT验证失败", "Failed to verify JWT"),
+        ClamberError::JwtExpiredError => ("JWT已过期", "JWT has expired"),
+        ClamberError::JwtMissingFieldError { .. } => {
+            ("JWT缺少必要字段", "JWT is missing a required field")
+        }
+        ClamberError::JwtIssuerMismatchError { .. } => ("JWT签发者不匹配", "JWT issuer mismatch"),
+        ClamberError::JwtAudienceMismatchError { .. } => ("JWT受众不匹配", "JWT audience mismatch"),
+        ClamberError::JwtNotYetValidError { .. } => ("JWT尚未生效", "JWT is not yet valid"),
+        ClamberError::JwtTokenTypeMismatchError { .. } => {
+            ("JWT类型不匹配", "JWT token type mismatch")
+        }
+        ClamberError::JwtAlgorithmMismatchError { .. } => {
+            ("JWT签名算法不匹配", "JWT algorithm mismatch")
+        }
+        ClamberError::JwtRevokedError => ("JWT已被吊销", "JWT has been revoked"),
+        ClamberError::JwtTooOldError { .. } => ("JWT签发过早，需要重新认证", "JWT is too old"),
+        ClamberError::SnowflakeInitError { .. } => {
+            ("Snowflake初始化错误", "Failed to initialize Snowflake")
+        }
+        ClamberError::SnowflakeGenerateError { .. } => {
+            ("Snowflake生成ID失败", "Failed to generate Snowflake ID")
+        }
+        ClamberError::SnowflakeConfigError { .. } => {
+            ("Snowflake配置无效", "Invalid Snowflake configuration")
+        }
+        ClamberError::SnowflakeClockMovedBackwards { .. } => {
+            ("系统时钟回拨", "System clock moved backwards")
+        }
+        ClamberError::SnowflakeInvalidIdError { .. } => {
+            ("Snowflake ID无效", "Invalid Snowflake ID")
+        }
+        ClamberError::ConfigLoadError { .. } => ("配置加载错误", "Failed to load configuration"),
+        ClamberError::ConfigFileNotFoundError { .. } => {
+            ("配置文件不存在", "Configuration file not found")
+        }
+        ClamberError::ConfigParseError { .. } => ("配置解析失败", "Failed to parse configuration"),
+        ClamberError::ConfigValidationError { .. } => {
+            ("配置验证失败", "Configuration validation failed")
+        }
+        ClamberError::EnvVarParseError { .. } => {
+            ("环境变量解析错误", "Failed to parse environment variable")
+        }
+        ClamberError::SerializationError { .. } => ("序列化错误", "Serialization error"),
+        ClamberError::DeserializationError { .. } => ("反序列化错误", "Deserialization error"),
+        ClamberError::IoError { .. } => ("IO错误", "IO error"),
+        ClamberError::Other { .. } => ("未知错误", "Unknown error"),
+        ClamberError::Contextual { .. } => ("", ""),
+        ClamberError::Multiple(_) => ("发现多个错误", "Multiple errors occurred"),
+    };
+
+    match language {
+        Language::Zh => zh,
+        Language::En => en,
+    }
+}
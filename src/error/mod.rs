@@ -0,0 +1,1503 @@
+//! 错误类型模块：对库内各子模块的错误进行统一建模，向外暴露 ClamberError 与 Result。
+//! 每个错误变体都有稳定的错误码（如 `CFG-001`、`JWT-003`）与所属分类。
+//! 错误文案维护在 `messages` 文案表中，支持中英文切换。
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use thiserror::Error;
+
+#[cfg(feature = "anyhow")]
+mod anyhow_ext;
+mod collector;
+mod messages;
+
+#[cfg(feature = "anyhow")]
+pub use anyhow_ext::{is_jwt_expired, is_retryable};
+pub use collector::ErrorCollector;
+pub use messages::Language;
+
+/// 底层错误源的统一装箱类型
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// 错误分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// 配置管理相关
+    Config,
+    /// JWT 相关
+    Jwt,
+    /// Snowflake 相关
+    Snowflake,
+    /// 日志系统相关
+    Logging,
+    /// IO 相关
+    Io,
+    /// 序列化与反序列化
+    Serialization,
+    /// 其他
+    Other,
+}
+
+impl ErrorCategory {
+    /// 分类名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::Config => "config",
+            ErrorCategory::Jwt => "jwt",
+            ErrorCategory::Snowflake => "snowflake",
+            ErrorCategory::Logging => "logging",
+            ErrorCategory::Io => "io",
+            ErrorCategory::Serialization => "serialization",
+            ErrorCategory::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&ClamberError> for ErrorCategory {
+    fn from(err: &ClamberError) -> Self {
+        err.category()
+    }
+}
+
+/// Clamber Core 库的统一错误类型
+///
+/// 标记为 `#[non_exhaustive]`，后续新增变体不算破坏性变更；匹配时需保留 `_` 分支，
+/// 或改用 [`ClamberError::code`] / [`ClamberError::category`]。
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum ClamberError {
+    /// 日志相关错误
+    LoggingError { message: String },
+
+    /// 目录创建错误
+    DirectoryCreationError {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// JWT相关错误
+    JwtError { message: String },
+
+    /// JWT密钥错误
+    JwtKeyError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// JWT签名错误
+    JwtSignError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// JWT验证错误
+    JwtVerifyError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// JWT过期错误
+    JwtExpiredError,
+
+    /// JWT缺少必要字段错误
+    JwtMissingFieldError { field: String },
+
+    /// JWT签发者（iss）与配置不一致
+    JwtIssuerMismatchError { expected: String, actual: String },
+
+    /// JWT受众（aud）与配置不一致
+    JwtAudienceMismatchError { expected: String, actual: String },
+
+    /// JWT尚未生效（nbf 晚于当前时间）
+    JwtNotYetValidError { not_before: i64 },
+
+    /// JWT类型（token_type）与期望不一致，如用 refresh token 访问接口
+    JwtTokenTypeMismatchError { expected: String, actual: String },
+
+    /// JWT签名算法与配置不一致（防止算法混淆）
+    JwtAlgorithmMismatchError { expected: String, actual: String },
+
+    /// JWT已被吊销
+    JwtRevokedError,
+
+    /// JWT签发时间早于 `max_token_age` 允许的范围，需要重新认证
+    JwtTooOldError { created_at: i64, max_age_secs: i64 },
+
+    /// Snowflake相关错误
+    SnowflakeInitError { details: String },
+
+    /// Snowflake生成ID错误
+    SnowflakeGenerateError { details: String },
+
+    /// Snowflake配置错误
+    SnowflakeConfigError { details: String },
+
+    /// 系统时钟回拨，`drift_ms` 为回拨的毫秒数
+    SnowflakeClockMovedBackwards { drift_ms: u64 },
+
+    /// 外部传入的 Snowflake ID 未通过校验，`reason` 说明具体原因
+    SnowflakeInvalidIdError { id: u64, reason: String },
+
+    /// 配置管理相关错误
+    ConfigLoadError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// 配置文件不存在错误
+    ConfigFileNotFoundError {
+        path: String,
+        /// 底层的 IO 错误（由配置键缺失转换而来时为 None）
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// 配置解析错误
+    ConfigParseError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// 配置验证错误
+    ConfigValidationError { details: String },
+
+    /// 环境变量解析错误
+    EnvVarParseError { details: String },
+
+    /// 序列化错误
+    SerializationError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// 反序列化错误
+    DeserializationError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// IO错误
+    IoError {
+        details: String,
+        #[source]
+        source: Option<BoxError>,
+    },
+
+    /// 其他错误
+    Other { message: String },
+
+    /// 附加了上下文的错误，输出为 `context: inner`
+    ///
+    /// 启用 `backtrace` feature 时额外携带捕获到的调用栈，建议通过 [`ResultExt`] 构造。
+    Contextual {
+        context: String,
+        #[source]
+        source: Box<ClamberError>,
+        #[cfg(feature = "backtrace")]
+        backtrace: Option<std::backtrace::Backtrace>,
+    },
+
+    /// 一次报告的多个错误，输出时按条目编号列出；建议通过 [`ErrorCollector`] 构造
+    Multiple(Vec<ClamberError>),
+}
+
+impl fmt::Display for ClamberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_message(Language::current(), f)
+    }
+}
+
+impl ClamberError {
+    /// 设置全局错误文案语言，影响此后所有 Display 输出
+    pub fn set_language(language: Language) {
+        Language::set_current(language);
+    }
+
+    /// 按指定语言渲染错误信息，不受全局语言影响
+    pub fn message(&self, language: Language) -> String {
+        struct Message<'a>(&'a ClamberError, Language);
+
+        impl fmt::Display for Message<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.write_message(self.1, f)
+            }
+        }
+
+        Message(self, language).to_string()
+    }
+
+    fn write_message(&self, language: Language, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = messages::label(self, language);
+        match self {
+            ClamberError::JwtExpiredError | ClamberError::JwtRevokedError => f.write_str(label),
+            ClamberError::DirectoryCreationError { path, source } => {
+                write!(f, "{}: {} ({}): {}", label, path, source.kind(), source)
+            }
+            ClamberError::Contextual {
+                context, source, ..
+            } => {
+                write!(f, "{}: ", context)?;
+                source.write_message(language, f)
+            }
+            ClamberError::Multiple(errors) => {
+                write!(f, "{} ({}):", label, errors.len())?;
+                for (index, err) in errors.iter().enumerate() {
+                    write!(f, "\n  {}. ", index + 1)?;
+                    err.write_message(language, f)?;
+                }
+                Ok(())
+            }
+            ClamberError::LoggingError { message }
+            | ClamberError::JwtError { message }
+            | ClamberError::Other { message } => write!(f, "{}: {}", label, message),
+            ClamberError::JwtMissingFieldError { field } => write!(f, "{}: {}", label, field),
+            ClamberError::JwtIssuerMismatchError { expected, actual }
+            | ClamberError::JwtAudienceMismatchError { expected, actual }
+            | ClamberError::JwtTokenTypeMismatchError { expected, actual }
+            | ClamberError::JwtAlgorithmMismatchError { expected, actual } => match language {
+                Language::Zh => write!(f, "{}: {}（期望 {}）", label, actual, expected),
+                Language::En => write!(f, "{}: {} (expected {})", label, actual, expected),
+            },
+            ClamberError::JwtNotYetValidError { not_before } => {
+                write!(f, "{}: nbf={}", label, not_before)
+            }
+            ClamberError::JwtTooOldError {
+                created_at,
+                max_age_secs,
+            } => write!(
+                f,
+                "{}: created_at={}, max_age={}s",
+                label, created_at, max_age_secs
+            ),
+            ClamberError::ConfigFileNotFoundError { path, .. } => write!(f, "{}: {}", label, path),
+            ClamberError::SnowflakeClockMovedBackwards { drift_ms } => {
+                write!(f, "{}: {}ms", label, drift_ms)
+            }
+            ClamberError::SnowflakeInvalidIdError { id, reason } => {
+                write!(f, "{}: {} (id={})", label, reason, id)
+            }
+            ClamberError::JwtKeyError { details, .. }
+            | ClamberError::JwtSignError { details, .. }
+            | ClamberError::JwtVerifyError { details, .. }
+            | ClamberError::ConfigLoadError { details, .. }
+            | ClamberError::ConfigParseError { details, .. }
+            | ClamberError::SerializationError { details, .. }
+            | ClamberError::DeserializationError { details, .. }
+            | ClamberError::IoError { details, .. }
+            | ClamberError::SnowflakeInitError { details }
+            | ClamberError::SnowflakeGenerateError { details }
+            | ClamberError::SnowflakeConfigError { details }
+            | ClamberError::ConfigValidationError { details }
+            | ClamberError::EnvVarParseError { details } => write!(f, "{}: {}", label, details),
+        }
+    }
+
+    /// 稳定错误码，每个变体唯一，可用于 API 网关等场景
+    pub fn code(&self) -> &'static str {
+        match self {
+            ClamberError::LoggingError { .. } => "LOG-001",
+            ClamberError::DirectoryCreationError { .. } => "LOG-002",
+            ClamberError::JwtError { .. } => "JWT-001",
+            ClamberError::JwtVerifyError { .. } => "JWT-002",
+            ClamberError::JwtExpiredError => "JWT-003",
+            ClamberError::JwtKeyError { .. } => "JWT-004",
+            ClamberError::JwtSignError { .. } => "JWT-005",
+            ClamberError::JwtMissingFieldError { .. } => "JWT-006",
+            ClamberError::JwtIssuerMismatchError { .. } => "JWT-007",
+            ClamberError::JwtAudienceMismatchError { .. } => "JWT-008",
+            ClamberError::JwtNotYetValidError { .. } => "JWT-009",
+            ClamberError::JwtTokenTypeMismatchError { .. } => "JWT-010",
+            ClamberError::JwtAlgorithmMismatchError { .. } => "JWT-011",
+            ClamberError::JwtRevokedError => "JWT-012",
+            ClamberError::JwtTooOldError { .. } => "JWT-013",
+            ClamberError::SnowflakeInitError { .. } => "SNF-001",
+            ClamberError::SnowflakeGenerateError { .. } => "SNF-002",
+            ClamberError::SnowflakeConfigError { .. } => "SNF-003",
+            ClamberError::SnowflakeClockMovedBackwards { .. } => "SNF-004",
+            ClamberError::SnowflakeInvalidIdError { .. } => "SNF-005",
+            ClamberError::ConfigLoadError { .. } => "CFG-001",
+            ClamberError::ConfigFileNotFoundError { .. } => "CFG-002",
+            ClamberError::ConfigParseError { .. } => "CFG-003",
+            ClamberError::ConfigValidationError { .. } => "CFG-004",
+            ClamberError::EnvVarParseError { .. } => "CFG-005",
+            ClamberError::SerializationError { .. } => "SER-001",
+            ClamberError::DeserializationError { .. } => "SER-002",
+            ClamberError::IoError { .. } => "IO-001",
+            ClamberError::Other { .. } => "OTH-001",
+            ClamberError::Contextual { source, .. } => source.code(),
+            ClamberError::Multiple(_) => "OTH-002",
+        }
+    }
+
+    /// 错误所属分类
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ClamberError::LoggingError { .. } | ClamberError::DirectoryCreationError { .. } => {
+                ErrorCategory::Logging
+            }
+            ClamberError::JwtError { .. }
+            | ClamberError::JwtVerifyError { .. }
+            | ClamberError::JwtExpiredError
+            | ClamberError::JwtKeyError { .. }
+            | ClamberError::JwtSignError { .. }
+            | ClamberError::JwtMissingFieldError { .. }
+            | ClamberError::JwtIssuerMismatchError { .. }
+            | ClamberError::JwtAudienceMismatchError { .. }
+            | ClamberError::JwtNotYetValidError { .. }
+            | ClamberError::JwtTokenTypeMismatchError { .. }
+            | ClamberError::JwtAlgorithmMismatchError { .. }
+            | ClamberError::JwtRevokedError
+            | ClamberError::JwtTooOldError { .. } => ErrorCategory::Jwt,
+            ClamberError::SnowflakeInitError { .. }
+            | ClamberError::SnowflakeGenerateError { .. }
+            | ClamberError::SnowflakeConfigError { .. }
+            | ClamberError::SnowflakeClockMovedBackwards { .. }
+            | ClamberError::SnowflakeInvalidIdError { .. } => ErrorCategory::Snowflake,
+            ClamberError::ConfigLoadError { .. }
+            | ClamberError::ConfigFileNotFoundError { .. }
+            | ClamberError::ConfigParseError { .. }
+            | ClamberError::ConfigValidationError { .. }
+            | ClamberError::EnvVarParseError { .. } => ErrorCategory::Config,
+            ClamberError::IoError { .. } => ErrorCategory::Io,
+            ClamberError::SerializationError { .. } | ClamberError::DeserializationError { .. } => {
+                ErrorCategory::Serialization
+            }
+            ClamberError::Other { .. } => ErrorCategory::Other,
+            ClamberError::Contextual { source, .. } => source.category(),
+            ClamberError::Multiple(_) => ErrorCategory::Other,
+        }
+    }
+
+    /// 带错误码的展示形式，如 `[JWT-003] JWT已过期`
+    pub fn display_with_code(&self) -> String {
+        format!("[{}] {}", self.code(), self)
+    }
+
+    /// 对应的 HTTP 状态码
+    ///
+    /// | 变体 | 状态码 |
+    /// |------|--------|
+    /// | JwtVerifyError / JwtExpiredError / JwtMissingFieldError / JwtError | 401 |
+    /// | JwtIssuerMismatchError / JwtAudienceMismatchError / JwtNotYetValidError | 401 |
+    /// | JwtTokenTypeMismatchError / JwtAlgorithmMismatchError / JwtRevokedError / JwtTooOldError | 401 |
+    /// | DeserializationError / SnowflakeInvalidIdError | 400 |
+    /// | JwtKeyError / JwtSignError | 500 |
+    /// | Config* / EnvVarParseError（含 ConfigFileNotFoundError） | 500 |
+    /// | Snowflake* / Logging* / DirectoryCreationError | 500 |
+    /// | SerializationError / IoError / Other | 500 |
+    /// | Contextual | 同内部错误 |
+    /// | Multiple | 子错误中最大的状态码（为空时 500） |
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ClamberError::JwtVerifyError { .. }
+            | ClamberError::JwtExpiredError
+            | ClamberError::JwtMissingFieldError { .. }
+            | ClamberError::JwtIssuerMismatchError { .. }
+            | ClamberError::JwtAudienceMismatchError { .. }
+            | ClamberError::JwtNotYetValidError { .. }
+            | ClamberError::JwtTokenTypeMismatchError { .. }
+            | ClamberError::JwtAlgorithmMismatchError { .. }
+            | ClamberError::JwtRevokedError
+            | ClamberError::JwtTooOldError { .. }
+            | ClamberError::JwtError { .. } => 401,
+            ClamberError::DeserializationError { .. }
+            | ClamberError::SnowflakeInvalidIdError { .. } => 400,
+            ClamberError::JwtKeyError { .. } | ClamberError::JwtSignError { .. } => 500,
+            ClamberError::ConfigLoadError { .. }
+            | ClamberError::ConfigFileNotFoundError { .. }
+            | ClamberError::ConfigParseError { .. }
+            | ClamberError::ConfigValidationError { .. }
+            | ClamberError::EnvVarParseError { .. } => 500,
+            ClamberError::SnowflakeInitError { .. }
+            | ClamberError::SnowflakeGenerateError { .. }
+            | ClamberError::SnowflakeConfigError { .. }
+            | ClamberError::SnowflakeClockMovedBackwards { .. } => 500,
+            ClamberError::LoggingError { .. } | ClamberError::DirectoryCreationError { .. } => 500,
+            ClamberError::SerializationError { .. }
+            | ClamberError::IoError { .. }
+            | ClamberError::Other { .. } => 500,
+            ClamberError::Contextual { source, .. } => source.http_status(),
+            ClamberError::Multiple(errors) => errors
+                .iter()
+                .map(ClamberError::http_status)
+                .max()
+                .unwrap_or(500),
+        }
+    }
+
+    /// 是否值得重试
+    ///
+    /// | 变体 | 可重试 | 依据 |
+    /// |------|--------|------|
+    /// | IoError | 是 | 磁盘/网络等临时故障 |
+    /// | ConfigLoadError | 是 | 配置源读取失败（含远程拉取超时） |
+    /// | SnowflakeGenerateError / SnowflakeClockMovedBackwards | 是 | 时钟回拨等短暂状态，稍后可恢复 |
+    /// | LoggingError / DirectoryCreationError | 否 | 权限或路径问题，重试无效 |
+    /// | Jwt* | 否 | 令牌或密钥本身有问题，结果确定 |
+    /// | SnowflakeInitError / SnowflakeConfigError / SnowflakeInvalidIdError | 否 | 参数非法 |
+    /// | ConfigFileNotFoundError / ConfigParseError / ConfigValidationError / EnvVarParseError | 否 | 配置内容问题 |
+    /// | SerializationError / DeserializationError / Other | 否 | 数据问题，结果确定 |
+    /// | Contextual | 同内部错误 | 上下文不改变错误性质 |
+    /// | Multiple | 子错误均可重试时 | 任一子错误结果确定则整体确定 |
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClamberError::IoError { .. }
+            | ClamberError::ConfigLoadError { .. }
+            | ClamberError::SnowflakeGenerateError { .. }
+            | ClamberError::SnowflakeClockMovedBackwards { .. } => true,
+            ClamberError::LoggingError { .. }
+            | ClamberError::DirectoryCreationError { .. }
+            | ClamberError::JwtError { .. }
+            | ClamberError::JwtKeyError { .. }
+            | ClamberError::JwtSignError { .. }
+            | ClamberError::JwtVerifyError { .. }
+            | ClamberError::JwtExpiredError
+            | ClamberError::JwtMissingFieldError { .. }
+            | ClamberError::JwtIssuerMismatchError { .. }
+            | ClamberError::JwtAudienceMismatchError { .. }
+            | ClamberError::JwtNotYetValidError { .. }
+            | ClamberError::JwtTokenTypeMismatchError { .. }
+            | ClamberError::JwtAlgorithmMismatchError { .. }
+            | ClamberError::JwtRevokedError
+            | ClamberError::JwtTooOldError { .. }
+            | ClamberError::SnowflakeInitError { .. }
+            | ClamberError::SnowflakeConfigError { .. }
+            | ClamberError::SnowflakeInvalidIdError { .. }
+            | ClamberError::ConfigFileNotFoundError { .. }
+            | ClamberError::ConfigParseError { .. }
+            | ClamberError::ConfigValidationError { .. }
+            | ClamberError::EnvVarParseError { .. }
+            | ClamberError::SerializationError { .. }
+            | ClamberError::DeserializationError { .. }
+            | ClamberError::Other { .. } => false,
+            ClamberError::Contextual { source, .. } => source.is_retryable(),
+            ClamberError::Multiple(errors) => {
+                !errors.is_empty() && errors.iter().all(ClamberError::is_retryable)
+            }
+        }
+    }
+
+    /// 是否由用户输入或使用方配置导致（否则视为系统内部错误）
+    ///
+    /// | 变体 | 用户错误 | 依据 |
+    /// |------|----------|------|
+    /// | JwtError / JwtVerifyError / JwtExpiredError / JwtMissingFieldError | 是 | 调用方提交的令牌无效 |
+    /// | JwtIssuerMismatchError / JwtAudienceMismatchError / JwtNotYetValidError | 是 | 令牌声明不满足校验要求 |
+    /// | JwtTokenTypeMismatchError / JwtAlgorithmMismatchError | 是 | 令牌类型或签名算法不符 |
+    /// | JwtRevokedError / JwtTooOldError | 是 | 调用方提交的令牌已被吊销或签发过早 |
+    /// | DeserializationError | 是 | 调用方提交的数据无法解析 |
+    /// | Config* / EnvVarParseError | 是 | 使用方提供的配置文件或环境变量有误 |
+    /// | SnowflakeConfigError / SnowflakeInvalidIdError | 是 | 使用方提供的 Snowflake 参数或 ID 非法 |
+    /// | JwtKeyError / JwtSignError | 否 | 服务端密钥或签名流程问题 |
+    /// | SnowflakeInitError / SnowflakeGenerateError / SnowflakeClockMovedBackwards | 否 | 生成器内部状态问题 |
+    /// | LoggingError / DirectoryCreationError / IoError | 否 | 运行环境问题 |
+    /// | SerializationError / Other | 否 | 内部错误 |
+    /// | Contextual | 同内部错误 | 上下文不改变错误性质 |
+    /// | Multiple | 子错误均为用户错误时 | 含任一内部错误即视为内部错误 |
+    pub fn is_user_error(&self) -> bool {
+        match self {
+            ClamberError::JwtError { .. }
+            | ClamberError::JwtVerifyError { .. }
+            | ClamberError::JwtExpiredError
+            | ClamberError::JwtMissingFieldError { .. }
+            | ClamberError::JwtIssuerMismatchError { .. }
+            | ClamberError::JwtAudienceMismatchError { .. }
+            | ClamberError::JwtNotYetValidError { .. }
+            | ClamberError::JwtTokenTypeMismatchError { .. }
+            | ClamberError::JwtAlgorithmMismatchError { .. }
+            | ClamberError::JwtRevokedError
+            | ClamberError::JwtTooOldError { .. }
+            | ClamberError::DeserializationError { .. }
+            | ClamberError::ConfigLoadError { .. }
+            | ClamberError::ConfigFileNotFoundError { .. }
+            | ClamberError::ConfigParseError { .. }
+            | ClamberError::ConfigValidationError { .. }
+            | ClamberError::EnvVarParseError { .. }
+            | ClamberError::SnowflakeConfigError { .. }
+            | ClamberError::SnowflakeInvalidIdError { .. } => true,
+            ClamberError::JwtKeyError { .. }
+            | ClamberError::JwtSignError { .. }
+            | ClamberError::SnowflakeInitError { .. }
+            | ClamberError::SnowflakeGenerateError { .. }
+            | ClamberError::SnowflakeClockMovedBackwards { .. }
+            | ClamberError::LoggingError { .. }
+            | ClamberError::DirectoryCreationError { .. }
+            | ClamberError::IoError { .. }
+            | ClamberError::SerializationError { .. }
+            | ClamberError::Other { .. } => false,
+            ClamberError::Contextual { source, .. } => source.is_user_error(),
+            ClamberError::Multiple(errors) => {
+                !errors.is_empty() && errors.iter().all(ClamberError::is_user_error)
+            }
+        }
+    }
+
+    /// 为错误附加上下文；启用 `backtrace` feature 时在最内层上下文处捕获调用栈
+    fn contextual(context: String, source: ClamberError) -> Self {
+        #[cfg(feature = "backtrace")]
+        let backtrace = match source.backtrace() {
+            Some(_) => None,
+            None => capture_backtrace(),
+        };
+
+        ClamberError::Contextual {
+            context,
+            source: Box::new(source),
+            #[cfg(feature = "backtrace")]
+            backtrace,
+        }
+    }
+
+    /// 错误产生时捕获的调用栈，仅在 `RUST_BACKTRACE` 开启时存在
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        match self {
+            ClamberError::Contextual {
+                source, backtrace, ..
+            } => backtrace.as_ref().or_else(|| source.backtrace()),
+            _ => None,
+        }
+    }
+
+    /// 便于排障的完整报告：带错误码的信息、source 链以及（若有）调用栈
+    pub fn debug_report(&self) -> String {
+        use std::error::Error;
+        use std::fmt::Write;
+
+        let mut report = self.display_with_code();
+        let mut source = self.source();
+        if source.is_some() {
+            report.push_str("\n\nCaused by:");
+        }
+        let mut index = 0;
+        while let Some(err) = source {
+            let _ = write!(report, "\n    {}: {}", index, err);
+            index += 1;
+            source = err.source();
+        }
+
+        #[cfg(feature = "backtrace")]
+        if let Some(backtrace) = self.backtrace() {
+            let _ = write!(report, "\n\nBacktrace:\n{}", backtrace);
+        }
+
+        report
+    }
+
+    /// 剥离所有上下文后的原始错误，便于按变体匹配
+    pub fn root_cause(&self) -> &ClamberError {
+        match self {
+            ClamberError::Contextual { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+
+    /// 转换为可直接返回给前端的错误体，使用默认脱敏规则 [`redact_sensitive`]
+    pub fn to_error_response(&self) -> ErrorResponse {
+        self.to_error_response_with(redact_sensitive)
+    }
+
+    /// 转换为错误体，并使用自定义脱敏钩子处理 message 与 details 中的每个值
+    pub fn to_error_response_with<F>(&self, redact: F) -> ErrorResponse
+    where
+        F: Fn(&str) -> String,
+    {
+        let details: BTreeMap<String, String> = self
+            .fields()
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), redact(&value)))
+            .collect();
+
+        ErrorResponse {
+            code: self.code().to_string(),
+            message: redact(&self.to_string()),
+            details: if details.is_empty() {
+                None
+            } else {
+                Some(details)
+            },
+        }
+    }
+
+    /// RFC 7807（Problem Details）格式的错误体，使用默认脱敏规则
+    ///
+    /// `type` 为 `urn:clamber:error:{错误码}`，`title` 为变体标签，`detail` 为完整错误信息；
+    /// 扩展字段 `code`、`category` 与 `details`（变体字段，有值时输出）。
+    pub fn to_problem_json(&self) -> serde_json::Value {
+        let response = self.to_error_response();
+        let title = messages::label(self.root_cause(), Language::current());
+        let mut problem = serde_json::json!({
+            "type": format!("urn:clamber:error:{}", response.code),
+            "title": title,
+            "status": self.http_status(),
+            "detail": response.message,
+            "code": response.code,
+            "category": self.category().as_str(),
+        });
+        if let Some(details) = response.details {
+            problem["details"] = serde_json::json!(details);
+        }
+        problem
+    }
+
+    /// 变体携带的字段（字段名, 值）
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        match self {
+            ClamberError::LoggingError { message }
+            | ClamberError::JwtError { message }
+            | ClamberError::Other { message } => vec![("message", message.clone())],
+            ClamberError::DirectoryCreationError { path, source } => {
+                vec![("path", path.clone()), ("kind", source.kind().to_string())]
+            }
+            ClamberError::ConfigFileNotFoundError { path, .. } => vec![("path", path.clone())],
+            ClamberError::JwtMissingFieldError { field } => vec![("field", field.clone())],
+            ClamberError::JwtIssuerMismatchError { expected, actual }
+            | ClamberError::JwtAudienceMismatchError { expected, actual }
+            | ClamberError::JwtTokenTypeMismatchError { expected, actual }
+            | ClamberError::JwtAlgorithmMismatchError { expected, actual } => {
+                vec![("expected", expected.clone()), ("actual", actual.clone())]
+            }
+            ClamberError::JwtNotYetValidError { not_before } => {
+                vec![("not_before", not_before.to_string())]
+            }
+            ClamberError::JwtTooOldError {
+                created_at,
+                max_age_secs,
+            } => vec![
+                ("created_at", created_at.to_string()),
+                ("max_age_secs", max_age_secs.to_string()),
+            ],
+            ClamberError::SnowflakeClockMovedBackwards { drift_ms } => {
+                vec![("drift_ms", drift_ms.to_string())]
+            }
+            ClamberError::SnowflakeInvalidIdError { id, reason } => {
+                vec![("id", id.to_string()), ("reason", reason.clone())]
+            }
+            ClamberError::JwtExpiredError | ClamberError::JwtRevokedError => Vec::new(),
+            ClamberError::JwtKeyError { details, .. }
+            | ClamberError::JwtSignError { details, .. }
+            | ClamberError::JwtVerifyError { details, .. }
+            | ClamberError::ConfigLoadError { details, .. }
+            | ClamberError::ConfigParseError { details, .. }
+            | ClamberError::SerializationError { details, .. }
+            | ClamberError::DeserializationError { details, .. }
+            | ClamberError::IoError { details, .. }
+            | ClamberError::SnowflakeInitError { details }
+            | ClamberError::SnowflakeGenerateError { details }
+            | ClamberError::SnowflakeConfigError { details }
+            | ClamberError::ConfigValidationError { details }
+            | ClamberError::EnvVarParseError { details } => vec![("details", details.clone())],
+            ClamberError::Contextual {
+                context, source, ..
+            } => {
+                let mut fields = source.fields();
+                fields.push(("context", context.clone()));
+                fields
+            }
+            ClamberError::Multiple(errors) => vec![
+                ("count", errors.len().to_string()),
+                (
+                    "codes",
+                    errors
+                        .iter()
+                        .map(ClamberError::code)
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ),
+            ],
+        }
+    }
+}
+
+/// Result 扩展：在错误传播时附加上下文
+///
+/// ```
+/// use clamber_core::{ClamberError, Result, ResultExt};
+///
+/// fn load(tenant: u32) -> Result<()> {
+///     Err(ClamberError::ConfigValidationError { details: "port 为空".to_string() })
+///         .with_context(|| format!("加载租户 {} 的配置时", tenant))
+/// }
+///
+/// assert_eq!(load(42).unwrap_err().to_string(), "加载租户 42 的配置时: 配置验证失败: port 为空");
+/// ```
+pub trait ResultExt<T> {
+    /// 附加固定的上下文信息
+    fn context(self, msg: impl Into<String>) -> Result<T>;
+
+    /// 仅在出错时计算上下文信息
+    fn with_context(self, f: impl FnOnce() -> String) -> Result<T>;
+}
+
+impl<T, E> ResultExt<T> for std::result::Result<T, E>
+where
+    E: Into<ClamberError>,
+{
+    fn context(self, msg: impl Into<String>) -> Result<T> {
+        self.map_err(|e| ClamberError::contextual(msg.into(), e.into()))
+    }
+
+    fn with_context(self, f: impl FnOnce() -> String) -> Result<T> {
+        self.map_err(|e| ClamberError::contextual(f(), e.into()))
+    }
+}
+
+/// 按当前 `RUST_BACKTRACE` 决定是否捕获调用栈
+///
+/// 不使用 `Backtrace::capture`，因为它会缓存首次读取到的环境变量。
+#[cfg(feature = "backtrace")]
+fn capture_backtrace() -> Option<std::backtrace::Backtrace> {
+    match std::env::var("RUST_BACKTRACE") {
+        Ok(value) if value != "0" => Some(std::backtrace::Backtrace::force_capture()),
+        _ => None,
+    }
+}
+
+/// 面向 API 调用方的错误体
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorResponse {
+    /// 稳定错误码
+    pub code: String,
+    /// 错误信息
+    pub message: String,
+    /// 错误详情（变体字段）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<BTreeMap<String, String>>,
+}
+
+impl Serialize for ClamberError {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_error_response().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for ClamberError {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        (status, axum::Json(self.to_error_response())).into_response()
+    }
+}
+
+/// 默认脱敏规则：隐藏路径中的用户名（`/home/<user>`、`/Users/<user>`、`C:\Users\<user>`）
+pub fn redact_sensitive(text: &str) -> String {
+    const MARKERS: [&str; 3] = ["/home/", "/Users/", "\\Users\\"];
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((pos, marker)) = MARKERS
+        .iter()
+        .filter_map(|m| rest.find(m).map(|pos| (pos, *m)))
+        .min_by_key(|(pos, _)| *pos)
+    {
+        let after = pos + marker.len();
+        result.push_str(&rest[..after]);
+        let user_len = rest[after..]
+            .find(|c: char| c == '/' || c == '\\' || c.is_whitespace() || c == '"')
+            .unwrap_or(rest.len() - after);
+        if user_len > 0 {
+            result.push_str("***");
+        }
+        rest = &rest[after + user_len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+impl From<std::io::Error> for ClamberError {
+    fn from(err: std::io::Error) -> Self {
+        ClamberError::IoError {
+            details: err.to_string(),
+            source: Some(err.into()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ClamberError {
+    fn from(err: serde_json::Error) -> Self {
+        ClamberError::SerializationError {
+            details: err.to_string(),
+            source: Some(err.into()),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<config::ConfigError> for ClamberError {
+    fn from(err: config::ConfigError) -> Self {
+        match err {
+            config::ConfigError::NotFound(key) => ClamberError::ConfigFileNotFoundError {
+                path: key,
+                source: None,
+            },
+            // 必需的配置文件缺失：config crate 只给出 `configuration file "<path>" not found`
+            config::ConfigError::Foreign(cause)
+                if cause
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound) =>
+            {
+                let message = cause.to_string();
+                let path = message
+                    .split_once('"')
+                    .and_then(|(_, rest)| rest.rsplit_once('"'))
+                    .map(|(path, _)| path.to_string())
+                    .unwrap_or(message);
+                ClamberError::ConfigFileNotFoundError {
+                    path,
+                    source: Some(cause),
+                }
+            }
+            _ => ClamberError::ConfigLoadError {
+                details: err.to_string(),
+                source: Some(err.into()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "token")]
+impl From<jwt::Error> for ClamberError {
+    /// 签名阶段的错误请显式映射为 [`ClamberError::JwtSignError`]
+    fn from(err: jwt::Error) -> Self {
+        match err {
+            // (token header 中的算法, 密钥对应的算法)
+            jwt::Error::AlgorithmMismatch(actual, expected) => {
+                ClamberError::JwtAlgorithmMismatchError {
+                    expected: format!("{:?}", expected).to_uppercase(),
+                    actual: format!("{:?}", actual).to_uppercase(),
+                }
+            }
+            err => ClamberError::JwtVerifyError {
+                details: err.to_string(),
+                source: Some(err.into()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "token")]
+impl From<hmac::digest::InvalidLength> for ClamberError {
+    fn from(err: hmac::digest::InvalidLength) -> Self {
+        ClamberError::JwtKeyError {
+            details: err.to_string(),
+            source: Some(err.into()),
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for ClamberError {
+    /// 用于 JWT 中时间戳字段（如 `exp`）的解析
+    fn from(err: std::num::ParseIntError) -> Self {
+        ClamberError::JwtError {
+            message: format!("无效的时间戳格式: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::ParseError> for ClamberError {
+    fn from(err: chrono::ParseError) -> Self {
+        ClamberError::DeserializationError {
+            details: err.to_string(),
+            source: Some(err.into()),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<toml::de::Error> for ClamberError {
+    fn from(err: toml::de::Error) -> Self {
+        ClamberError::ConfigParseError {
+            details: err.to_string(),
+            source: Some(err.into()),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<serde_yaml::Error> for ClamberError {
+    fn from(err: serde_yaml::Error) -> Self {
+        ClamberError::ConfigParseError {
+            details: err.to_string(),
+            source: Some(err.into()),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClamberError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    /// 每个变体的示例值（Contextual 为包装变体，单独测试）；新增变体时需同步补充
+    pub(crate) fn all_variants() -> Vec<ClamberError> {
+        let text = || "x".to_string();
+        vec![
+            ClamberError::LoggingError { message: text() },
+            ClamberError::DirectoryCreationError {
+                path: text(),
+                source: std::io::Error::other("x"),
+            },
+            ClamberError::JwtError { message: text() },
+            ClamberError::JwtKeyError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::JwtSignError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::JwtVerifyError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::JwtExpiredError,
+            ClamberError::JwtMissingFieldError { field: text() },
+            ClamberError::JwtIssuerMismatchError {
+                expected: text(),
+                actual: text(),
+            },
+            ClamberError::JwtAudienceMismatchError {
+                expected: text(),
+                actual: text(),
+            },
+            ClamberError::JwtNotYetValidError { not_before: 0 },
+            ClamberError::JwtTokenTypeMismatchError {
+                expected: text(),
+                actual: text(),
+            },
+            ClamberError::JwtAlgorithmMismatchError {
+                expected: text(),
+                actual: text(),
+            },
+            ClamberError::JwtRevokedError,
+            ClamberError::JwtTooOldError {
+                created_at: 0,
+                max_age_secs: 3600,
+            },
+            ClamberError::SnowflakeInitError { details: text() },
+            ClamberError::SnowflakeGenerateError { details: text() },
+            ClamberError::SnowflakeConfigError { details: text() },
+            ClamberError::SnowflakeClockMovedBackwards { drift_ms: 5 },
+            ClamberError::SnowflakeInvalidIdError {
+                id: 42,
+                reason: text(),
+            },
+            ClamberError::ConfigLoadError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::ConfigFileNotFoundError {
+                path: text(),
+                source: None,
+            },
+            ClamberError::ConfigParseError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::ConfigValidationError { details: text() },
+            ClamberError::EnvVarParseError { details: text() },
+            ClamberError::SerializationError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::DeserializationError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::IoError {
+                details: text(),
+                source: None,
+            },
+            ClamberError::Other { message: text() },
+            ClamberError::Multiple(vec![ClamberError::Other { message: text() }]),
+        ]
+    }
+
+    #[test]
+    fn test_error_codes_are_unique() {
+        let variants = all_variants();
+        let codes: HashSet<&str> = variants.iter().map(|e| e.code()).collect();
+        assert_eq!(codes.len(), variants.len());
+    }
+
+    #[test]
+    fn test_code_prefix_matches_category() {
+        for err in all_variants() {
+            let prefix = err.code().split('-').next().unwrap();
+            let expected = match err.category() {
+                ErrorCategory::Config => "CFG",
+                ErrorCategory::Jwt => "JWT",
+                ErrorCategory::Snowflake => "SNF",
+                ErrorCategory::Logging => "LOG",
+                ErrorCategory::Io => "IO",
+                ErrorCategory::Serialization => "SER",
+                ErrorCategory::Other => "OTH",
+            };
+            assert_eq!(prefix, expected, "{}", err.code());
+        }
+    }
+
+    #[test]
+    fn test_from_conversions_keep_source() {
+        use std::error::Error;
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing file");
+        let err: ClamberError = io.into();
+        assert_eq!(err.to_string(), "IO错误: missing file");
+        let source = err.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<std::io::Error>().unwrap().kind(),
+            std::io::ErrorKind::NotFound
+        );
+
+        let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        let err: ClamberError = json.into();
+        assert!(matches!(err, ClamberError::SerializationError { .. }));
+        assert!(err.source().unwrap().is::<serde_json::Error>());
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_config_conversions_keep_source() {
+        use std::error::Error;
+
+        let toml = toml::from_str::<toml::Value>("a = ").unwrap_err();
+        let err: ClamberError = toml.into();
+        assert!(err.source().unwrap().is::<toml::de::Error>());
+
+        let config = config::ConfigError::Message("bad".to_string());
+        let err: ClamberError = config.into();
+        assert_eq!(err.to_string(), "配置加载错误: bad");
+        assert!(err.source().unwrap().is::<config::ConfigError>());
+
+        let missing = config::Config::builder()
+            .add_source(config::File::with_name("/nonexistent/app.yaml"))
+            .build()
+            .unwrap_err();
+        let missing = ClamberError::from(missing);
+        let io = missing
+            .source()
+            .and_then(|source| source.downcast_ref::<std::io::Error>())
+            .unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        match missing {
+            ClamberError::ConfigFileNotFoundError { path, .. } => {
+                assert_eq!(path, "/nonexistent/app.yaml")
+            }
+            other => panic!("expected file not found, got {:?}", other),
+        }
+        let property = config::ConfigError::NotFound("database.host".to_string());
+        match ClamberError::from(property) {
+            ClamberError::ConfigFileNotFoundError { path, source } => {
+                assert_eq!(path, "database.host");
+                assert!(source.is_none());
+            }
+            other => panic!("expected not found, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "token")]
+    #[test]
+    fn test_token_related_conversions() {
+        use std::error::Error;
+
+        let key = hmac::digest::InvalidLength;
+        let err: ClamberError = key.into();
+        assert_eq!(err.code(), "JWT-004");
+        assert!(err.source().unwrap().is::<hmac::digest::InvalidLength>());
+
+        let jwt = jwt::Error::InvalidSignature;
+        let err: ClamberError = jwt.into();
+        assert!(matches!(err, ClamberError::JwtVerifyError { .. }));
+        assert!(err.source().unwrap().is::<jwt::Error>());
+
+        let int = "abc".parse::<i64>().unwrap_err();
+        let err: ClamberError = int.into();
+        assert!(matches!(err, ClamberError::JwtError { .. }));
+
+        let time = chrono::NaiveDate::parse_from_str("2024-13-01", "%Y-%m-%d").unwrap_err();
+        let err: ClamberError = time.into();
+        assert!(matches!(err, ClamberError::DeserializationError { .. }));
+    }
+
+    #[test]
+    fn test_redact_sensitive() {
+        assert_eq!(
+            redact_sensitive("无法读取 /home/alice/app/config.yaml"),
+            "无法读取 /home/***/app/config.yaml"
+        );
+        assert_eq!(
+            redact_sensitive("/Users/bob/a and C:\\Users\\carol\\b"),
+            "/Users/***/a and C:\\Users\\***\\b"
+        );
+        assert_eq!(redact_sensitive("no paths here"), "no paths here");
+    }
+
+    #[test]
+    fn test_error_response_snapshots() {
+        let cases = vec![
+            (
+                ClamberError::JwtExpiredError,
+                r#"{"code":"JWT-003","message":"JWT已过期"}"#,
+            ),
+            (
+                ClamberError::ConfigFileNotFoundError {
+                    path: "/home/alice/app.yaml".to_string(),
+                    source: None,
+                },
+                r#"{"code":"CFG-002","message":"配置文件不存在: /home/***/app.yaml","details":{"path":"/home/***/app.yaml"}}"#,
+            ),
+            (
+                ClamberError::SnowflakeConfigError {
+                    details: "bad worker".to_string(),
+                },
+                r#"{"code":"SNF-003","message":"Snowflake配置无效: bad worker","details":{"details":"bad worker"}}"#,
+            ),
+            (
+                ClamberError::LoggingError {
+                    message: "oops".to_string(),
+                },
+                r#"{"code":"LOG-001","message":"日志系统错误: oops","details":{"message":"oops"}}"#,
+            ),
+            (
+                ClamberError::IoError {
+                    details: "disk full".to_string(),
+                    source: None,
+                },
+                r#"{"code":"IO-001","message":"IO错误: disk full","details":{"details":"disk full"}}"#,
+            ),
+            (
+                ClamberError::Other {
+                    message: "x".to_string(),
+                },
+                r#"{"code":"OTH-001","message":"未知错误: x","details":{"message":"x"}}"#,
+            ),
+        ];
+
+        for (err, expected) in cases {
+            assert_eq!(serde_json::to_string(&err).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_error_response_custom_redactor() {
+        let err = ClamberError::JwtKeyError {
+            details: "secret=abc".to_string(),
+            source: None,
+        };
+        let response = err.to_error_response_with(|v| v.replace("abc", "***"));
+        assert_eq!(response.message, "JWT密钥无效: secret=***");
+        assert_eq!(response.details.unwrap()["details"], "secret=***");
+
+        // 每个变体都能序列化
+        for err in all_variants() {
+            let value = serde_json::to_value(&err).unwrap();
+            assert_eq!(value["code"], err.code());
+        }
+    }
+
+    /// 每个变体期望的错误码与分类；新增变体时这里的穷尽匹配无法编译，提醒补充映射与 `all_variants`
+    fn expected_mapping(err: &ClamberError) -> (&'static str, ErrorCategory) {
+        use ErrorCategory::*;
+        match err {
+            ClamberError::LoggingError { .. } => ("LOG-001", Logging),
+            ClamberError::DirectoryCreationError { .. } => ("LOG-002", Logging),
+            ClamberError::JwtError { .. } => ("JWT-001", Jwt),
+            ClamberError::JwtVerifyError { .. } => ("JWT-002", Jwt),
+            ClamberError::JwtExpiredError => ("JWT-003", Jwt),
+            ClamberError::JwtKeyError { .. } => ("JWT-004", Jwt),
+            ClamberError::JwtSignError { .. } => ("JWT-005", Jwt),
+            ClamberError::JwtMissingFieldError { .. } => ("JWT-006", Jwt),
+            ClamberError::JwtIssuerMismatchError { .. } => ("JWT-007", Jwt),
+            ClamberError::JwtAudienceMismatchError { .. } => ("JWT-008", Jwt),
+            ClamberError::JwtNotYetValidError { .. } => ("JWT-009", Jwt),
+            ClamberError::JwtTokenTypeMismatchError { .. } => ("JWT-010", Jwt),
+            ClamberError::JwtAlgorithmMismatchError { .. } => ("JWT-011", Jwt),
+            ClamberError::JwtRevokedError => ("JWT-012", Jwt),
+            ClamberError::JwtTooOldError { .. } => ("JWT-013", Jwt),
+            ClamberError::SnowflakeInitError { .. } => ("SNF-001", Snowflake),
+            ClamberError::SnowflakeGenerateError { .. } => ("SNF-002", Snowflake),
+            ClamberError::SnowflakeConfigError { .. } => ("SNF-003", Snowflake),
+            ClamberError::SnowflakeClockMovedBackwards { .. } => ("SNF-004", Snowflake),
+            ClamberError::SnowflakeInvalidIdError { .. } => ("SNF-005", Snowflake),
+            ClamberError::ConfigLoadError { .. } => ("CFG-001", Config),
+            ClamberError::ConfigFileNotFoundError { .. } => ("CFG-002", Config),
+            ClamberError::ConfigParseError { .. } => ("CFG-003", Config),
+            ClamberError::ConfigValidationError { .. } => ("CFG-004", Config),
+            ClamberError::EnvVarParseError { .. } => ("CFG-005", Config),
+            ClamberError::SerializationError { .. } => ("SER-001", Serialization),
+            ClamberError::DeserializationError { .. } => ("SER-002", Serialization),
+            ClamberError::IoError { .. } => ("IO-001", Io),
+            ClamberError::Other { .. } => ("OTH-001", Other),
+            ClamberError::Multiple(_) => ("OTH-002", Other),
+            ClamberError::Contextual { source, .. } => expected_mapping(source),
+        }
+    }
+
+    #[test]
+    fn test_every_variant_code_and_category() {
+        let variants = all_variants();
+        // 除 Contextual 外的变体数，与 expected_mapping 的分支一致
+        assert_eq!(variants.len(), 30);
+        for err in &variants {
+            assert_eq!(
+                (err.code(), err.category()),
+                expected_mapping(err),
+                "{:?}",
+                err
+            );
+            assert_eq!(ErrorCategory::from(err), err.category());
+        }
+
+        let wrapped =
+            ClamberError::contextual("校验登录态".to_string(), ClamberError::JwtExpiredError);
+        assert_eq!(
+            (wrapped.code(), wrapped.category()),
+            ("JWT-003", ErrorCategory::Jwt)
+        );
+        assert!(ClamberError::SnowflakeClockMovedBackwards { drift_ms: 3 }.is_retryable());
+    }
+
+    #[test]
+    fn test_problem_json() {
+        let problem = ClamberError::ConfigFileNotFoundError {
+            path: "/home/alice/app.yaml".to_string(),
+            source: None,
+        }
+        .to_problem_json();
+        assert_eq!(
+            problem,
+            serde_json::json!({
+                "type": "urn:clamber:error:CFG-002",
+                "title": "配置文件不存在",
+                "status": 500,
+                "detail": "配置文件不存在: /home/***/app.yaml",
+                "code": "CFG-002",
+                "category": "config",
+                "details": {"path": "/home/***/app.yaml"}
+            })
+        );
+
+        let problem =
+            ClamberError::contextual("校验登录态".to_string(), ClamberError::JwtExpiredError)
+                .to_problem_json();
+        assert_eq!(problem["title"], "JWT已过期");
+        assert_eq!(problem["status"], 401);
+        assert_eq!(problem["code"], "JWT-003");
+        assert!(
+            ClamberError::JwtExpiredError
+                .to_problem_json()
+                .get("details")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_http_status_mapping() {
+        for err in all_variants() {
+            let status = err.http_status();
+            assert!((400..600).contains(&status), "{}", err.code());
+            if err.category() == ErrorCategory::Config {
+                assert_eq!(status, 500);
+            }
+        }
+        assert_eq!(ClamberError::JwtExpiredError.http_status(), 401);
+        let verify = ClamberError::JwtVerifyError {
+            details: "bad signature".to_string(),
+            source: None,
+        };
+        assert_eq!(verify.http_status(), 401);
+    }
+
+    #[test]
+    fn test_retryable_and_user_error_table() {
+        // (错误码, 可重试, 用户错误)；新增变体时必须在此补充映射
+        let expected: HashMap<&str, (bool, bool)> = [
+            ("LOG-001", (false, false)),
+            ("LOG-002", (false, false)),
+            ("JWT-001", (false, true)),
+            ("JWT-002", (false, true)),
+            ("JWT-003", (false, true)),
+            ("JWT-004", (false, false)),
+            ("JWT-005", (false, false)),
+            ("JWT-006", (false, true)),
+            ("JWT-007", (false, true)),
+            ("JWT-008", (false, true)),
+            ("JWT-009", (false, true)),
+            ("JWT-010", (false, true)),
+            ("JWT-011", (false, true)),
+            ("JWT-012", (false, true)),
+            ("JWT-013", (false, true)),
+            ("SNF-001", (false, false)),
+            ("SNF-002", (true, false)),
+            ("SNF-003", (false, true)),
+            ("SNF-004", (true, false)),
+            ("SNF-005", (false, true)),
+            ("CFG-001", (true, true)),
+            ("CFG-002", (false, true)),
+            ("CFG-003", (false, true)),
+            ("CFG-004", (false, true)),
+            ("CFG-005", (false, true)),
+            ("SER-001", (false, false)),
+            ("SER-002", (false, true)),
+            ("IO-001", (true, false)),
+            ("OTH-001", (false, false)),
+            ("OTH-002", (false, false)),
+        ]
+        .into_iter()
+        .collect();
+
+        let variants = all_variants();
+        assert_eq!(expected.len(), variants.len());
+        for err in variants {
+            let (retryable, user) = expected
+                .get(err.code())
+                .unwrap_or_else(|| panic!("{} 缺少重试/用户错误映射", err.code()));
+            assert_eq!(err.is_retryable(), *retryable, "{}", err.code());
+            assert_eq!(err.is_user_error(), *user, "{}", err.code());
+        }
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn test_into_response() {
+        use axum::response::IntoResponse;
+
+        let response = ClamberError::JwtExpiredError.into_response();
+        assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_context_chain() {
+        use std::error::Error;
+
+        let result: Result<()> = Err(std::io::Error::other("disk full"))
+            .context("写入快照")
+            .with_context(|| format!("同步租户 {} 时", 42));
+        let err = result.unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "同步租户 42 时: 写入快照: IO错误: disk full"
+        );
+        assert_eq!(err.code(), "IO-001");
+        assert_eq!(err.category(), ErrorCategory::Io);
+        assert!(err.is_retryable());
+        assert!(matches!(err.root_cause(), ClamberError::IoError { .. }));
+
+        let inner = err.source().unwrap();
+        assert_eq!(inner.to_string(), "写入快照: IO错误: disk full");
+        let io = inner.source().unwrap().source().unwrap();
+        assert!(io.is::<std::io::Error>());
+
+        let response = err.to_error_response();
+        assert_eq!(response.details.unwrap()["context"], "同步租户 42 时");
+    }
+
+    #[test]
+    fn test_debug_report_lists_source_chain() {
+        let err: ClamberError = Err::<(), _>(std::io::Error::other("disk full"))
+            .context("写入快照")
+            .unwrap_err();
+        let report = err.debug_report();
+        assert!(report.starts_with("[IO-001] 写入快照: IO错误: disk full"));
+        assert!(report.contains("Caused by:\n    0: IO错误: disk full\n    1: disk full"));
+    }
+
+    #[cfg(feature = "backtrace")]
+    #[test]
+    fn test_backtrace_follows_env() {
+        use crate::testing::EnvGuard;
+
+        // 环境变量为进程级状态，两种情况放在同一测试中串行执行
+        let wrap = || {
+            Err::<(), _>(ClamberError::ConfigParseError {
+                details: "bad".to_string(),
+                source: None,
+            })
+            .context("加载配置")
+            .context("启动服务")
+            .unwrap_err()
+        };
+
+        {
+            let _env = EnvGuard::new().set("RUST_BACKTRACE", "0");
+            let err = wrap();
+            assert!(err.backtrace().is_none());
+            assert!(!err.debug_report().contains("Backtrace:"));
+        }
+
+        {
+            let _env = EnvGuard::new().set("RUST_BACKTRACE", "1");
+            let err = wrap();
+            assert!(err.backtrace().is_some());
+            assert!(err.debug_report().contains("Backtrace:"));
+            assert!(format!("{:#?}", err).contains("backtrace"));
+        }
+
+        let _env = EnvGuard::new().remove("RUST_BACKTRACE");
+        assert!(wrap().backtrace().is_none());
+    }
+
+    #[test]
+    fn test_messages_in_both_languages() {
+        for err in all_variants() {
+            let zh = err.message(Language::Zh);
+            let en = err.message(Language::En);
+            assert!(!zh.is_empty() && !en.is_empty(), "{}", err.code());
+            assert!(en.is_ascii(), "{}: {}", err.code(), en);
+            if !matches!(
+                err,
+                ClamberError::JwtExpiredError
+                    | ClamberError::JwtRevokedError
+                    | ClamberError::JwtNotYetValidError { .. }
+                    | ClamberError::JwtTooOldError { .. }
+                    | ClamberError::SnowflakeClockMovedBackwards { .. }
+            ) {
+                assert!(zh.contains("x"), "{}: {}", err.code(), zh);
+                assert!(en.contains(": x"), "{}: {}", err.code(), en);
+            }
+        }
+
+        let err = Err::<(), _>(ClamberError::ConfigFileNotFoundError {
+            path: "app.yaml".to_string(),
+            source: None,
+        })
+        .context("boot")
+        .unwrap_err();
+        assert_eq!(err.message(Language::Zh), "boot: 配置文件不存在: app.yaml");
+        assert_eq!(
+            err.message(Language::En),
+            "boot: Configuration file not found: app.yaml"
+        );
+    }
+
+    #[test]
+    fn test_display_with_code() {
+        let err = ClamberError::JwtExpiredError;
+        assert_eq!(err.to_string(), "JWT已过期");
+        assert_eq!(err.display_with_code(), "[JWT-003] JWT已过期");
+    }
+}
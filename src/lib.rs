@@ -5,52 +5,99 @@
 //! - 分布式唯一 ID（Snowflake）
 //! - 基于 tracing 的结构化日志初始化
 //! - 统一错误处理（thiserror）
+//! - 应用引导（AppContext：从单一配置初始化日志、JWT 与 Snowflake）
 //!
 //! 快速开始示例：
 //!
 //! ```no_run
+//! # #[cfg(feature = "token")] mod example {
 //! use serde::{Serialize, Deserialize};
-//! use clamber_core::token::{JwtConfig, generate_token, verify_token};
+//! use clamber_core::token::{JwtConfig, generate_token_default, set_default_config, verify_token};
 //!
 //! #[derive(Serialize, Deserialize, Debug, PartialEq)]
 //! struct User { id: String, username: String, role: String }
 //!
+//! # pub
 //! fn main() -> clamber_core::Result<()> {
 //!     let user = User { id: "1".into(), username: "alice".into(), role: "admin".into() };
-//!     let token = generate_token(&user, JwtConfig::default())?;
+//!     set_default_config(JwtConfig::new("replace-with-a-real-secret", 7))?;
+//!     let token = generate_token_default(&user)?;
 //!     let decoded: User = verify_token(&token)?;
 //!     assert_eq!(user, decoded);
 //!     Ok(())
 //! }
+//! # }
+//! # #[cfg(feature = "token")] fn main() { example::main().unwrap() }
+//! # #[cfg(not(feature = "token"))] fn main() {}
 //! ```
 //!
+//! 各模块可通过 cargo feature 按需引入（`config`、`token`、`snowflake`、`tracing-logs`、`bootstrap`），
+//! 默认全部启用；错误类型模块始终可用。`async` feature 提供 JWT 与批量 ID 生成的异步版本。测试工具位于 `testing` feature 下的 [`testing`] 模块。
+//!
 //! 更多示例请查看项目 README 以及 examples 目录。
 
+#[cfg(feature = "bootstrap")]
+pub mod bootstrap;
+#[cfg(feature = "config")]
 pub mod config;
 pub mod error;
+#[cfg(all(feature = "async", any(feature = "token", feature = "snowflake")))]
+mod offload;
+#[cfg(feature = "snowflake")]
 pub mod snowflake;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "token")]
 pub mod token;
+#[cfg(feature = "tracing-logs")]
 pub mod tracing_logs;
 
-pub use error::{ClamberError, Result};
-pub use tracing_logs::{LogConfig, logger_start_with_config};
+#[cfg(feature = "bootstrap")]
+pub use bootstrap::AppContext;
+pub use error::{
+    ClamberError, ErrorCategory, ErrorCollector, ErrorResponse, Language, Result, ResultExt,
+};
+#[cfg(feature = "tracing-logs")]
+pub use tracing_logs::fields::{RequestSpan, current_request_id};
+#[cfg(all(feature = "tracing-logs", feature = "config"))]
+pub use tracing_logs::logger_start_from_config_file;
+#[cfg(feature = "tracing-logs")]
+pub use tracing_logs::{
+    ConsoleTarget, FileRoute, InfoFileMode, JsonOptions, LogConfig, LogFormat, LogOutput,
+    LogTimezone, LoggerHandle, RingBufferWriter, Rotation, logger_start_once,
+    logger_start_with_config, logger_start_with_layers, logger_start_with_writer,
+};
 
 /// re-export: token 模块的主要类型与函数
-pub use token::{JwtConfig, JwtManager, generate_token, is_valid_token, verify_token};
+#[cfg(feature = "token")]
+pub use token::{
+    JwtAlgorithm, JwtConfig, JwtManager, MemoryRevocationStore, TokenClaims, TokenPair,
+    TokenRevocationStore, TokenStatus, TokenType, decode_claims, generate_token,
+    generate_token_default, is_valid_token, set_default_config, verify_token,
+};
 
 /// re-export: snowflake 模块的主要类型
-pub use snowflake::{SnowflakeConfig, SnowflakeIdInfo, SnowflakeManager};
+#[cfg(feature = "snowflake")]
+pub use snowflake::{
+    ClockDriftPolicy, IdBlock, SnowflakeConfig, SnowflakeId, SnowflakeIdInfo, SnowflakeLayout,
+    SnowflakeManager, SnowflakeMetrics, SnowflakePool, WorkerIdRegistry, WorkerIdStrategy,
+};
 
 /// re-export: config 模块的主要类型与函数
+#[cfg(feature = "config")]
 pub use config::{
-    ConfigBuilder, ConfigFormat, ConfigManager, auto_load_config, get_config_paths, load_config,
-    load_config_with_env,
+    ConfigBuilder, ConfigFormat, ConfigManager, ConfigPathOptions, ConfigReport, ConfigView,
+    Validate, WatchedConfig, auto_load_config, auto_load_config_with_options,
+    auto_load_config_with_profile, config_to_string, get_config_paths, get_config_paths_extended,
+    load_config, load_config_with_env, save_config,
 };
 
 /// snowflake 便利函数（使用前缀避免命名冲突）
+#[cfg(feature = "snowflake")]
 pub mod snowflake_utils {
-    //! 便利函数：Snowflake ID 相关的快捷 API。
     pub use crate::snowflake::{
-        generate_id, generate_ids, generate_string_id, parse_id, parse_string_id,
+        generate_id, generate_ids, generate_short_id, generate_string_id, id_range_for,
+        init_default, init_default_from_env, parse_id, parse_short_id, parse_string_id,
+        validate_id,
     };
 }
@@ -0,0 +1,148 @@
+//! 在后台线程池中执行耗时的同步操作，返回可在任意异步运行时（tokio、async-std 等）中 await 的 future。
+//!
+//! 不依赖具体运行时：首次使用时启动固定数量的工作线程（CPU 核数，至少 2 个），之后的任务排队复用，
+//! 线程数不随调用量增长。池被占满时新任务在队列中等待，只用于超过内联阈值的大任务。
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// 工作线程数的下限，单核环境中也能并行处理两个任务
+const MIN_WORKERS: usize = 2;
+
+static POOL: OnceLock<Mutex<Sender<Job>>> = OnceLock::new();
+
+/// 工作线程个数：CPU 核数，至少 [`MIN_WORKERS`]
+pub(crate) fn worker_count() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .max(MIN_WORKERS)
+}
+
+fn pool() -> &'static Mutex<Sender<Job>> {
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..worker_count() {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("clamber-offload-{}", index))
+                .spawn(move || work(&receiver))
+                .expect("无法启动后台工作线程");
+        }
+        Mutex::new(sender)
+    })
+}
+
+fn work(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // 只在取任务时持有锁，任务本身并行执行
+        let job = receiver
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .recv();
+        match job {
+            Ok(job) => job(),
+            Err(_) => return,
+        }
+    }
+}
+
+/// [`offload`] 返回的 future，完成时给出闭包的返回值；闭包 panic 时在 await 处重新抛出
+pub(crate) struct Offload<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+pub(crate) fn offload<T, F>(job: F) -> Offload<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(Shared {
+        result: None,
+        waker: None,
+    }));
+    let worker = shared.clone();
+    let job: Job = Box::new(move || {
+        // 捕获 panic，工作线程不会因任务失败而退出
+        let result = panic::catch_unwind(AssertUnwindSafe(job));
+        let mut shared = worker.lock().unwrap_or_else(PoisonError::into_inner);
+        shared.result = Some(result);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+    pool()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .send(job)
+        .expect("后台工作线程不会退出");
+    Offload { shared }
+}
+
+impl<T> Future for Offload<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        match shared.result.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_offload_returns_value_and_propagates_panic() {
+        let main = thread::current().id();
+        let worker = offload(|| thread::current().id()).await;
+        assert_ne!(worker, main);
+
+        let result = tokio::spawn(offload(|| panic!("job failed"))).await;
+        assert!(result.unwrap_err().is_panic());
+        // panic 之后工作线程仍可继续处理任务
+        assert_eq!(offload(|| 1 + 1).await, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_offload_reuses_bounded_workers() {
+        let handles: Vec<_> = (0..worker_count() * 8)
+            .map(|_| {
+                tokio::spawn(offload(|| {
+                    thread::sleep(Duration::from_millis(2));
+                    thread::current().name().map(str::to_string)
+                }))
+            })
+            .collect();
+        let mut names = HashSet::new();
+        for handle in handles {
+            names.insert(handle.await.unwrap());
+        }
+        assert!(names.len() <= worker_count(), "{:?}", names);
+        assert!(names.iter().all(|name| {
+            name.as_deref()
+                .is_some_and(|name| name.starts_with("clamber-offload-"))
+        }));
+    }
+}
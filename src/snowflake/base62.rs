@@ -0,0 +1,68 @@
+//! Base62 编码：字母表 `0-9A-Za-z` 按 ASCII 升序排列，定长补零后字符串顺序与数值顺序一致。
+use crate::error::{ClamberError, Result};
+
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// 编码后的固定长度：62^11 > 2^64，11 位足以容纳任意 u64
+pub const BASE62_LEN: usize = 11;
+
+/// 编码为 11 位定长字符串，不足时左侧补 `0`
+pub(super) fn encode(mut value: u64) -> String {
+    let mut buf = [b'0'; BASE62_LEN];
+    for slot in buf.iter_mut().rev() {
+        *slot = ALPHABET[(value % 62) as usize];
+        value /= 62;
+    }
+    buf.iter().map(|&b| b as char).collect()
+}
+
+/// 解码 Base62 字符串，允许省略前导 `0`；非法字符或超出 u64 时返回错误
+pub(super) fn decode(text: &str) -> Result<u64> {
+    let invalid = |details: String| ClamberError::SnowflakeConfigError { details };
+    if text.is_empty() {
+        return Err(invalid("Base62 ID不能为空".to_string()));
+    }
+    text.chars().try_fold(0u64, |value, c| {
+        let digit = match c {
+            '0'..='9' => c as u64 - '0' as u64,
+            'A'..='Z' => c as u64 - 'A' as u64 + 10,
+            'a'..='z' => c as u64 - 'a' as u64 + 36,
+            _ => return Err(invalid(format!("Base62 ID包含非法字符: {:?}", c))),
+        };
+        value
+            .checked_mul(62)
+            .and_then(|value| value.checked_add(digit))
+            .ok_or_else(|| invalid(format!("Base62 ID超出u64范围: {}", text)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base62_round_trip() {
+        for value in [0, 1, 61, 62, u64::MAX >> 1, u64::MAX] {
+            let text = encode(value);
+            assert_eq!(text.len(), BASE62_LEN);
+            assert_eq!(decode(&text).unwrap(), value);
+        }
+        assert_eq!(encode(0), "00000000000");
+        assert_eq!(encode(61), "0000000000z");
+        assert_eq!(decode("z").unwrap(), 61);
+    }
+
+    #[test]
+    fn test_base62_rejects_invalid() {
+        for text in ["", "abc-def", "0000000000+", "中文"] {
+            assert!(matches!(
+                decode(text),
+                Err(ClamberError::SnowflakeConfigError { .. })
+            ));
+        }
+        // u64::MAX 为 "LygHa16AHYF"，再加一即溢出
+        assert_eq!(encode(u64::MAX), "LygHa16AHYF");
+        assert!(decode("LygHa16AHYG").is_err());
+        assert!(decode("zzzzzzzzzzz").is_err());
+    }
+}
@@ -0,0 +1,39 @@
+//! ID 新类型：十进制字符串与 u64 之间的转换。
+use crate::error::ClamberError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Snowflake ID，按十进制字符串显示、解析与序列化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SnowflakeId(#[serde(with = "super::serde_string")] pub u64);
+
+impl fmt::Display for SnowflakeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for SnowflakeId {
+    type Err = ClamberError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.parse()
+            .map(SnowflakeId)
+            .map_err(|e| ClamberError::SnowflakeConfigError {
+                details: format!("无法解析ID字符串: {}", e),
+            })
+    }
+}
+
+impl From<u64> for SnowflakeId {
+    fn from(id: u64) -> Self {
+        SnowflakeId(id)
+    }
+}
+
+impl From<SnowflakeId> for u64 {
+    fn from(id: SnowflakeId) -> Self {
+        id.0
+    }
+}
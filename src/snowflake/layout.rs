@@ -0,0 +1,118 @@
+//! ID 位布局：时间戳、工作者ID与序列号各占的位数，三者之和为 63（最高位为符号位，始终为 0）。
+use crate::error::{ClamberError, Result};
+use serde::{Deserialize, Serialize};
+
+/// 时间戳最少位数：32 位约可使用 49 天
+const MIN_TIMESTAMP_BITS: u8 = 32;
+/// 序列号最多位数，与 [`super::SnowflakeIdInfo::sequence`] 的 u16 一致
+const MAX_SEQUENCE_BITS: u8 = 16;
+
+/// ID 位布局，默认为 Twitter 的 41 位时间戳 + 10 位工作者ID + 12 位序列号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SnowflakeLayout {
+    pub timestamp_bits: u8,
+    pub worker_id_bits: u8,
+    pub sequence_bits: u8,
+}
+
+impl Default for SnowflakeLayout {
+    fn default() -> Self {
+        Self {
+            timestamp_bits: 41,
+            worker_id_bits: 10,
+            sequence_bits: 12,
+        }
+    }
+}
+
+impl SnowflakeLayout {
+    /// 创建并校验布局：三者之和为 63，时间戳至少 32 位，工作者ID至少 1 位，序列号 1-16 位
+    pub fn new(timestamp_bits: u8, worker_id_bits: u8, sequence_bits: u8) -> Result<Self> {
+        let layout = Self {
+            timestamp_bits,
+            worker_id_bits,
+            sequence_bits,
+        };
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    pub(super) fn validate(&self) -> Result<()> {
+        let invalid = |details: String| Err(ClamberError::SnowflakeConfigError { details });
+        let total =
+            self.timestamp_bits as u32 + self.worker_id_bits as u32 + self.sequence_bits as u32;
+        if total != 63 {
+            return invalid(format!(
+                "位布局之和必须为63，当前为 {}+{}+{}={}",
+                self.timestamp_bits, self.worker_id_bits, self.sequence_bits, total
+            ));
+        }
+        if self.timestamp_bits < MIN_TIMESTAMP_BITS {
+            return invalid(format!(
+                "时间戳至少需要{}位，当前值: {}",
+                MIN_TIMESTAMP_BITS, self.timestamp_bits
+            ));
+        }
+        if self.worker_id_bits == 0 {
+            return invalid("工作者ID至少需要1位".to_string());
+        }
+        if !(1..=MAX_SEQUENCE_BITS).contains(&self.sequence_bits) {
+            return invalid(format!(
+                "序列号位数必须在1-{}范围内，当前值: {}",
+                MAX_SEQUENCE_BITS, self.sequence_bits
+            ));
+        }
+        Ok(())
+    }
+
+    /// 工作者ID最大值
+    pub fn max_worker_id(&self) -> u64 {
+        (1 << self.worker_id_bits) - 1
+    }
+
+    /// 每毫秒序列号最大值
+    pub fn max_sequence(&self) -> u64 {
+        (1 << self.sequence_bits) - 1
+    }
+
+    /// 时间戳部分（距纪元的毫秒数）最大值
+    pub fn max_timestamp(&self) -> u64 {
+        (1 << self.timestamp_bits) - 1
+    }
+
+    /// 生成状态 `时间戳 << sequence_bits | 序列号` 加上工作者ID组成最终ID
+    pub(super) fn compose(&self, state: u64, worker_id: u64) -> u64 {
+        let timestamp = state >> self.sequence_bits;
+        (timestamp << (self.worker_id_bits + self.sequence_bits))
+            | (worker_id << self.sequence_bits)
+            | (state & self.max_sequence())
+    }
+
+    /// 拆解ID，返回 `(时间戳, 工作者ID, 序列号)`
+    pub(super) fn split(&self, id: u64) -> (u64, u64, u64) {
+        (
+            (id >> (self.worker_id_bits + self.sequence_bits)) & self.max_timestamp(),
+            (id >> self.sequence_bits) & self.max_worker_id(),
+            id & self.max_sequence(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_validation() {
+        assert!(SnowflakeLayout::default().validate().is_ok());
+        assert!(SnowflakeLayout::new(41, 4, 18).is_err());
+        assert!(SnowflakeLayout::new(41, 10, 13).is_err());
+        assert!(SnowflakeLayout::new(31, 16, 16).is_err());
+        assert!(SnowflakeLayout::new(47, 16, 0).is_err());
+        assert!(SnowflakeLayout::new(47, 0, 16).is_err());
+
+        let layout = SnowflakeLayout::new(43, 4, 16).unwrap();
+        assert_eq!(layout.max_worker_id(), 15);
+        assert_eq!(layout.max_sequence(), 0xFFFF);
+    }
+}
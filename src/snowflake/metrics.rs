@@ -0,0 +1,83 @@
+//! ID 生成指标：通过 [`SnowflakeMetrics`] 回调统计生成数量、序列号用尽与时钟回拨。
+//!
+//! 回调在 CAS 成功之后调用，不影响无锁更新本身；未设置指标时只多一次 `Option` 判断。
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// ID 生成指标回调，通过 [`super::SnowflakeManager::with_metrics`] 设置
+///
+/// 各方法默认不做任何事，按需实现即可。
+pub trait SnowflakeMetrics {
+    /// 生成了一个ID
+    fn on_generate(&self) {}
+
+    /// 一次调用生成了 `count` 个ID，默认逐个调用 [`SnowflakeMetrics::on_generate`]
+    fn on_generate_batch(&self, count: u64) {
+        for _ in 0..count {
+            self.on_generate();
+        }
+    }
+
+    /// 当前毫秒的序列号用尽，等待时钟进入下一毫秒共 `wait_ms` 毫秒
+    fn on_sequence_exhausted(&self, wait_ms: u64) {
+        let _ = wait_ms;
+    }
+
+    /// 检测到时钟回拨 `drift_ms` 毫秒，之后按 [`super::ClockDriftPolicy`] 处理
+    fn on_clock_drift(&self, drift_ms: u64) {
+        let _ = drift_ms;
+    }
+}
+
+/// 基于原子计数器的指标实现，由调用方定期读取并导出（如 Prometheus）
+#[derive(Debug, Default)]
+pub struct AtomicMetrics {
+    generated: AtomicU64,
+    rollovers: AtomicU64,
+    max_wait_ms: AtomicU64,
+    clock_drifts: AtomicU64,
+}
+
+impl AtomicMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 已生成的ID总数
+    pub fn generated_total(&self) -> u64 {
+        self.generated.load(Ordering::Relaxed)
+    }
+
+    /// 序列号用尽（进位到下一毫秒）的次数
+    pub fn rollover_total(&self) -> u64 {
+        self.rollovers.load(Ordering::Relaxed)
+    }
+
+    /// 序列号用尽时单次等待的最长时间（毫秒）
+    pub fn max_wait_ms(&self) -> u64 {
+        self.max_wait_ms.load(Ordering::Relaxed)
+    }
+
+    /// 检测到时钟回拨的次数
+    pub fn clock_drift_total(&self) -> u64 {
+        self.clock_drifts.load(Ordering::Relaxed)
+    }
+}
+
+impl SnowflakeMetrics for AtomicMetrics {
+    fn on_generate(&self) {
+        self.generated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_generate_batch(&self, count: u64) {
+        self.generated.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn on_sequence_exhausted(&self, wait_ms: u64) {
+        self.rollovers.fetch_add(1, Ordering::Relaxed);
+        self.max_wait_ms.fetch_max(wait_ms, Ordering::Relaxed);
+    }
+
+    fn on_clock_drift(&self, _drift_ms: u64) {
+        self.clock_drifts.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -1,17 +1,98 @@
 //! Snowflake 模块：线程安全的分布式唯一 ID 生成与解析，支持自定义纪元与批量生成。
 //! 详见根目录 SNOWFLAKE.md 获取更完整说明与示例。
 use crate::error::{ClamberError, Result};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
-use twitter_snowflake::Snowflake;
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod base62;
+mod id;
+mod layout;
+mod metrics;
+mod pool;
+mod registry;
+pub mod serde_string;
+mod worker;
+
+pub use base62::BASE62_LEN;
+pub use id::SnowflakeId;
+pub use layout::SnowflakeLayout;
+pub use metrics::{AtomicMetrics, SnowflakeMetrics};
+pub use pool::SnowflakePool;
+pub use registry::{LocalRegistry, WorkerIdRegistry, WorkerLease};
+pub use worker::{WorkerIdStrategy, hostname_hash, ip_worker_id};
+
+/// 系统时钟回拨时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ClockDriftPolicy {
+    /// 返回 [`ClamberError::SnowflakeClockMovedBackwards`]
+    #[default]
+    Error,
+    /// 等待时钟追上后再生成；回拨超过 `max_wait_ms` 时返回错误
+    WaitUntilCaughtUp { max_wait_ms: u64 },
+    /// 沿用上一次的时间戳继续递增序列号，序列号用尽后返回错误
+    BorrowFromSequence,
+}
+
+/// 时间来源，返回 Unix 毫秒时间戳
+pub trait Clock {
+    fn now_millis(&self) -> u64;
+}
+
+/// 系统时钟
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
 
 /// Snowflake配置结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnowflakeConfig {
-    /// 工作者ID (0-1023)
+    /// 工作者ID（默认布局下为 0-1023）
     pub worker_id: u64,
     /// 自定义纪元时间戳（毫秒，可选）
     pub epoch: Option<u64>,
+    /// 时钟回拨处理策略
+    #[serde(default)]
+    pub clock_drift_policy: ClockDriftPolicy,
+    /// 时间戳位数，默认 41
+    #[serde(default)]
+    pub timestamp_bits: Option<u8>,
+    /// 工作者ID位数，默认 10
+    #[serde(default)]
+    pub worker_id_bits: Option<u8>,
+    /// 序列号位数，默认 12
+    #[serde(default)]
+    pub sequence_bits: Option<u8>,
+    /// 数据中心ID（0-31）；设置后工作者ID位拆为 5 位数据中心 + 5 位工作者
+    #[serde(default)]
+    pub datacenter_id: Option<u64>,
+    /// 工作者ID分配策略；设置后创建管理器时据此解析并覆盖 `worker_id`
+    #[serde(default)]
+    pub worker_strategy: Option<WorkerIdStrategy>,
+    /// [`SnowflakeManager::validate_id`] 允许的工作者ID范围（数据中心模式下为工作者部分），`None` 不限制
+    #[serde(default)]
+    pub allowed_worker_ids: Option<RangeInclusive<u64>>,
+    /// [`SnowflakeManager::validate_id`] 允许ID生成时间超前当前时间的毫秒数，容忍节点间的时钟偏差
+    #[serde(default = "default_future_tolerance_ms")]
+    pub future_tolerance_ms: u64,
+}
+
+fn default_future_tolerance_ms() -> u64 {
+    DEFAULT_FUTURE_TOLERANCE_MS
 }
 
 impl Default for SnowflakeConfig {
@@ -19,6 +100,14 @@ impl Default for SnowflakeConfig {
         Self {
             worker_id: 1,
             epoch: None, // 使用默认纪元
+            clock_drift_policy: ClockDriftPolicy::default(),
+            timestamp_bits: None,
+            worker_id_bits: None,
+            sequence_bits: None,
+            datacenter_id: None,
+            worker_strategy: None,
+            allowed_worker_ids: None,
+            future_tolerance_ms: DEFAULT_FUTURE_TOLERANCE_MS,
         }
     }
 }
@@ -26,28 +115,40 @@ impl Default for SnowflakeConfig {
 impl SnowflakeConfig {
     /// 创建新的Snowflake配置
     pub fn new(worker_id: u64) -> Result<Self> {
-        Self::validate_worker_id(worker_id)?;
-
-        Ok(Self {
-            worker_id,
-            epoch: None,
-        })
+        Self::default().worker_id(worker_id)
     }
 
     /// 创建带自定义纪元的配置
     pub fn with_epoch(worker_id: u64, epoch: u64) -> Result<Self> {
-        Self::validate_worker_id(worker_id)?;
+        Ok(Self::new(worker_id)?.epoch(epoch))
+    }
 
-        Ok(Self {
+    /// 创建数据中心模式的配置，数据中心ID与工作者ID均为 0-31
+    pub fn with_datacenter(datacenter_id: u64, worker_id: u64) -> Result<Self> {
+        let config = Self {
             worker_id,
-            epoch: Some(epoch),
-        })
+            datacenter_id: Some(datacenter_id),
+            ..Self::default()
+        };
+        config.layout()?;
+        Ok(config)
+    }
+
+    /// 创建按策略分配工作者ID的配置
+    pub fn with_worker_strategy(strategy: WorkerIdStrategy) -> Self {
+        Self::default().worker_strategy(strategy)
+    }
+
+    /// 设置工作者ID分配策略，优先于 `worker_id`
+    pub fn worker_strategy(mut self, strategy: WorkerIdStrategy) -> Self {
+        self.worker_strategy = Some(strategy);
+        self
     }
 
-    /// 设置工作者ID
+    /// 设置工作者ID，按当前位布局校验范围
     pub fn worker_id(mut self, worker_id: u64) -> Result<Self> {
-        Self::validate_worker_id(worker_id)?;
         self.worker_id = worker_id;
+        self.layout()?;
         Ok(self)
     }
 
@@ -57,73 +158,430 @@ impl SnowflakeConfig {
         self
     }
 
-    /// 验证工作者ID有效性
-    fn validate_worker_id(worker_id: u64) -> Result<()> {
-        if worker_id > 1023 {
+    /// 设置时钟回拨处理策略
+    pub fn clock_drift_policy(mut self, policy: ClockDriftPolicy) -> Self {
+        self.clock_drift_policy = policy;
+        self
+    }
+
+    /// 设置 [`SnowflakeManager::validate_id`] 允许的工作者ID范围
+    pub fn allowed_worker_ids(mut self, range: RangeInclusive<u64>) -> Self {
+        self.allowed_worker_ids = Some(range);
+        self
+    }
+
+    /// 设置 [`SnowflakeManager::validate_id`] 允许的生成时间超前毫秒数
+    pub fn future_tolerance_ms(mut self, tolerance_ms: u64) -> Self {
+        self.future_tolerance_ms = tolerance_ms;
+        self
+    }
+
+    /// 设置位布局（三者之和为 63），工作者ID需在新布局的范围内
+    pub fn bit_layout(
+        mut self,
+        timestamp_bits: u8,
+        worker_id_bits: u8,
+        sequence_bits: u8,
+    ) -> Result<Self> {
+        self.timestamp_bits = Some(timestamp_bits);
+        self.worker_id_bits = Some(worker_id_bits);
+        self.sequence_bits = Some(sequence_bits);
+        self.layout()?;
+        Ok(self)
+    }
+
+    /// 写入ID工作者字段的机器ID：数据中心模式下为 `datacenter_id << 5 | worker_id`
+    pub fn machine_id(&self) -> u64 {
+        match self.datacenter_id {
+            Some(datacenter_id) => (datacenter_id << DATACENTER_WORKER_BITS) | self.worker_id,
+            None => self.worker_id,
+        }
+    }
+
+    /// 生效的位布局，未设置的位数取默认值；同时校验工作者ID是否在范围内
+    pub fn layout(&self) -> Result<SnowflakeLayout> {
+        let layout = self.bits()?;
+        if let Some(datacenter_id) = self.datacenter_id {
+            let invalid = |details: String| Err(ClamberError::SnowflakeConfigError { details });
+            if layout.worker_id_bits != 2 * DATACENTER_WORKER_BITS {
+                return invalid(format!(
+                    "数据中心模式要求工作者ID位数为{}，当前值: {}",
+                    2 * DATACENTER_WORKER_BITS,
+                    layout.worker_id_bits
+                ));
+            }
+            if datacenter_id > MAX_DATACENTER_PART {
+                return invalid(format!(
+                    "数据中心ID必须在0-{}范围内，当前值: {}",
+                    MAX_DATACENTER_PART, datacenter_id
+                ));
+            }
+            if self.worker_id > MAX_DATACENTER_PART {
+                return invalid(format!(
+                    "数据中心模式下工作者ID必须在0-{}范围内，当前值: {}",
+                    MAX_DATACENTER_PART, self.worker_id
+                ));
+            }
+            return Ok(layout);
+        }
+        if self.worker_id > layout.max_worker_id() {
             return Err(ClamberError::SnowflakeConfigError {
-                details: format!("工作者ID必须在0-1023范围内，当前值: {}", worker_id),
+                details: format!(
+                    "工作者ID必须在0-{}范围内，当前值: {}",
+                    layout.max_worker_id(),
+                    self.worker_id
+                ),
             });
         }
+        Ok(layout)
+    }
+
+    /// 按策略解析出工作者ID，写回 `worker_id`
+    fn resolve_worker_id(&mut self) -> Result<()> {
+        if let Some(strategy) = &self.worker_strategy {
+            let max_worker_id = match self.datacenter_id {
+                Some(_) => MAX_DATACENTER_PART,
+                None => self.bits()?.max_worker_id(),
+            };
+            self.worker_id = strategy.resolve(max_worker_id)?;
+        }
         Ok(())
     }
+
+    fn bits(&self) -> Result<SnowflakeLayout> {
+        let default = SnowflakeLayout::default();
+        SnowflakeLayout::new(
+            self.timestamp_bits.unwrap_or(default.timestamp_bits),
+            self.worker_id_bits.unwrap_or(default.worker_id_bits),
+            self.sequence_bits.unwrap_or(default.sequence_bits),
+        )
+    }
+}
+
+/// 数据中心模式下数据中心ID与工作者ID各占的位数
+const DATACENTER_WORKER_BITS: u8 = 5;
+/// 数据中心模式下两者的最大值
+const MAX_DATACENTER_PART: u64 = (1 << DATACENTER_WORKER_BITS) - 1;
+
+/// 默认纪元：2024-01-01 00:00:00 UTC+8（即 2023-12-31 16:00:00 UTC，毫秒）
+const DEFAULT_EPOCH: u64 = 1_704_038_400_000;
+/// 校验外部ID时默认允许的生成时间超前量（毫秒）
+const DEFAULT_FUTURE_TOLERANCE_MS: u64 = 1_000;
+/// 等待时钟追上已分配的时间戳时，在预计等待时间之外最多再等多久
+const WAIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 底层ID生成器
+enum IdGenerator {
+    /// 基于系统时钟，时间戳为距 `epoch` 的毫秒数
+    System { epoch: u64 },
+    /// 固定起始时间戳、序列号递增的确定性生成器
+    Sequential,
 }
 
 /// Snowflake ID生成器封装
+///
+/// 生成状态 `时间戳 << sequence_bits | 序列号` 存放在单个原子变量中，通过 CAS 无锁更新，
+/// 多线程共享同一实例时生成的ID全局唯一且单调递增。
 pub struct SnowflakeManager {
-    generator: Mutex<Snowflake>,
+    generator: IdGenerator,
+    state: AtomicU64,
+    /// 读到过的最大时钟值（距纪元的毫秒数），用于识别时钟回拨
+    last_clock: AtomicU64,
+    clock: Arc<dyn Clock + Send + Sync>,
+    layout: SnowflakeLayout,
     config: SnowflakeConfig,
+    /// 通过登记表占用的工作者ID，销毁时释放
+    lease: Option<(Arc<dyn WorkerIdRegistry + Send + Sync>, WorkerLease)>,
+    /// 补录ID时各毫秒已用的序列号数，由 [`SnowflakeManager::clear_backfill`] 清空
+    backfill: Mutex<HashMap<u64, u64>>,
+    /// 生成指标回调
+    metrics: Option<Arc<dyn SnowflakeMetrics + Send + Sync>>,
+}
+
+// 管理器通常放在 Arc 或 static 中跨线程共享
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SnowflakeManager>();
+};
+
+#[cfg(feature = "async")]
+impl SnowflakeManager {
+    /// [`SnowflakeManager::generate_ids`] 的异步版本
+    ///
+    /// `count` 不超过每毫秒序列号容量（默认布局为 4096）时同步执行；更多的ID需要等待时钟走过多个毫秒，
+    /// 在后台线程池中生成，不阻塞异步运行时的工作线程。管理器需放在 `Arc` 中：
+    /// `manager.clone().generate_ids_async(10_000).await`。
+    pub async fn generate_ids_async(self: Arc<Self>, count: usize) -> Result<Vec<u64>> {
+        if count as u64 <= self.layout.max_sequence() + 1 {
+            return self.generate_ids(count);
+        }
+        crate::offload::offload(move || self.generate_ids(count)).await
+    }
 }
 
 impl SnowflakeManager {
     /// 使用自定义配置创建Snowflake管理器
     pub fn new(config: SnowflakeConfig) -> Result<Self> {
-        let generator = if let Some(epoch) = config.epoch {
-            Snowflake::builder()
-                .with_worker_id(config.worker_id)
-                .with_epoch(epoch)
-                .build()
-        } else {
-            Snowflake::new(config.worker_id)
-        };
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// 使用指定时间来源创建Snowflake管理器
+    pub fn with_clock(
+        mut config: SnowflakeConfig,
+        clock: Arc<dyn Clock + Send + Sync>,
+    ) -> Result<Self> {
+        config.resolve_worker_id()?;
+        let layout = config.layout()?;
+        let epoch = config.epoch.unwrap_or(DEFAULT_EPOCH);
+        if epoch >= clock.now_millis() {
+            return Err(ClamberError::SnowflakeInitError {
+                details: format!("初始化Snowflake生成器失败: 纪元 {} 晚于当前时间", epoch),
+            });
+        }
+
+        Ok(Self {
+            generator: IdGenerator::System { epoch },
+            state: AtomicU64::new(0),
+            last_clock: AtomicU64::new(0),
+            clock,
+            layout,
+            config,
+            lease: None,
+            backfill: Mutex::new(HashMap::new()),
+            metrics: None,
+        })
+    }
+
+    /// 创建管理器并在登记表中占用工作者ID（数据中心模式下为合并后的机器ID），销毁时释放
+    ///
+    /// 工作者ID已被占用时返回 [`ClamberError::SnowflakeConfigError`]。
+    pub fn new_with_registry(
+        config: SnowflakeConfig,
+        registry: Arc<dyn WorkerIdRegistry + Send + Sync>,
+    ) -> Result<Self> {
+        let mut manager = Self::new(config)?;
+        let lease = registry.try_claim(manager.config.machine_id())?;
+        manager.lease = Some((registry, lease));
+        Ok(manager)
+    }
+
+    /// 设置生成指标回调，如 [`AtomicMetrics`]
+    pub fn with_metrics(mut self, metrics: Arc<dyn SnowflakeMetrics + Send + Sync>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// 登记表中的租约，未使用登记表时为 None
+    pub fn lease(&self) -> Option<&WorkerLease> {
+        self.lease.as_ref().map(|(_, lease)| lease)
+    }
 
-        let generator = generator.map_err(|e| ClamberError::SnowflakeInitError {
-            details: format!("初始化Snowflake生成器失败: {:?}", e),
-        })?;
+    /// 续约占用的工作者ID；未使用登记表时直接返回成功
+    pub fn renew_lease(&self) -> Result<()> {
+        match &self.lease {
+            Some((registry, lease)) => registry.renew(lease),
+            None => Ok(()),
+        }
+    }
 
+    /// 创建确定性的管理器：时间戳部分从 `timestamp` 开始，序列号逐个递增（用尽后时间戳加一）
+    ///
+    /// 不读取系统时钟，生成的ID序列完全可预测，仅适用于测试。
+    pub fn deterministic(mut config: SnowflakeConfig, timestamp: u64) -> Result<Self> {
+        config.resolve_worker_id()?;
+        let layout = config.layout()?;
         Ok(Self {
-            generator: Mutex::new(generator),
+            generator: IdGenerator::Sequential,
+            state: AtomicU64::new(timestamp << layout.sequence_bits),
+            last_clock: AtomicU64::new(0),
+            clock: Arc::new(SystemClock),
+            layout,
             config,
+            lease: None,
+            backfill: Mutex::new(HashMap::new()),
+            metrics: None,
         })
     }
 
     /// 使用默认配置创建Snowflake管理器
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Result<Self> {
         Self::new(SnowflakeConfig::default())
     }
 
     /// 生成新的Snowflake ID
     pub fn generate_id(&self) -> Result<u64> {
-        let mut generator =
-            self.generator
-                .lock()
-                .map_err(|e| ClamberError::SnowflakeGenerateError {
-                    details: format!("获取生成器锁失败: {}", e),
-                })?;
-
-        generator
-            .generate()
-            .map_err(|e| ClamberError::SnowflakeGenerateError {
-                details: format!("生成ID失败: {:?}", e),
-            })
+        let state = self.reserve(1)?;
+        if let Some(metrics) = &self.metrics {
+            metrics.on_generate();
+        }
+        Ok(self.layout.compose(state, self.config.machine_id()))
+    }
+
+    /// 生成并丢弃一个ID，供编排系统探活：时钟回拨、时间戳溢出等无法生成ID的状态会返回对应错误
+    pub fn health_check(&self) -> Result<()> {
+        self.generate_id().map(|_| ())
     }
 
-    /// 生成多个ID
+    /// 生成多个ID，一次预留整段序列号，结果严格递增且不与其他调用方交错
     pub fn generate_ids(&self, count: usize) -> Result<Vec<u64>> {
-        let mut ids = Vec::with_capacity(count);
-        for _ in 0..count {
-            ids.push(self.generate_id()?);
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        Ok(self.generate_id_block(count)?.iter().collect())
+    }
+
+    /// 预留连续的一段ID（如批量插入前预分配主键）
+    ///
+    /// 整段通过一次 CAS 预留：当前毫秒的序列号不够时顺延到之后的毫秒，
+    /// 并等待时钟追上最后一个毫秒后再返回，保证ID不超前于时钟。
+    pub fn generate_id_block(&self, count: usize) -> Result<IdBlock> {
+        if count == 0 {
+            return Err(ClamberError::SnowflakeGenerateError {
+                details: "ID数量必须大于0".to_string(),
+            });
+        }
+        let first = self.reserve(count as u64)?;
+        if let Some(metrics) = &self.metrics {
+            metrics.on_generate_batch(count as u64);
+        }
+        Ok(IdBlock {
+            first,
+            len: count as u64,
+            machine_id: self.config.machine_id(),
+            layout: self.layout,
+        })
+    }
+
+    /// CAS 预留 `count` 个连续状态，返回第一个
+    ///
+    /// 进入新的毫秒时序列号从 0 开始，否则接着上一次的序列号；序列号用尽时进位到下一毫秒。
+    /// 时钟回拨按 [`ClockDriftPolicy`] 处理。
+    fn reserve(&self, count: u64) -> Result<u64> {
+        let epoch = match self.generator {
+            IdGenerator::System { epoch } => epoch,
+            // 序列号溢出时自然进位到时间戳
+            IdGenerator::Sequential => return Ok(self.state.fetch_add(count, Ordering::Relaxed)),
+        };
+
+        let mut current = self.state.load(Ordering::Acquire);
+        loop {
+            let (now, drift) = self.observe_clock(epoch)?;
+            let shift = self.layout.sequence_bits;
+            let last = current >> shift;
+            let first = if now > last {
+                now << shift
+            } else {
+                current + 1
+            };
+            let end = first + count - 1;
+            let end_timestamp = end >> shift;
+            // 进位到了时钟尚未到达的毫秒
+            let carries = end_timestamp > last.max(now);
+            if carries && drift > 0 {
+                return Err(ClamberError::SnowflakeClockMovedBackwards { drift_ms: drift });
+            }
+            if end_timestamp > self.layout.max_timestamp() {
+                return Err(ClamberError::SnowflakeGenerateError {
+                    details: format!(
+                        "生成ID失败: 时间戳超出{}位，请调整纪元或位布局",
+                        self.layout.timestamp_bits
+                    ),
+                });
+            }
+
+            match self.state.compare_exchange_weak(
+                current,
+                end,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    if carries {
+                        self.wait_for_rollover(epoch, end_timestamp, now)?;
+                    }
+                    return Ok(first);
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// 序列号进位到时钟尚未到达的毫秒后，等待时钟追上 `end_timestamp`
+    fn wait_for_rollover(&self, epoch: u64, end_timestamp: u64, now: u64) -> Result<()> {
+        let expected = Duration::from_millis(end_timestamp.saturating_sub(now));
+        let start = Instant::now();
+        let caught_up = self.wait_until(epoch, end_timestamp, WAIT_TIMEOUT + expected);
+        if let Some(metrics) = &self.metrics {
+            metrics.on_sequence_exhausted(start.elapsed().as_millis() as u64);
+        }
+        if !caught_up {
+            return Err(ClamberError::SnowflakeGenerateError {
+                details: "生成ID失败: 等待时钟追上已分配的时间戳超时".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// 读取时钟（距纪元的毫秒数），返回 `(当前值, 回拨毫秒数)`
+    ///
+    /// 检测到回拨时按策略报错或等待；只有 [`ClockDriftPolicy::BorrowFromSequence`] 会返回非零的回拨值。
+    fn observe_clock(&self, epoch: u64) -> Result<(u64, u64)> {
+        let mut now = self.clock_millis(epoch);
+        let mut max = self.last_clock.fetch_max(now, Ordering::AcqRel);
+        if now < max {
+            // 其他线程可能在本线程读时钟之后才更新记录，重读一次排除误判
+            now = self.clock_millis(epoch);
+            max = self.last_clock.fetch_max(now, Ordering::AcqRel);
+        }
+        if now >= max {
+            return Ok((now, 0));
+        }
+
+        let drift = max - now;
+        if let Some(metrics) = &self.metrics {
+            metrics.on_clock_drift(drift);
+        }
+        match self.config.clock_drift_policy {
+            ClockDriftPolicy::Error => {
+                Err(ClamberError::SnowflakeClockMovedBackwards { drift_ms: drift })
+            }
+            ClockDriftPolicy::BorrowFromSequence => Ok((now, drift)),
+            ClockDriftPolicy::WaitUntilCaughtUp { max_wait_ms } => {
+                if drift > max_wait_ms
+                    || !self.wait_until(epoch, max, Duration::from_millis(max_wait_ms))
+                {
+                    return Err(ClamberError::SnowflakeClockMovedBackwards { drift_ms: drift });
+                }
+                let now = self.clock_millis(epoch);
+                self.last_clock.fetch_max(now, Ordering::AcqRel);
+                Ok((now, 0))
+            }
+        }
+    }
+
+    /// 当前时间距纪元的毫秒数
+    fn clock_millis(&self, epoch: u64) -> u64 {
+        self.clock.now_millis().saturating_sub(epoch)
+    }
+
+    /// 等待距纪元的毫秒数达到 `timestamp`，超过 `max_wait` 仍未到达时返回 false
+    fn wait_until(&self, epoch: u64, timestamp: u64, max_wait: Duration) -> bool {
+        let start = Instant::now();
+        loop {
+            let ahead = timestamp.saturating_sub(self.clock_millis(epoch));
+            if ahead == 0 {
+                return true;
+            }
+            if start.elapsed() > max_wait {
+                return false;
+            }
+            if ahead > 1 {
+                std::thread::sleep(Duration::from_millis(ahead - 1));
+            } else {
+                std::hint::spin_loop();
+            }
         }
-        Ok(ids)
     }
 
     /// 获取当前配置
@@ -136,22 +594,220 @@ impl SnowflakeManager {
         self.config.worker_id
     }
 
-    /// 解析Snowflake ID的各个组成部分
+    /// 获取数据中心ID，未启用数据中心模式时为 None
+    pub fn datacenter_id(&self) -> Option<u64> {
+        self.config.datacenter_id
+    }
+
+    /// 生效的位布局
+    pub fn layout(&self) -> SnowflakeLayout {
+        self.layout
+    }
+
+    /// 生成 11 位定长的 Base62 字符串ID，字符串顺序与生成顺序一致
+    pub fn generate_base62_id(&self) -> Result<String> {
+        Ok(base62::encode(self.generate_id()?))
+    }
+
+    /// 时间范围 `[start, end]`（毫秒精度，两端包含）内可能出现的最小和最大ID
+    ///
+    /// 最小值为 `start` 时刻、工作者ID与序列号全 0；最大值为 `end` 时刻、两者全 1，
+    /// 可直接用于按主键范围查询：`WHERE id BETWEEN min AND max`。
+    pub fn id_range_for(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<(u64, u64)> {
+        if start > end {
+            return Err(ClamberError::SnowflakeConfigError {
+                details: format!("时间范围无效: 起点 {} 晚于终点 {}", start, end),
+            });
+        }
+        let shift = self.layout.sequence_bits;
+        let min = self.layout.compose(self.timestamp_of(start)? << shift, 0);
+        let max = self.layout.compose(
+            (self.timestamp_of(end)? << shift) | self.layout.max_sequence(),
+            self.layout.max_worker_id(),
+        );
+        Ok((min, max))
+    }
+
+    /// 以指定时间生成ID，用于迁移历史数据时补录，使其与实时ID按时间正确排序
+    ///
+    /// 时间需在纪元与当前时间之间；同一毫秒内的序列号单独计数，不影响实时生成器的状态。
+    /// 补录的毫秒可能与实时生成的ID重合，建议为补录任务分配单独的工作者ID。
+    pub fn generate_id_at(&self, timestamp: DateTime<Utc>) -> Result<u64> {
+        Ok(self.generate_ids_at(timestamp, 1)?[0])
+    }
+
+    /// 以指定时间批量生成ID，该毫秒剩余的序列号不足时整体失败，不会部分分配
+    pub fn generate_ids_at(&self, timestamp: DateTime<Utc>, count: usize) -> Result<Vec<u64>> {
+        let offset = self.timestamp_of(timestamp)?;
+        if offset > self.clock_millis(self.config.epoch.unwrap_or(DEFAULT_EPOCH)) {
+            return Err(ClamberError::SnowflakeConfigError {
+                details: format!("补录时间 {} 晚于当前时间", timestamp),
+            });
+        }
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let first = {
+            let mut counters = self.backfill.lock().unwrap_or_else(|e| e.into_inner());
+            let next = counters.entry(offset).or_insert(0);
+            let capacity = self.layout.max_sequence() + 1;
+            if *next + count as u64 > capacity {
+                return Err(ClamberError::SnowflakeGenerateError {
+                    details: format!(
+                        "生成ID失败: {} 的序列号已用尽（剩余 {} 个，请求 {} 个）",
+                        timestamp,
+                        capacity - *next,
+                        count
+                    ),
+                });
+            }
+            let first = *next;
+            *next += count as u64;
+            first
+        };
+        let base = offset << self.layout.sequence_bits;
+        Ok((first..first + count as u64)
+            .map(|sequence| {
+                self.layout
+                    .compose(base | sequence, self.config.machine_id())
+            })
+            .collect())
+    }
+
+    /// 清空补录时记录的各毫秒序列号计数
+    ///
+    /// 每个补录过的毫秒占用一条记录，在清空前一直保留；长时间运行的迁移任务应在每批完成后调用。
+    /// 清空后再次补录同一毫秒会从序列号 0 重新开始，与之前补录的ID重复，
+    /// 因此只应在已补录的时间段不会再次补录时调用。
+    pub fn clear_backfill(&self) {
+        self.backfill
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    /// 时间距纪元的毫秒数，早于纪元或超出时间戳位数时返回错误
+    fn timestamp_of(&self, time: DateTime<Utc>) -> Result<u64> {
+        let epoch = self.config.epoch.unwrap_or(DEFAULT_EPOCH);
+        let millis = time.timestamp_millis();
+        if millis < epoch as i64 {
+            return Err(ClamberError::SnowflakeConfigError {
+                details: format!("时间 {} 早于纪元 {}", time, epoch),
+            });
+        }
+        let timestamp = millis as u64 - epoch;
+        if timestamp > self.layout.max_timestamp() {
+            return Err(ClamberError::SnowflakeConfigError {
+                details: format!(
+                    "时间 {} 超出{}位时间戳范围",
+                    time, self.layout.timestamp_bits
+                ),
+            });
+        }
+        Ok(timestamp)
+    }
+
+    /// 解析 Base62 字符串ID
+    pub fn parse_base62_id(&self, text: &str) -> Result<SnowflakeIdInfo> {
+        Ok(self.parse_id(base62::decode(text)?))
+    }
+
+    /// 按管理器的位布局解析Snowflake ID的各个组成部分
+    ///
+    /// 数据中心模式下工作者ID位再拆为 `datacenter_id` 与 `worker_id`。
     pub fn parse_id(&self, id: u64) -> SnowflakeIdInfo {
-        // Twitter Snowflake ID结构：1位符号位 + 41位时间戳 + 10位工作者ID + 12位序列号
-        let timestamp = (id >> 22) & 0x1FFFFFFFFFF; // 41位时间戳
-        let worker_id = (id >> 12) & 0x3FF; // 10位工作者ID
-        let sequence = id & 0xFFF; // 12位序列号
+        // 布局在创建管理器时已校验
+        let mut info = SnowflakeIdInfo::split(id, self.layout);
+        info.epoch = self.config.epoch.unwrap_or(DEFAULT_EPOCH);
+        if self.config.datacenter_id.is_some() {
+            info.datacenter_id = Some(info.worker_id >> DATACENTER_WORKER_BITS);
+            info.worker_id &= MAX_DATACENTER_PART;
+        }
+        info
+    }
 
-        SnowflakeIdInfo {
-            id,
-            timestamp,
-            worker_id,
-            sequence: sequence as u16,
+    /// 解析并校验外部传入的ID，如请求参数中的主键
+    ///
+    /// 以下情况返回 [`ClamberError::SnowflakeInvalidIdError`]，`reason` 说明具体原因：
+    /// 最高位为 1（按 `i64` 存储时为负数，早于纪元）；生成时间晚于当前时间超过
+    /// `future_tolerance_ms`；工作者ID不在 `allowed_worker_ids` 范围内。
+    pub fn validate_id(&self, id: u64) -> Result<SnowflakeIdInfo> {
+        let invalid = |reason: String| Err(ClamberError::SnowflakeInvalidIdError { id, reason });
+        if id >> 63 != 0 {
+            return invalid("最高位为1，按有符号整数解读早于纪元".to_string());
+        }
+        let info = self.parse_id(id);
+        let now = self.clock.now_millis();
+        let latest = now.saturating_add(self.config.future_tolerance_ms);
+        if info.generation_time() > latest {
+            return invalid(format!(
+                "生成时间 {} 晚于当前时间 {}ms",
+                info.generation_time_string(),
+                info.generation_time() - now
+            ));
+        }
+        if let Some(range) = &self.config.allowed_worker_ids
+            && !range.contains(&info.worker_id)
+        {
+            return invalid(format!(
+                "工作者ID {} 不在允许范围 {}-{} 内",
+                info.worker_id,
+                range.start(),
+                range.end()
+            ));
+        }
+        Ok(info)
+    }
+}
+
+impl Drop for SnowflakeManager {
+    fn drop(&mut self) {
+        if let Some((registry, lease)) = &self.lease {
+            registry.release(lease);
         }
     }
 }
 
+/// [`SnowflakeManager::generate_id_block`] 预留的一段ID
+///
+/// 段内ID严格递增，但跨毫秒处不连续，需通过 [`IdBlock::iter`] 逐个取出。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdBlock {
+    first: u64,
+    len: u64,
+    machine_id: u64,
+    layout: SnowflakeLayout,
+}
+
+impl IdBlock {
+    /// 第一个ID
+    pub fn start(&self) -> u64 {
+        self.layout.compose(self.first, self.machine_id)
+    }
+
+    /// 最后一个ID（包含）
+    pub fn end(&self) -> u64 {
+        self.layout
+            .compose(self.first + self.len - 1, self.machine_id)
+    }
+
+    /// ID数量
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// 是否为空（预留的段至少包含一个ID）
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// 按递增顺序遍历段内ID
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        (self.first..self.first + self.len).map(|state| self.layout.compose(state, self.machine_id))
+    }
+}
+
 /// Snowflake ID信息结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnowflakeIdInfo {
@@ -161,55 +817,186 @@ pub struct SnowflakeIdInfo {
     pub timestamp: u64,
     /// 工作者ID部分
     pub worker_id: u64,
+    /// 数据中心ID部分，仅由数据中心模式的管理器解析时填充
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacenter_id: Option<u64>,
     /// 序列号部分
     pub sequence: u16,
+    /// 解析时使用的位布局
+    #[serde(default)]
+    pub layout: SnowflakeLayout,
+    /// 解析时使用的纪元（毫秒），`timestamp` 为距该纪元的毫秒数
+    #[serde(default = "default_epoch")]
+    pub epoch: u64,
+}
+
+fn default_epoch() -> u64 {
+    DEFAULT_EPOCH
 }
 
 impl SnowflakeIdInfo {
-    /// 获取生成时间（毫秒时间戳）
-    pub fn generation_time(&self, epoch: Option<u64>) -> u64 {
-        let epoch = epoch.unwrap_or(1288834974657); // Twitter纪元 (2010-11-04T01:42:54.657Z)
-        self.timestamp + epoch
+    /// 按 Twitter Snowflake 位布局拆解ID，无需生成器实例
+    pub fn from_id(id: u64) -> Self {
+        // Twitter Snowflake ID结构：1位符号位 + 41位时间戳 + 10位工作者ID + 12位序列号
+        Self::split(id, SnowflakeLayout::default())
+    }
+
+    /// 按指定位布局拆解ID，纪元取默认纪元；自定义纪元的ID请使用 [`SnowflakeManager::parse_id`]
+    ///
+    /// 布局不合法（如直接构造或反序列化得到的字段未经校验）时返回 [`ClamberError::SnowflakeConfigError`]。
+    pub fn from_id_with_layout(id: u64, layout: SnowflakeLayout) -> Result<Self> {
+        layout.validate()?;
+        Ok(Self::split(id, layout))
     }
 
-    /// 获取可读的时间字符串
-    pub fn generation_time_string(&self, epoch: Option<u64>) -> String {
-        let timestamp_ms = self.generation_time(epoch);
-        let datetime = chrono::DateTime::from_timestamp_millis(timestamp_ms as i64);
-        match datetime {
+    /// 按已校验的布局拆解ID
+    fn split(id: u64, layout: SnowflakeLayout) -> Self {
+        let (timestamp, worker_id, sequence) = layout.split(id);
+        Self {
+            id,
+            timestamp,
+            worker_id,
+            datacenter_id: None,
+            sequence: sequence as u16,
+            layout,
+            epoch: DEFAULT_EPOCH,
+        }
+    }
+
+    /// 获取生成时间（Unix 毫秒时间戳），按解析时记录的纪元计算
+    pub fn generation_time(&self) -> u64 {
+        self.timestamp + self.epoch
+    }
+
+    /// 获取生成时间
+    pub fn generation_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(i64::try_from(self.generation_time()).ok()?)
+    }
+
+    /// 获取可读的时间字符串（UTC）
+    pub fn generation_time_string(&self) -> String {
+        match self.generation_datetime() {
             Some(dt) => dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
             None => "Invalid timestamp".to_string(),
         }
     }
 
+    /// 按指定纪元获取生成时间，`None` 使用解析时记录的纪元
+    #[deprecated(note = "纪元已记录在 `epoch` 字段中，使用 `generation_time()`")]
+    pub fn generation_time_with_epoch(&self, epoch: Option<u64>) -> u64 {
+        self.timestamp + epoch.unwrap_or(self.epoch)
+    }
+
+    /// 按指定纪元获取可读的时间字符串，`None` 使用解析时记录的纪元
+    #[deprecated(note = "纪元已记录在 `epoch` 字段中，使用 `generation_time_string()`")]
+    pub fn generation_time_string_with_epoch(&self, epoch: Option<u64>) -> String {
+        let info = Self {
+            epoch: epoch.unwrap_or(self.epoch),
+            ..self.clone()
+        };
+        info.generation_time_string()
+    }
+
     /// 转换为字符串格式的ID
+    #[deprecated(note = "使用 Display 实现，如 `format!(\"{}\", info)`")]
+    #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         self.id.to_string()
     }
 
+    /// 转换为 11 位定长的 Base62 字符串（`0-9A-Za-z`），补零后可直接按字符串排序
+    pub fn to_base62(&self) -> String {
+        base62::encode(self.id)
+    }
+
+    /// 从 Base62 字符串解析ID，允许省略前导 `0`
+    pub fn from_base62(text: &str) -> Result<u64> {
+        base62::decode(text)
+    }
+
     /// 从字符串解析ID
+    #[deprecated(note = "使用 `str::parse::<SnowflakeId>()`")]
     pub fn from_string(id_str: &str) -> Result<u64> {
-        id_str
-            .parse::<u64>()
-            .map_err(|e| ClamberError::SnowflakeConfigError {
-                details: format!("无法解析ID字符串: {}", e),
-            })
+        Ok(id_str.parse::<SnowflakeId>()?.0)
+    }
+}
+
+/// 显示十进制ID
+impl fmt::Display for SnowflakeIdInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+/// 相等与排序只比较 `id`
+impl PartialEq for SnowflakeIdInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
     }
 }
 
-// 便利函数：使用默认配置
-use once_cell::sync::Lazy;
+impl Eq for SnowflakeIdInfo {}
+
+impl PartialOrd for SnowflakeIdInfo {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SnowflakeIdInfo {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.id.cmp(&other.id)
+    }
+}
+
+// 便利函数：使用默认管理器
+use once_cell::sync::OnceCell;
+
+/// [`init_default_from_env`] 读取的工作者ID环境变量
+pub const WORKER_ID_ENV: &str = "CLAMBER_WORKER_ID";
+/// [`init_default_from_env`] 读取的纪元环境变量（毫秒时间戳，可选）
+pub const EPOCH_ENV: &str = "CLAMBER_SNOWFLAKE_EPOCH";
+
+static DEFAULT_MANAGER: OnceCell<SnowflakeManager> = OnceCell::new();
+
+/// 使用指定配置初始化便利函数使用的默认管理器
+///
+/// 需在首次调用 `generate_id` 等便利函数之前执行（本库其他模块不使用默认管理器）；默认管理器已创建（已初始化或已被使用）时返回
+/// `SnowflakeInitError`。未调用时首次使用按默认配置（工作者ID为 1）创建，多实例部署会产生重复ID。
+pub fn init_default(config: SnowflakeConfig) -> Result<()> {
+    let manager = SnowflakeManager::new(config)?;
+    DEFAULT_MANAGER
+        .set(manager)
+        .map_err(|_| ClamberError::SnowflakeInitError {
+            details: "default manager already initialized".to_string(),
+        })
+}
 
-static DEFAULT_MANAGER: Lazy<Result<SnowflakeManager>> = Lazy::new(|| SnowflakeManager::default());
+/// 按环境变量初始化默认管理器：`CLAMBER_WORKER_ID`（必填）与 `CLAMBER_SNOWFLAKE_EPOCH`（可选）
+///
+/// 变量缺失或无效时返回 `SnowflakeConfigError`，默认管理器保持未初始化。
+pub fn init_default_from_env() -> Result<()> {
+    let mut config =
+        SnowflakeConfig::with_worker_strategy(WorkerIdStrategy::FromEnv(WORKER_ID_ENV.to_string()));
+    if let Ok(value) = std::env::var(EPOCH_ENV) {
+        let epoch = value
+            .trim()
+            .parse()
+            .map_err(|e| ClamberError::SnowflakeConfigError {
+                details: format!("环境变量 {} 无效: {}", EPOCH_ENV, e),
+            })?;
+        config = config.epoch(epoch);
+    }
+    init_default(config)
+}
 
 /// 获取或创建默认的Snowflake管理器
 fn get_default_manager() -> Result<&'static SnowflakeManager> {
-    match &*DEFAULT_MANAGER {
-        Ok(manager) => Ok(manager),
-        Err(e) => Err(ClamberError::SnowflakeInitError {
+    DEFAULT_MANAGER
+        .get_or_try_init(SnowflakeManager::default)
+        .map_err(|e| ClamberError::SnowflakeInitError {
             details: format!("无法初始化默认Snowflake管理器: {}", e),
-        }),
-    }
+        })
 }
 
 /// 使用默认配置生成ID
@@ -227,6 +1014,11 @@ pub fn parse_id(id: u64) -> Result<SnowflakeIdInfo> {
     Ok(get_default_manager()?.parse_id(id))
 }
 
+/// 使用默认管理器校验ID，见 [`SnowflakeManager::validate_id`]
+pub fn validate_id(id: u64) -> Result<SnowflakeIdInfo> {
+    get_default_manager()?.validate_id(id)
+}
+
 /// 生成字符串格式的ID
 pub fn generate_string_id() -> Result<String> {
     Ok(generate_id()?.to_string())
@@ -234,13 +1026,29 @@ pub fn generate_string_id() -> Result<String> {
 
 /// 从字符串解析并获取ID信息
 pub fn parse_string_id(id_str: &str) -> Result<SnowflakeIdInfo> {
-    let id = SnowflakeIdInfo::from_string(id_str)?;
-    parse_id(id)
+    let id: SnowflakeId = id_str.parse()?;
+    parse_id(id.0)
+}
+
+/// 使用默认配置计算时间范围内的ID上下界，见 [`SnowflakeManager::id_range_for`]
+pub fn id_range_for(start: DateTime<Utc>, end: DateTime<Utc>) -> Result<(u64, u64)> {
+    get_default_manager()?.id_range_for(start, end)
+}
+
+/// 生成 11 位 Base62 短ID
+pub fn generate_short_id() -> Result<String> {
+    get_default_manager()?.generate_base62_id()
+}
+
+/// 从 Base62 短ID解析ID信息
+pub fn parse_short_id(text: &str) -> Result<SnowflakeIdInfo> {
+    get_default_manager()?.parse_base62_id(text)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{EnvGuard, ManualClock};
     use std::collections::HashSet;
 
     #[test]
@@ -283,6 +1091,273 @@ mod tests {
         assert_eq!(unique_ids.len(), 100); // 所有ID应该是唯一的
     }
 
+    #[test]
+    fn test_init_default_after_use() {
+        generate_id().unwrap();
+        let err = init_default(SnowflakeConfig::new(9).unwrap()).unwrap_err();
+        assert!(matches!(
+            &err,
+            ClamberError::SnowflakeInitError { details } if details == "default manager already initialized"
+        ));
+        // 无效配置先于重复初始化报错
+        let err = init_default(SnowflakeConfig {
+            worker_id: 4096,
+            ..SnowflakeConfig::default()
+        })
+        .unwrap_err();
+        assert!(matches!(err, ClamberError::SnowflakeConfigError { .. }));
+    }
+
+    #[test]
+    fn test_concurrent_generation() {
+        let manager =
+            std::sync::Arc::new(SnowflakeManager::new(SnowflakeConfig::new(7).unwrap()).unwrap());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let manager = std::sync::Arc::clone(&manager);
+                std::thread::spawn(move || manager.generate_ids(10_000).unwrap())
+            })
+            .collect();
+
+        let mut unique_ids = HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            // 每个线程内严格递增
+            assert!(ids.windows(2).all(|w| w[0] < w[1]));
+            assert!(ids.iter().all(|&id| manager.parse_id(id).worker_id == 7));
+            unique_ids.extend(ids);
+        }
+        assert_eq!(unique_ids.len(), 80_000);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_generate_ids_async() {
+        let manager = Arc::new(SnowflakeManager::new(SnowflakeConfig::new(8).unwrap()).unwrap());
+        let small = manager.clone().generate_ids_async(10).await.unwrap();
+        assert_eq!(small.len(), 10);
+
+        // 超过单毫秒容量时在后台线程池中生成，仍与同一实例的其他ID保持递增
+        let large = manager.clone().generate_ids_async(10_000).await.unwrap();
+        assert_eq!(large.len(), 10_000);
+        assert!(large.windows(2).all(|w| w[0] < w[1]));
+        assert!(small[9] < large[0]);
+    }
+
+    const NOW: u64 = DEFAULT_EPOCH + 1_000;
+
+    fn manual_manager(policy: ClockDriftPolicy) -> (SnowflakeManager, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new(NOW));
+        let config = SnowflakeConfig::new(1).unwrap().clock_drift_policy(policy);
+        let manager = SnowflakeManager::with_clock(config, clock.clone()).unwrap();
+        (manager, clock)
+    }
+
+    #[test]
+    fn test_clock_drift_error() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        manager.generate_id().unwrap();
+        clock.set(NOW - 5);
+        match manager.generate_id() {
+            Err(ClamberError::SnowflakeClockMovedBackwards { drift_ms }) => assert_eq!(drift_ms, 5),
+            other => panic!("expected clock drift error, got {:?}", other),
+        }
+        clock.set(NOW);
+        assert!(manager.generate_id().is_ok());
+    }
+
+    #[test]
+    fn test_clock_drift_wait() {
+        let (manager, clock) =
+            manual_manager(ClockDriftPolicy::WaitUntilCaughtUp { max_wait_ms: 1_000 });
+        let manager = Arc::new(manager);
+        let before = manager.generate_id().unwrap();
+        clock.set(NOW - 5);
+        let waiting = {
+            let manager = Arc::clone(&manager);
+            std::thread::spawn(move || manager.generate_id())
+        };
+        // 时钟追上之前一直等待
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!waiting.is_finished());
+        clock.set(NOW + 1);
+        let after = waiting.join().unwrap().unwrap();
+        assert!(after > before);
+        assert_eq!(manager.parse_id(after).timestamp, 1_001);
+
+        // 回拨超过允许的等待时间
+        let (manager, clock) =
+            manual_manager(ClockDriftPolicy::WaitUntilCaughtUp { max_wait_ms: 2 });
+        manager.generate_id().unwrap();
+        clock.set(NOW - 5);
+        assert!(matches!(
+            manager.generate_id(),
+            Err(ClamberError::SnowflakeClockMovedBackwards { drift_ms: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_clock_drift_borrow() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::BorrowFromSequence);
+        let before = manager.generate_id().unwrap();
+        clock.set(NOW - 5);
+        let ids = manager.generate_ids(4_095).unwrap();
+        assert!(ids[0] > before);
+        assert!(
+            ids.iter()
+                .all(|&id| manager.parse_id(id).timestamp == 1_000)
+        );
+
+        // 上一个毫秒的序列号已用尽
+        assert!(matches!(
+            manager.generate_id(),
+            Err(ClamberError::SnowflakeClockMovedBackwards { drift_ms: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_frozen_clock_sequence_rollover() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        let manager = Arc::new(manager);
+        let ids = manager.generate_ids(4_096).unwrap();
+        for (sequence, &id) in ids.iter().enumerate() {
+            let info = manager.parse_id(id);
+            assert_eq!((info.timestamp, info.sequence as usize), (1_000, sequence));
+        }
+
+        // 第 4097 个ID等待时钟进入下一毫秒
+        let next = {
+            let manager = Arc::clone(&manager);
+            std::thread::spawn(move || manager.generate_id())
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!next.is_finished());
+        clock.advance(1);
+        let rolled = manager.parse_id(next.join().unwrap().unwrap());
+        assert_eq!((rolled.timestamp, rolled.sequence), (1_001, 0));
+
+        // 进入新的毫秒后序列号从 0 开始
+        clock.advance(5);
+        let info = manager.parse_id(manager.generate_id().unwrap());
+        assert_eq!((info.timestamp, info.sequence), (1_006, 0));
+    }
+
+    #[test]
+    fn test_metrics_counters() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        let metrics = Arc::new(AtomicMetrics::new());
+        let manager = Arc::new(manager.with_metrics(metrics.clone()));
+        manager.generate_ids(4_000).unwrap();
+        manager.generate_id().unwrap();
+        assert_eq!(metrics.generated_total(), 4_001);
+        assert_eq!(metrics.rollover_total(), 0);
+
+        // 剩余 95 个序列号，再要 100 个需进位到下一毫秒
+        let next = {
+            let manager = Arc::clone(&manager);
+            std::thread::spawn(move || manager.generate_ids(100))
+        };
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(!next.is_finished());
+        clock.advance(1);
+        next.join().unwrap().unwrap();
+        assert_eq!(metrics.generated_total(), 4_101);
+        assert_eq!(metrics.rollover_total(), 1);
+        assert!(metrics.max_wait_ms() >= 10, "{}", metrics.max_wait_ms());
+
+        clock.set(NOW - 5);
+        assert!(manager.generate_id().is_err());
+        assert_eq!(metrics.clock_drift_total(), 1);
+        assert_eq!(metrics.generated_total(), 4_101);
+    }
+
+    #[test]
+    fn test_recovers_from_poisoned_lock() {
+        let manager = SnowflakeManager::new(SnowflakeConfig::new(1).unwrap()).unwrap();
+        let backfill_at = Utc::now() - chrono::Duration::seconds(1);
+        let before = manager.generate_id_at(backfill_at).unwrap();
+
+        // 持有锁时 panic，锁被标记为中毒
+        std::thread::scope(|scope| {
+            let result = scope
+                .spawn(|| {
+                    let _guard = manager.backfill.lock().unwrap();
+                    panic!("panic while holding the backfill lock");
+                })
+                .join();
+            assert!(result.is_err());
+        });
+        assert!(manager.backfill.is_poisoned());
+
+        manager.health_check().unwrap();
+        let first = manager.generate_id().unwrap();
+        assert!(manager.generate_id().unwrap() > first);
+        // 锁内计数仍有效，继续分配同一毫秒的下一个序列号
+        let after = manager.generate_id_at(backfill_at).unwrap();
+        assert_eq!(after, before + 1);
+    }
+
+    #[test]
+    fn test_id_block() {
+        let manager = SnowflakeManager::new(SnowflakeConfig::new(3).unwrap()).unwrap();
+        let ids = manager.generate_ids(10_000).unwrap();
+        assert_eq!(ids.len(), 10_000);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+        let block = manager.generate_id_block(5_000).unwrap();
+        assert_eq!(block.len(), 5_000);
+        assert!(block.start() > ids[9_999]);
+        let block_ids: Vec<u64> = block.iter().collect();
+        assert_eq!(
+            (block_ids[0], block_ids[4_999]),
+            (block.start(), block.end())
+        );
+        assert!(manager.generate_id_block(0).is_err());
+        assert!(manager.generate_ids(0).unwrap().is_empty());
+
+        // 并发的单个ID不会落在其他调用方预留的段内
+        let manager = std::sync::Arc::new(manager);
+        let single = {
+            let manager = std::sync::Arc::clone(&manager);
+            std::thread::spawn(move || {
+                (0..20_000)
+                    .map(|_| manager.generate_id().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        };
+        let blocks: Vec<IdBlock> = (0..10)
+            .map(|_| manager.generate_id_block(2_000).unwrap())
+            .collect();
+        let single = single.join().unwrap();
+        for block in &blocks {
+            assert!(
+                single
+                    .iter()
+                    .all(|&id| id < block.start() || id > block.end())
+            );
+        }
+    }
+
+    #[test]
+    fn test_deterministic_sequence_rollover() {
+        use crate::testing::{DETERMINISTIC_TIMESTAMP, deterministic_snowflake};
+
+        let manager = deterministic_snowflake(5);
+        let ids = manager.generate_ids(4097).unwrap();
+
+        let last = manager.parse_id(ids[4095]);
+        assert_eq!(
+            (last.timestamp, last.worker_id, last.sequence),
+            (DETERMINISTIC_TIMESTAMP, 5, 4095)
+        );
+        let rolled = manager.parse_id(ids[4096]);
+        assert_eq!(
+            (rolled.timestamp, rolled.sequence),
+            (DETERMINISTIC_TIMESTAMP + 1, 0)
+        );
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
     #[test]
     fn test_id_parsing() {
         let config = SnowflakeConfig::new(1).unwrap();
@@ -295,6 +1370,296 @@ mod tests {
         assert!(info.timestamp > 0);
     }
 
+    #[test]
+    fn test_custom_layout_round_trip() {
+        for (timestamp_bits, worker_id_bits, sequence_bits) in
+            [(41, 10, 12), (43, 4, 16), (39, 12, 12)]
+        {
+            let config = SnowflakeConfig::new(1)
+                .unwrap()
+                .bit_layout(timestamp_bits, worker_id_bits, sequence_bits)
+                .unwrap()
+                .worker_id((1 << worker_id_bits) - 1)
+                .unwrap();
+            let manager =
+                SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(NOW))).unwrap();
+            let layout = manager.layout();
+            assert_eq!(layout.sequence_bits, sequence_bits);
+
+            let ids = manager.generate_ids(3).unwrap();
+            for (sequence, id) in ids.into_iter().enumerate() {
+                let info = manager.parse_id(id);
+                assert_eq!(info.timestamp, 1_000);
+                assert_eq!(info.worker_id, layout.max_worker_id());
+                assert_eq!(info.sequence as usize, sequence);
+                assert_eq!(info.layout, layout);
+            }
+        }
+
+        // 默认布局与旧的固定位移一致
+        let manager = manual_manager(ClockDriftPolicy::Error).0;
+        assert_eq!(manager.generate_id().unwrap(), (1_000 << 22) | (1 << 12));
+    }
+
+    #[test]
+    fn test_from_id_with_layout_validates() {
+        let layout = SnowflakeLayout::new(43, 4, 16).unwrap();
+        let id = (1_000 << 20) | (15 << 16) | 7;
+        let info = SnowflakeIdInfo::from_id_with_layout(id, layout).unwrap();
+        assert_eq!(info.timestamp, 1_000);
+        assert_eq!(info.worker_id, 15);
+        assert_eq!(info.sequence, 7);
+
+        // 公开字段可绕过 SnowflakeLayout::new 构造出非法布局
+        let invalid = SnowflakeLayout {
+            timestamp_bits: 60,
+            worker_id_bits: 10,
+            sequence_bits: 12,
+        };
+        assert!(matches!(
+            SnowflakeIdInfo::from_id_with_layout(id, invalid),
+            Err(ClamberError::SnowflakeConfigError { .. })
+        ));
+        let invalid: SnowflakeLayout =
+            serde_json::from_str(r#"{"timestamp_bits":41,"worker_id_bits":0,"sequence_bits":22}"#)
+                .unwrap();
+        assert!(SnowflakeIdInfo::from_id_with_layout(id, invalid).is_err());
+    }
+
+    #[test]
+    fn test_custom_layout_worker_id_range() {
+        let config = SnowflakeConfig::new(15)
+            .unwrap()
+            .bit_layout(43, 4, 16)
+            .unwrap();
+        assert!(matches!(
+            config.worker_id(16),
+            Err(ClamberError::SnowflakeConfigError { .. })
+        ));
+        // 缩小位数时已有的工作者ID也要校验
+        assert!(matches!(
+            SnowflakeConfig::new(100).unwrap().bit_layout(43, 4, 16),
+            Err(ClamberError::SnowflakeConfigError { .. })
+        ));
+        assert!(
+            SnowflakeConfig::new(1)
+                .unwrap()
+                .bit_layout(41, 10, 13)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_datacenter_round_trip() {
+        let config = SnowflakeConfig::with_datacenter(3, 17).unwrap();
+        assert_eq!(config.machine_id(), (3 << 5) | 17);
+        let manager =
+            SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(NOW))).unwrap();
+        assert_eq!(manager.datacenter_id(), Some(3));
+
+        let id = manager.generate_id().unwrap();
+        assert_eq!(id, (1_000 << 22) | (((3 << 5) | 17) << 12));
+        let info = manager.parse_id(id);
+        assert_eq!(info.datacenter_id, Some(3));
+        assert_eq!(info.worker_id, 17);
+        assert_eq!(info.timestamp, 1_000);
+
+        let block = manager.generate_id_block(2).unwrap();
+        assert_eq!(manager.parse_id(block.end()).datacenter_id, Some(3));
+
+        // 默认模式不拆分
+        let plain = manual_manager(ClockDriftPolicy::Error).0;
+        let info = plain.parse_id(plain.generate_id().unwrap());
+        assert_eq!(info.datacenter_id, None);
+        assert_eq!(info.worker_id, 1);
+    }
+
+    #[test]
+    fn test_datacenter_validation() {
+        assert!(SnowflakeConfig::with_datacenter(31, 31).is_ok());
+        for (datacenter_id, worker_id) in [(32, 0), (0, 32)] {
+            assert!(matches!(
+                SnowflakeConfig::with_datacenter(datacenter_id, worker_id),
+                Err(ClamberError::SnowflakeConfigError { .. })
+            ));
+        }
+        let config = SnowflakeConfig::with_datacenter(1, 1).unwrap();
+        assert!(config.clone().worker_id(100).is_err());
+        assert!(config.bit_layout(43, 4, 16).is_err());
+    }
+
+    #[test]
+    fn test_base62_ids() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        let mut ids = Vec::with_capacity(1000);
+        for i in 0..1000 {
+            if i % 300 == 0 {
+                clock.advance(1);
+            }
+            ids.push(manager.generate_base62_id().unwrap());
+        }
+        assert!(ids.iter().all(|id| id.len() == BASE62_LEN));
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+
+        let info = manager.parse_base62_id(&ids[0]).unwrap();
+        assert_eq!(info.worker_id, 1);
+        assert_eq!(info.to_base62(), ids[0]);
+
+        let max = SnowflakeIdInfo::from_id(u64::MAX >> 1);
+        assert_eq!(
+            SnowflakeIdInfo::from_base62(&max.to_base62()).unwrap(),
+            max.id
+        );
+        assert!(manager.parse_base62_id("not-base62!").is_err());
+
+        let short = generate_short_id().unwrap();
+        assert_eq!(parse_short_id(&short).unwrap().to_base62(), short);
+    }
+
+    #[test]
+    fn test_worker_strategy_resolution() {
+        let _env = EnvGuard::new().set("CLAMBER_TEST_SNOWFLAKE_WORKER", "77");
+        let config = SnowflakeConfig::with_worker_strategy(WorkerIdStrategy::FromEnv(
+            "CLAMBER_TEST_SNOWFLAKE_WORKER".to_string(),
+        ));
+        let manager =
+            SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(NOW))).unwrap();
+        assert_eq!(manager.worker_id(), 77);
+        assert_eq!(manager.get_config().worker_id, 77);
+        assert_eq!(
+            manager.parse_id(manager.generate_id().unwrap()).worker_id,
+            77
+        );
+
+        // 数据中心模式下工作者ID上限为 31
+        let config = SnowflakeConfig::with_datacenter(1, 0)
+            .unwrap()
+            .worker_strategy(WorkerIdStrategy::FromEnv(
+                "CLAMBER_TEST_SNOWFLAKE_WORKER".to_string(),
+            ));
+        assert!(SnowflakeManager::new(config).is_err());
+    }
+
+    #[test]
+    fn test_id_range_for() {
+        let at = |offset: u64| DateTime::from_timestamp_millis((NOW + offset) as i64).unwrap();
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        let (min, max) = manager.id_range_for(at(10), at(20)).unwrap();
+
+        let mut ids = Vec::new();
+        for offset in [5, 9, 10, 15, 20, 21, 30] {
+            clock.set(NOW + offset);
+            ids.push((offset, manager.generate_ids(3).unwrap()));
+        }
+        for (offset, batch) in ids {
+            let inside = (10..=20).contains(&offset);
+            assert!(batch.iter().all(|id| (min..=max).contains(id) == inside));
+        }
+        // 其他工作者在边界时刻生成的ID同样落在范围内
+        let config = SnowflakeConfig::new(1023).unwrap();
+        let other =
+            SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(NOW + 20))).unwrap();
+        assert!(other.generate_id().unwrap() <= max);
+
+        // 与自定义位布局一致
+        let config = SnowflakeConfig::new(0)
+            .unwrap()
+            .bit_layout(43, 4, 16)
+            .unwrap();
+        let custom =
+            SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(NOW + 10))).unwrap();
+        let (low, high) = custom.id_range_for(at(10), at(10)).unwrap();
+        assert_eq!(custom.generate_id().unwrap(), low);
+        assert_eq!(high - low, (1 << 20) - 1);
+
+        assert!(manager.id_range_for(at(20), at(10)).is_err());
+        let before_epoch = DateTime::from_timestamp_millis(DEFAULT_EPOCH as i64 - 1).unwrap();
+        assert!(manager.id_range_for(before_epoch, at(10)).is_err());
+        assert!(id_range_for(at(0), at(1)).is_ok());
+    }
+
+    #[test]
+    fn test_worker_registry() {
+        let registry = Arc::new(LocalRegistry::new());
+        let config = SnowflakeConfig::new(9).unwrap();
+        let manager =
+            SnowflakeManager::new_with_registry(config.clone(), registry.clone()).unwrap();
+        assert_eq!(manager.lease().unwrap().worker_id, 9);
+        manager.renew_lease().unwrap();
+
+        let err = SnowflakeManager::new_with_registry(config.clone(), registry.clone())
+            .err()
+            .unwrap();
+        assert!(matches!(err, ClamberError::SnowflakeConfigError { .. }));
+        assert!(err.to_string().contains("已被占用"));
+        // 不同工作者ID互不影响
+        let other = SnowflakeConfig::new(10).unwrap();
+        assert!(SnowflakeManager::new_with_registry(other, registry.clone()).is_ok());
+
+        drop(manager);
+        let again = SnowflakeManager::new_with_registry(config, registry.clone()).unwrap();
+        assert!(again.lease().is_some());
+        assert!(SnowflakeManager::default().unwrap().lease().is_none());
+    }
+
+    #[test]
+    fn test_generate_id_at() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        let live = manager.generate_id().unwrap();
+        let past = DateTime::from_timestamp_millis((NOW - 400) as i64).unwrap();
+
+        let mut ids: HashSet<u64> = manager
+            .generate_ids_at(past, 4000)
+            .unwrap()
+            .into_iter()
+            .collect();
+        for _ in 0..96 {
+            ids.insert(manager.generate_id_at(past).unwrap());
+        }
+        assert_eq!(ids.len(), 4096);
+        assert!(ids.iter().all(|&id| id < live));
+        assert!(matches!(
+            manager.generate_id_at(past),
+            Err(ClamberError::SnowflakeGenerateError { .. })
+        ));
+
+        let info = manager.parse_id(*ids.iter().max().unwrap());
+        assert_eq!(info.timestamp, 600);
+        assert_eq!(info.sequence, 4095);
+        assert_eq!(info.worker_id, 1);
+
+        // 实时生成器不受影响
+        clock.advance(1);
+        assert_eq!(manager.parse_id(manager.generate_id().unwrap()).sequence, 0);
+
+        let before_epoch = DateTime::from_timestamp_millis(DEFAULT_EPOCH as i64 - 1).unwrap();
+        assert!(manager.generate_id_at(before_epoch).is_err());
+        let future = DateTime::from_timestamp_millis((NOW + 10) as i64).unwrap();
+        assert!(manager.generate_id_at(future).is_err());
+    }
+
+    #[test]
+    fn test_clear_backfill() {
+        let (manager, _clock) = manual_manager(ClockDriftPolicy::Error);
+        for offset in 1..=100 {
+            let at = DateTime::from_timestamp_millis((NOW - offset) as i64).unwrap();
+            manager.generate_id_at(at).unwrap();
+        }
+        assert_eq!(manager.backfill.lock().unwrap().len(), 100);
+
+        manager.clear_backfill();
+        assert!(manager.backfill.lock().unwrap().is_empty());
+
+        // 清空后同一毫秒的序列号从 0 重新开始
+        let at = DateTime::from_timestamp_millis((NOW - 1) as i64).unwrap();
+        assert_eq!(
+            manager
+                .parse_id(manager.generate_id_at(at).unwrap())
+                .sequence,
+            0
+        );
+    }
+
     #[test]
     fn test_convenience_functions() {
         // 测试便利函数
@@ -314,7 +1679,69 @@ mod tests {
         assert!(!id.is_empty());
 
         let info = parse_string_id(&id).unwrap();
-        assert_eq!(info.to_string(), id);
+        assert_eq!(format!("{}", info), id);
+        #[allow(deprecated)]
+        {
+            assert_eq!(info.to_string(), id);
+            assert_eq!(SnowflakeIdInfo::from_string(&id).unwrap(), info.id);
+        }
+    }
+
+    #[test]
+    fn test_snowflake_id_string_forms() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Order {
+            #[serde(with = "serde_string")]
+            id: u64,
+            typed: SnowflakeId,
+        }
+
+        let order = Order {
+            id: u64::MAX,
+            typed: SnowflakeId(1 << 60),
+        };
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"18446744073709551615","typed":"1152921504606846976"}"#
+        );
+        assert_eq!(serde_json::from_str::<Order>(&json).unwrap(), order);
+        // 兼容数字形式
+        let legacy: Order = serde_json::from_str(r#"{"id":42,"typed":7}"#).unwrap();
+        assert_eq!((legacy.id, legacy.typed), (42, SnowflakeId(7)));
+        assert!(serde_json::from_str::<Order>(r#"{"id":"abc","typed":"1"}"#).is_err());
+
+        assert_eq!("123".parse::<SnowflakeId>().unwrap(), SnowflakeId(123));
+        assert_eq!(SnowflakeId(123).to_string(), "123");
+        for text in ["", "12a", "-1", "18446744073709551616"] {
+            assert!(matches!(
+                text.parse::<SnowflakeId>(),
+                Err(ClamberError::SnowflakeConfigError { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_id_info_ordering() {
+        let (manager, clock) = manual_manager(ClockDriftPolicy::Error);
+        let mut infos = Vec::new();
+        for _ in 0..5 {
+            infos.extend(
+                manager
+                    .generate_ids(100)
+                    .unwrap()
+                    .into_iter()
+                    .map(|id| manager.parse_id(id)),
+            );
+            clock.advance(1);
+        }
+        assert!(infos.windows(2).all(|w| w[0] < w[1]));
+
+        let mut shuffled = infos.clone();
+        shuffled.reverse();
+        shuffled.sort();
+        assert_eq!(shuffled, infos);
+        assert_eq!(infos.iter().max(), infos.last());
     }
 
     #[test]
@@ -322,11 +1749,89 @@ mod tests {
         let id = generate_id().unwrap();
         let info = parse_id(id).unwrap();
 
-        let gen_time = info.generation_time(None);
-        assert!(gen_time > 0);
+        let gen_time = info.generation_time();
+        assert!(gen_time > DEFAULT_EPOCH);
 
-        let time_str = info.generation_time_string(None);
+        let time_str = info.generation_time_string();
         assert!(!time_str.is_empty());
         assert!(time_str.contains("-")); // 应该包含日期格式
     }
+
+    #[test]
+    fn test_parse_id_uses_manager_epoch() {
+        let custom_epoch = 1_609_459_200_000; // 2021-01-01 00:00:00 UTC
+        let now = NOW;
+        let config = SnowflakeConfig::with_epoch(10, custom_epoch).unwrap();
+        let manager =
+            SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(now))).unwrap();
+
+        let info = manager.parse_id(manager.generate_id().unwrap());
+        assert_eq!(info.epoch, custom_epoch);
+        assert_eq!(info.timestamp, now - custom_epoch);
+        assert_eq!(info.generation_time(), now);
+        assert_eq!(
+            info.generation_datetime().unwrap().timestamp_millis() as u64,
+            now
+        );
+
+        // 序列化后保留纪元
+        let json = serde_json::to_string(&info).unwrap();
+        let restored: SnowflakeIdInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.generation_time(), now);
+
+        // 旧接口：显式纪元仍然覆盖记录的纪元
+        #[allow(deprecated)]
+        {
+            assert_eq!(info.generation_time_with_epoch(None), now);
+            assert_eq!(info.generation_time_with_epoch(Some(0)), now - custom_epoch);
+            assert_eq!(
+                info.generation_time_string_with_epoch(None),
+                info.generation_time_string()
+            );
+        }
+
+        // 不经管理器解析时按默认纪元计算
+        assert_eq!(SnowflakeIdInfo::from_id(info.id).epoch, DEFAULT_EPOCH);
+    }
+
+    #[test]
+    fn test_validate_id() {
+        let config = SnowflakeConfig::with_datacenter(3, 17)
+            .unwrap()
+            .allowed_worker_ids(16..=20)
+            .future_tolerance_ms(500);
+        let manager =
+            SnowflakeManager::with_clock(config, Arc::new(ManualClock::new(NOW))).unwrap();
+        // 按默认布局拼装：时间戳 << 22 | 数据中心 << 17 | 工作者 << 12 | 序列号
+        let id =
+            |timestamp: u64, worker_id: u64| (timestamp << 22) | (3 << 17) | (worker_id << 12) | 7;
+
+        let info = manager.validate_id(manager.generate_id().unwrap()).unwrap();
+        assert_eq!((info.datacenter_id, info.worker_id), (Some(3), 17));
+        for valid in [id(0, 16), id(1_000, 20), id(1_500, 18)] {
+            assert_eq!(manager.validate_id(valid).unwrap().id, valid);
+        }
+
+        let cases = [
+            (id(1_501, 17), "晚于当前时间 501ms"),
+            (id(1_000, 15), "工作者ID 15 不在允许范围 16-20 内"),
+            (id(1_000, 21), "工作者ID 21 不在允许范围 16-20 内"),
+            (id(1_000, 17) | 1 << 63, "最高位为1"),
+        ];
+        for (invalid, reason_part) in cases {
+            match manager.validate_id(invalid) {
+                Err(ClamberError::SnowflakeInvalidIdError { id, reason }) => {
+                    assert_eq!(id, invalid);
+                    assert!(reason.contains(reason_part), "{}", reason);
+                }
+                other => panic!("{:#x}: {:?}", invalid, other),
+            }
+        }
+
+        // 未限制工作者ID时只校验时间，默认容忍 1 秒
+        let manager = manual_manager(ClockDriftPolicy::Error).0;
+        assert!(manager.validate_id((2_000 << 22) | (1_023 << 12)).is_ok());
+        assert!(manager.validate_id(2_001 << 22).is_err());
+        assert!(validate_id(generate_id().unwrap()).is_ok());
+    }
 }
@@ -0,0 +1,159 @@
+//! 同一进程内的多工作者ID生成池
+//!
+//! 单个管理器每毫秒最多生成 `2^sequence_bits` 个ID（默认 4096），池内每个工作者ID各自持有序列号，
+//! 吞吐量随工作者数量线性增长。
+use super::{SnowflakeConfig, SnowflakeIdInfo, SnowflakeManager};
+use crate::error::{ClamberError, Result};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 持有多个 [`SnowflakeManager`] 的ID生成池，调用按轮询分配到各管理器
+///
+/// 池中的工作者ID需在整个部署范围内唯一，与单个管理器的要求相同。
+pub struct SnowflakePool {
+    managers: Vec<SnowflakeManager>,
+    next: AtomicUsize,
+}
+
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SnowflakePool>();
+};
+
+impl SnowflakePool {
+    /// 以 `base_config` 为模板，为每个工作者ID创建一个管理器
+    ///
+    /// 纪元、位布局、数据中心等设置取自 `base_config`，`worker_strategy` 被忽略；
+    /// `worker_ids` 为空、重复或超出布局范围时返回 `SnowflakeConfigError`。
+    pub fn new(base_config: SnowflakeConfig, worker_ids: Vec<u64>) -> Result<Self> {
+        if worker_ids.is_empty() {
+            return Err(ClamberError::SnowflakeConfigError {
+                details: "ID生成池至少需要一个工作者ID".to_string(),
+            });
+        }
+        let mut seen = HashSet::new();
+        let mut managers = Vec::with_capacity(worker_ids.len());
+        for worker_id in worker_ids {
+            if !seen.insert(worker_id) {
+                return Err(ClamberError::SnowflakeConfigError {
+                    details: format!("工作者ID {} 在池中重复", worker_id),
+                });
+            }
+            let config = SnowflakeConfig {
+                worker_strategy: None,
+                ..base_config.clone()
+            }
+            .worker_id(worker_id)?;
+            managers.push(SnowflakeManager::new(config)?);
+        }
+        Ok(Self {
+            managers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// 生成一个ID
+    ///
+    /// 同一管理器生成的ID单调递增，池内不同管理器之间的ID不保证顺序。
+    pub fn generate_id(&self) -> Result<u64> {
+        self.pick().generate_id()
+    }
+
+    /// 批量生成ID，按数量平均分配到各管理器
+    pub fn generate_ids(&self, count: usize) -> Result<Vec<u64>> {
+        let len = self.managers.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let mut ids = Vec::with_capacity(count);
+        for offset in 0..len.min(count) {
+            let share = count / len + usize::from(offset < count % len);
+            ids.extend(self.managers[(start + offset) % len].generate_ids(share)?);
+        }
+        Ok(ids)
+    }
+
+    /// 解析ID；池内管理器共用纪元与位布局
+    pub fn parse_id(&self, id: u64) -> SnowflakeIdInfo {
+        self.managers[0].parse_id(id)
+    }
+
+    /// 池中的工作者ID
+    pub fn worker_ids(&self) -> Vec<u64> {
+        self.managers
+            .iter()
+            .map(SnowflakeManager::worker_id)
+            .collect()
+    }
+
+    /// 池中的管理器
+    pub fn managers(&self) -> &[SnowflakeManager] {
+        &self.managers
+    }
+
+    fn pick(&self) -> &SnowflakeManager {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.managers.len();
+        &self.managers[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_validation() {
+        let base = SnowflakeConfig::default();
+        assert!(SnowflakePool::new(base.clone(), vec![]).is_err());
+
+        let err = SnowflakePool::new(base.clone(), vec![1, 2, 1])
+            .err()
+            .unwrap();
+        assert!(matches!(err, ClamberError::SnowflakeConfigError { .. }));
+        assert!(err.to_string().contains("重复"), "{}", err);
+
+        assert!(SnowflakePool::new(base.clone(), vec![1, 1024]).is_err());
+        let dc = SnowflakeConfig::with_datacenter(2, 0).unwrap();
+        assert!(SnowflakePool::new(dc.clone(), vec![31, 32]).is_err());
+
+        let pool = SnowflakePool::new(dc, vec![3, 4]).unwrap();
+        assert_eq!(pool.worker_ids(), [3, 4]);
+        let info = pool.parse_id(pool.generate_id().unwrap());
+        assert_eq!(info.datacenter_id, Some(2));
+        assert!([3, 4].contains(&info.worker_id));
+    }
+
+    #[test]
+    fn test_concurrent_uniqueness() {
+        let epoch = 1_609_459_200_000;
+        let pool = Arc::new(
+            SnowflakePool::new(SnowflakeConfig::default().epoch(epoch), vec![1, 2, 3, 4]).unwrap(),
+        );
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    let mut ids: Vec<u64> =
+                        (0..5_000).map(|_| pool.generate_id().unwrap()).collect();
+                    ids.extend(pool.generate_ids(3_001).unwrap());
+                    ids
+                })
+            })
+            .collect();
+
+        let mut unique = HashSet::new();
+        let mut workers = HashSet::new();
+        for handle in handles {
+            for id in handle.join().unwrap() {
+                assert!(unique.insert(id), "重复ID: {}", id);
+                let info = pool.parse_id(id);
+                assert_eq!(info.epoch, epoch);
+                workers.insert(info.worker_id);
+            }
+        }
+        assert_eq!(unique.len(), 8 * 8_001);
+        assert_eq!(workers, HashSet::from([1, 2, 3, 4]));
+        assert_eq!(pool.generate_ids(0).unwrap(), Vec::<u64>::new());
+        assert_eq!(pool.generate_ids(3).unwrap().len(), 3);
+    }
+}
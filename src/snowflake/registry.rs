@@ -0,0 +1,153 @@
+//! 工作者ID登记：启动时占用工作者ID，防止多个实例误用同一ID生成重复的ID。
+//!
+//! 跨进程的协调（Redis、etcd 等）由下游实现 [`WorkerIdRegistry`]，本模块只提供进程内的 [`LocalRegistry`]。
+use super::{Clock, SystemClock};
+use crate::error::{ClamberError, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 占用工作者ID的凭据，续约与释放时需原样交回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerLease {
+    /// 占用的工作者ID（数据中心模式下为合并后的机器ID）
+    pub worker_id: u64,
+    /// 本次占用的唯一标识，用于区分同一ID的前后两次占用
+    pub token: u64,
+}
+
+/// 工作者ID登记表
+pub trait WorkerIdRegistry {
+    /// 占用工作者ID，已被占用时返回 [`ClamberError::SnowflakeConfigError`]
+    fn try_claim(&self, worker_id: u64) -> Result<WorkerLease>;
+
+    /// 续约，租约已过期或已被他人占用时返回错误
+    fn renew(&self, lease: &WorkerLease) -> Result<()>;
+
+    /// 释放占用；[`super::SnowflakeManager`] 销毁时自动调用
+    fn release(&self, lease: &WorkerLease);
+}
+
+/// 进程内登记表，适用于同一进程内存在多个 [`super::SnowflakeManager`] 的场景
+pub struct LocalRegistry {
+    /// 工作者ID -> (token, 过期时间的 Unix 毫秒时间戳)
+    claims: Mutex<HashMap<u64, (u64, Option<u64>)>>,
+    next_token: AtomicU64,
+    ttl: Option<Duration>,
+    clock: Arc<dyn Clock + Send + Sync>,
+}
+
+impl Default for LocalRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalRegistry {
+    /// 占用不过期，直到释放
+    pub fn new() -> Self {
+        Self::build(None, Arc::new(SystemClock))
+    }
+
+    /// 占用在 `ttl` 后过期，需定期 [`WorkerIdRegistry::renew`]
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self::build(Some(ttl), Arc::new(SystemClock))
+    }
+
+    /// 使用指定时间来源判断过期
+    pub fn with_clock(ttl: Duration, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        Self::build(Some(ttl), clock)
+    }
+
+    fn build(ttl: Option<Duration>, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        Self {
+            claims: Mutex::new(HashMap::new()),
+            next_token: AtomicU64::new(1),
+            ttl,
+            clock,
+        }
+    }
+
+    fn expires_at(&self) -> Option<u64> {
+        self.ttl
+            .map(|ttl| self.clock.now_millis() + ttl.as_millis() as u64)
+    }
+
+    fn is_live(&self, expires_at: Option<u64>) -> bool {
+        expires_at.is_none_or(|expires_at| self.clock.now_millis() < expires_at)
+    }
+}
+
+impl WorkerIdRegistry for LocalRegistry {
+    fn try_claim(&self, worker_id: u64) -> Result<WorkerLease> {
+        let mut claims = self.claims.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&(_, expires_at)) = claims.get(&worker_id)
+            && self.is_live(expires_at)
+        {
+            return Err(ClamberError::SnowflakeConfigError {
+                details: format!(
+                    "工作者ID {} 已被占用，请检查是否有其他实例使用相同配置",
+                    worker_id
+                ),
+            });
+        }
+        let token = self.next_token.fetch_add(1, Ordering::Relaxed);
+        claims.insert(worker_id, (token, self.expires_at()));
+        Ok(WorkerLease { worker_id, token })
+    }
+
+    fn renew(&self, lease: &WorkerLease) -> Result<()> {
+        let mut claims = self.claims.lock().unwrap_or_else(|e| e.into_inner());
+        match claims.get(&lease.worker_id) {
+            Some(&(token, expires_at)) if token == lease.token && self.is_live(expires_at) => {
+                claims.insert(lease.worker_id, (token, self.expires_at()));
+                Ok(())
+            }
+            _ => Err(ClamberError::SnowflakeConfigError {
+                details: format!("工作者ID {} 的租约已失效", lease.worker_id),
+            }),
+        }
+    }
+
+    fn release(&self, lease: &WorkerLease) {
+        let mut claims = self.claims.lock().unwrap_or_else(|e| e.into_inner());
+        if claims
+            .get(&lease.worker_id)
+            .is_some_and(|&(token, _)| token == lease.token)
+        {
+            claims.remove(&lease.worker_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::ManualClock;
+
+    #[test]
+    fn test_lease_expiry_and_renew() {
+        let clock = Arc::new(ManualClock::new(0));
+        let registry = LocalRegistry::with_clock(Duration::from_millis(100), clock.clone());
+        let lease = registry.try_claim(7).unwrap();
+        assert!(registry.try_claim(7).is_err());
+
+        clock.advance(80);
+        registry.renew(&lease).unwrap();
+        // 续约后从当前时间重新计算
+        clock.advance(80);
+        assert!(registry.try_claim(7).is_err());
+
+        clock.advance(20);
+        assert!(registry.renew(&lease).is_err());
+        let next = registry.try_claim(7).unwrap();
+        assert_ne!(next.token, lease.token);
+
+        // 过期的旧租约不能释放新的占用
+        registry.release(&lease);
+        assert!(registry.try_claim(7).is_err());
+        registry.release(&next);
+        assert!(registry.try_claim(7).is_ok());
+    }
+}
@@ -0,0 +1,49 @@
+//! 将 u64 ID 序列化为 JSON 字符串，避免 JavaScript 超过 53 位的整数精度丢失。
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Order {
+//!     #[serde(with = "clamber_core::snowflake::serde_string")]
+//!     id: u64,
+//! }
+//!
+//! let json = serde_json::to_string(&Order { id: 1234567890123456789 }).unwrap();
+//! assert_eq!(json, r#"{"id":"1234567890123456789"}"#);
+//! ```
+//!
+//! 反序列化同时接受字符串与数字，便于兼容旧数据。
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+pub fn serialize<S: Serializer>(id: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(id)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    deserializer.deserialize_any(IdVisitor)
+}
+
+struct IdVisitor;
+
+impl Visitor<'_> for IdVisitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("十进制字符串或非负整数形式的ID")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(E::custom)
+    }
+}
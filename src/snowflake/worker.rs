@@ -0,0 +1,153 @@
+//! 工作者ID分配策略：在创建 [`super::SnowflakeManager`] 时解析出具体的工作者ID。
+use crate::error::{ClamberError, Result};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+
+/// 工作者ID的来源
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WorkerIdStrategy {
+    /// 固定值
+    Static(u64),
+    /// 从环境变量读取，变量缺失、无法解析或超出范围时报错
+    FromEnv(String),
+    /// 对主机名做哈希后取模
+    HostnameHash,
+    /// 取本机主要的非回环 IPv4 地址的低位
+    FromIpLastOctets,
+}
+
+impl WorkerIdStrategy {
+    /// 解析出工作者ID，`max_worker_id` 为当前位布局允许的最大值
+    pub fn resolve(&self, max_worker_id: u64) -> Result<u64> {
+        match self {
+            WorkerIdStrategy::Static(worker_id) => check_range(*worker_id, max_worker_id),
+            WorkerIdStrategy::FromEnv(name) => {
+                let value = std::env::var(name)
+                    .map_err(|_| invalid(format!("环境变量 {} 未设置", name)))?;
+                parse_worker_id(&value, max_worker_id)
+                    .map_err(|e| invalid(format!("环境变量 {} 无效: {}", name, e)))
+            }
+            WorkerIdStrategy::HostnameHash => {
+                let hostname = hostname().ok_or_else(|| invalid("无法获取主机名".to_string()))?;
+                Ok(hostname_hash(&hostname, max_worker_id))
+            }
+            WorkerIdStrategy::FromIpLastOctets => {
+                let ip = primary_ipv4()
+                    .ok_or_else(|| invalid("无法获取非回环的 IPv4 地址".to_string()))?;
+                Ok(ip_worker_id(ip, max_worker_id))
+            }
+        }
+    }
+}
+
+/// 主机名的 FNV-1a 哈希对 `max_worker_id + 1` 取模，跨进程、跨版本结果稳定
+pub fn hostname_hash(hostname: &str, max_worker_id: u64) -> u64 {
+    let hash = hostname
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    hash % (max_worker_id + 1)
+}
+
+/// 取 IPv4 地址的低位作为工作者ID（默认布局下为最后 10 位）
+pub fn ip_worker_id(ip: Ipv4Addr, max_worker_id: u64) -> u64 {
+    u32::from(ip) as u64 & max_worker_id
+}
+
+fn parse_worker_id(value: &str, max_worker_id: u64) -> std::result::Result<u64, String> {
+    let worker_id = value
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| format!("{:?} 不是数字 ({})", value, e))?;
+    if worker_id > max_worker_id {
+        return Err(format!("{} 超出范围 0-{}", worker_id, max_worker_id));
+    }
+    Ok(worker_id)
+}
+
+fn check_range(worker_id: u64, max_worker_id: u64) -> Result<u64> {
+    if worker_id > max_worker_id {
+        return Err(invalid(format!(
+            "工作者ID必须在0-{}范围内，当前值: {}",
+            max_worker_id, worker_id
+        )));
+    }
+    Ok(worker_id)
+}
+
+fn invalid(details: String) -> ClamberError {
+    ClamberError::SnowflakeConfigError { details }
+}
+
+/// 依次尝试环境变量与系统文件
+fn hostname() -> Option<String> {
+    let from_env = ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok());
+    let from_file = || {
+        ["/proc/sys/kernel/hostname", "/etc/hostname"]
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok())
+    };
+    from_env
+        .or_else(from_file)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// 通过连接（不发送数据的）UDP 套接字取得默认路由使用的本机地址
+fn primary_ipv4() -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80)).ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_unspecified() => Some(ip),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::EnvGuard;
+
+    #[test]
+    fn test_from_env() {
+        let strategy = WorkerIdStrategy::FromEnv("CLAMBER_TEST_WORKER_ID".to_string());
+
+        let env = EnvGuard::new().set("CLAMBER_TEST_WORKER_ID", " 42 ");
+        assert_eq!(strategy.resolve(1023).unwrap(), 42);
+
+        let env = env.set("CLAMBER_TEST_WORKER_ID", "1024");
+        assert!(matches!(
+            strategy.resolve(1023),
+            Err(ClamberError::SnowflakeConfigError { .. })
+        ));
+
+        let env = env.set("CLAMBER_TEST_WORKER_ID", "abc");
+        assert!(strategy.resolve(1023).is_err());
+
+        let _env = env.remove("CLAMBER_TEST_WORKER_ID");
+        assert!(strategy.resolve(1023).is_err());
+    }
+
+    #[test]
+    fn test_hostname_hash() {
+        let worker_id = hostname_hash("api-server-7", 1023);
+        assert_eq!(worker_id, hostname_hash("api-server-7", 1023));
+        assert!(worker_id <= 1023);
+        assert_ne!(worker_id, hostname_hash("api-server-8", 1023));
+        assert!(hostname_hash("api-server-7", 31) <= 31);
+    }
+
+    #[test]
+    fn test_ip_and_static() {
+        assert_eq!(ip_worker_id(Ipv4Addr::new(10, 0, 3, 7), 1023), (3 << 8) | 7);
+        assert_eq!(
+            ip_worker_id(Ipv4Addr::new(10, 0, 7, 255), 1023),
+            (3 << 8) | 255
+        );
+        assert_eq!(WorkerIdStrategy::Static(5).resolve(1023).unwrap(), 5);
+        assert!(WorkerIdStrategy::Static(32).resolve(31).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! 测试工具模块：为下游 crate 的测试提供固定时钟、手动时钟、确定性 ID、临时配置文件、环境变量与日志捕获等工具。
+//!
+//! 需启用 `testing` feature，一般只在 `[dev-dependencies]` 中开启：
+//!
+//! ```toml
+//! [dev-dependencies]
+//! clamber-core = { version = "*", features = ["testing"] }
+//! ```
+use std::ffi::OsString;
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "config")]
+use crate::config::ConfigFormat;
+#[cfg(feature = "snowflake")]
+use crate::snowflake::{Clock, SnowflakeConfig, SnowflakeManager};
+#[cfg(feature = "token")]
+use crate::token::{JwtConfig, JwtManager};
+#[cfg(feature = "token")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "config")]
+use std::path::PathBuf;
+#[cfg(feature = "snowflake")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "config")]
+use tempfile::TempDir;
+
+/// 确定性 Snowflake 生成器的起始时间戳（相对 epoch 的毫秒数）
+#[cfg(feature = "snowflake")]
+pub const DETERMINISTIC_TIMESTAMP: u64 = 1_000_000;
+
+/// 创建时钟固定在 `now` 的 JWT 管理器，签发与过期判断都基于该时间
+#[cfg(feature = "token")]
+pub fn fixed_clock_jwt(secret: &str, now: DateTime<Utc>) -> JwtManager {
+    JwtManager::new(JwtConfig::with_secret(secret)).with_clock(move || now)
+}
+
+/// 创建确定性的 Snowflake 生成器：时间戳从 [`DETERMINISTIC_TIMESTAMP`] 开始，序列号逐个递增
+///
+/// # Panics
+///
+/// `worker_id` 超出范围时 panic。
+#[cfg(feature = "snowflake")]
+pub fn deterministic_snowflake(worker_id: u64) -> SnowflakeManager {
+    let config = SnowflakeConfig::new(worker_id).expect("无效的 worker_id");
+    SnowflakeManager::deterministic(config, DETERMINISTIC_TIMESTAMP)
+        .expect("创建确定性 Snowflake 生成器失败")
+}
+
+/// 手动推进的时钟（Unix 毫秒），配合 [`SnowflakeManager::with_clock`] 精确控制时间戳
+///
+/// ```
+/// use clamber_core::snowflake::{SnowflakeConfig, SnowflakeManager};
+/// use clamber_core::testing::ManualClock;
+/// use std::sync::Arc;
+///
+/// let clock = Arc::new(ManualClock::new(1_800_000_000_000));
+/// let manager = SnowflakeManager::with_clock(SnowflakeConfig::default(), clock.clone()).unwrap();
+/// let first = manager.parse_id(manager.generate_id().unwrap());
+/// clock.advance(10);
+/// let second = manager.parse_id(manager.generate_id().unwrap());
+/// assert_eq!(second.timestamp, first.timestamp + 10);
+/// ```
+#[cfg(feature = "snowflake")]
+#[derive(Debug)]
+pub struct ManualClock {
+    now: AtomicU64,
+}
+
+#[cfg(feature = "snowflake")]
+impl ManualClock {
+    pub fn new(now_millis: u64) -> Self {
+        Self {
+            now: AtomicU64::new(now_millis),
+        }
+    }
+
+    /// 设置当前时间，可以早于之前的值以模拟时钟回拨
+    pub fn set(&self, now_millis: u64) {
+        self.now.store(now_millis, Ordering::SeqCst);
+    }
+
+    /// 前进 `millis` 毫秒
+    pub fn advance(&self, millis: u64) {
+        self.now.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+#[cfg(feature = "snowflake")]
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+/// 将配置内容写入临时目录下的 `config.{ext}` 文件
+///
+/// 返回的 [`TempDir`] 被 drop 时文件随之删除，需在测试期间保持存活。
+///
+/// # Panics
+///
+/// 创建临时目录或写入文件失败时 panic。
+#[cfg(feature = "config")]
+pub fn write_temp_config(format: ConfigFormat, content: &str) -> (TempDir, PathBuf) {
+    let ext = match format {
+        ConfigFormat::Yaml => "yaml",
+        ConfigFormat::Toml => "toml",
+        ConfigFormat::Json => "json",
+        #[cfg(feature = "ini")]
+        ConfigFormat::Ini => "ini",
+        #[cfg(feature = "ron")]
+        ConfigFormat::Ron => "ron",
+    };
+    let dir = tempfile::tempdir().expect("创建临时目录失败");
+    let path = dir.path().join(format!("config.{}", ext));
+    std::fs::write(&path, content).expect("写入临时配置文件失败");
+    (dir, path)
+}
+
+/// 串行化所有 EnvGuard，避免并行测试互相覆盖环境变量
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// 临时修改环境变量，drop 时恢复原值
+///
+/// 存活期间持有全局锁，同一时刻只有一个 EnvGuard 生效；不要在同一线程内同时创建两个。
+///
+/// ```
+/// use clamber_core::testing::EnvGuard;
+///
+/// let _env = EnvGuard::new().set("APP_PORT", "8080").remove("APP_DEBUG");
+/// assert_eq!(std::env::var("APP_PORT").unwrap(), "8080");
+/// ```
+pub struct EnvGuard {
+    saved: Vec<(String, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl EnvGuard {
+    /// 获取全局锁并创建空的 guard
+    pub fn new() -> Self {
+        Self {
+            saved: Vec::new(),
+            _lock: ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner()),
+        }
+    }
+
+    /// 设置环境变量
+    pub fn set(mut self, key: &str, value: &str) -> Self {
+        self.save(key);
+        // SAFETY: 修改期间持有 ENV_LOCK，其他 EnvGuard 不会并发读写
+        unsafe { std::env::set_var(key, value) };
+        self
+    }
+
+    /// 删除环境变量
+    pub fn remove(mut self, key: &str) -> Self {
+        self.save(key);
+        // SAFETY: 同 set
+        unsafe { std::env::remove_var(key) };
+        self
+    }
+
+    fn save(&mut self, key: &str) {
+        if !self.saved.iter().any(|(k, _)| k == key) {
+            self.saved.push((key.to_string(), std::env::var_os(key)));
+        }
+    }
+}
+
+impl Default for EnvGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (key, value) in self.saved.drain(..).rev() {
+            // SAFETY: 锁在字段 drop 之前仍然持有
+            unsafe {
+                match value {
+                    Some(v) => std::env::set_var(&key, v),
+                    None => std::env::remove_var(&key),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tracing-logs")]
+pub use capture::CapturingLogger;
+
+#[cfg(feature = "tracing-logs")]
+mod capture {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+    use tracing::subscriber::DefaultGuard;
+    use tracing_subscriber::fmt::MakeWriter;
+
+    /// 捕获日志输出到内存，供断言使用
+    ///
+    /// 可直接调用 [`CapturingLogger::set_default`] 安装一个纯文本订阅者，
+    /// 也可作为 `MakeWriter` 交给自定义的 layer；克隆共享同一块缓冲区。
+    #[derive(Clone, Default)]
+    pub struct CapturingLogger(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturingLogger {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// 在当前线程安装捕获所有级别的订阅者（无颜色、无时间戳），guard drop 后恢复
+        pub fn set_default(&self) -> DefaultGuard {
+            let subscriber = tracing_subscriber::fmt()
+                .with_writer(self.clone())
+                .with_ansi(false)
+                .without_time()
+                .with_max_level(tracing::Level::TRACE)
+                .finish();
+            tracing::subscriber::set_default(subscriber)
+        }
+
+        /// 已捕获的全部输出
+        pub fn contents(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+        }
+
+        /// 按行拆分的输出
+        pub fn lines(&self) -> Vec<String> {
+            self.contents().lines().map(str::to_string).collect()
+        }
+
+        /// 输出中是否包含指定文本
+        pub fn contains(&self, needle: &str) -> bool {
+            self.contents().contains(needle)
+        }
+    }
+
+    impl io::Write for CapturingLogger {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturingLogger {
+        type Writer = CapturingLogger;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_guard_restores() {
+        let key = "CLAMBER_TESTING_ENV_GUARD";
+        {
+            let _env = EnvGuard::new().set(key, "outer");
+        }
+        assert!(std::env::var_os(key).is_none());
+
+        // SAFETY: 仅本测试使用该变量
+        unsafe { std::env::set_var(key, "original") };
+        {
+            let _env = EnvGuard::new().set(key, "first").set(key, "second");
+            assert_eq!(std::env::var(key).unwrap(), "second");
+        }
+        assert_eq!(std::env::var(key).unwrap(), "original");
+
+        {
+            let _env = EnvGuard::new().remove(key);
+            assert!(std::env::var_os(key).is_none());
+        }
+        assert_eq!(std::env::var(key).unwrap(), "original");
+        unsafe { std::env::remove_var(key) };
+    }
+
+    #[cfg(feature = "token")]
+    #[test]
+    fn test_fixed_clock_jwt() {
+        use chrono::TimeZone;
+
+        let now = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let manager = fixed_clock_jwt("secret", now);
+        let token = manager.generate_token(&"payload").unwrap();
+
+        let claims = crate::token::decode_unverified(&token).unwrap().claims;
+        let expected = (now + JwtConfig::default().expire).timestamp();
+        assert_eq!(claims["exp"], expected);
+        assert_eq!(claims["createAt"], now.timestamp().to_string());
+
+        // 相同时钟下 token 有效；真实时钟下已过期
+        assert!(manager.is_valid_token(&token));
+        let real = JwtManager::new(JwtConfig::with_secret("secret"));
+        assert!(!real.is_valid_token(&token));
+    }
+
+    #[cfg(feature = "snowflake")]
+    #[test]
+    fn test_deterministic_snowflake() {
+        let a = deterministic_snowflake(3);
+        let b = deterministic_snowflake(3);
+        let ids_a = a.generate_ids(5).unwrap();
+        assert_eq!(ids_a, b.generate_ids(5).unwrap());
+
+        for (seq, id) in ids_a.iter().enumerate() {
+            let info = a.parse_id(*id);
+            assert_eq!(info.timestamp, DETERMINISTIC_TIMESTAMP);
+            assert_eq!(info.worker_id, 3);
+            assert_eq!(info.sequence, seq as u16);
+        }
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_write_temp_config() {
+        let (dir, path) = write_temp_config(ConfigFormat::Toml, "name = \"svc\"\n");
+        assert_eq!(path.extension().unwrap(), "toml");
+        assert!(path.starts_with(dir.path()));
+
+        let value: serde_json::Value = crate::config::ConfigBuilder::new()
+            .add_file(&path, None)
+            .build()
+            .unwrap();
+        assert_eq!(value["name"], "svc");
+
+        drop(dir);
+        assert!(!path.exists());
+    }
+
+    #[cfg(feature = "tracing-logs")]
+    #[test]
+    fn test_capturing_logger() {
+        let logger = CapturingLogger::new();
+        {
+            let _guard = logger.set_default();
+            tracing::debug!(user_id = 7, "captured");
+            tracing::warn!("second");
+        }
+        tracing::info!("not captured");
+
+        let lines = logger.lines();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("DEBUG") && lines[0].contains("user_id=7"));
+        assert!(logger.contains("second"));
+        assert!(!logger.contains("not captured"));
+    }
+}
@@ -0,0 +1,211 @@
+//! axum / tower 集成：[`JwtLayer`] 校验 `Authorization: Bearer <token>`，通过后把 payload 放入请求扩展，
+//! 处理函数用 [`Claims`] 提取。
+//!
+//! ```no_run
+//! use axum::{Router, routing::get};
+//! use clamber_core::token::axum::{Claims, JwtLayer};
+//! use clamber_core::{JwtConfig, JwtManager};
+//! use serde::Deserialize;
+//!
+//! #[derive(Clone, Deserialize)]
+//! struct User {
+//!     id: String,
+//! }
+//!
+//! async fn me(Claims(user): Claims<User>) -> String {
+//!     user.id
+//! }
+//!
+//! let manager = JwtManager::new(JwtConfig::new("secret", 7));
+//! let app: Router = Router::new()
+//!     .route("/me", get(me))
+//!     .route_layer(JwtLayer::<User>::new(manager))
+//!     .route("/health", get(|| async { "ok" }));
+//! ```
+use super::{JwtManager, TokenType};
+use crate::error::ClamberError;
+use ::axum::extract::FromRequestParts;
+use ::axum::http::{HeaderMap, HeaderValue, Request, StatusCode, header, request::Parts};
+use ::axum::response::{IntoResponse, Response};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// 校验 Bearer token 的 tower 中间件，payload 类型为 `T`
+///
+/// 校验失败时直接返回错误响应（JSON 错误体，含错误码），不再调用内层服务：
+///
+/// | 情况 | 状态码 |
+/// |------|--------|
+/// | 缺少或格式错误的 `Authorization` 头（JWT-001） | 401 |
+/// | 过期（JWT-003）、签名无效、已吊销等 | 401 |
+/// | 受众不符（JWT-008）、token 类型不符（JWT-010） | 403 |
+/// | 密钥配置错误 | 500 |
+///
+/// 只保护部分路由时使用 `Router::route_layer`，其后添加的路由不受影响。
+pub struct JwtLayer<T> {
+    manager: Arc<JwtManager>,
+    token_type: Option<TokenType>,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T> JwtLayer<T> {
+    pub fn new(manager: JwtManager) -> Self {
+        Self::from_arc(Arc::new(manager))
+    }
+
+    /// 与其他组件共享同一个管理器
+    pub fn from_arc(manager: Arc<JwtManager>) -> Self {
+        Self {
+            manager,
+            token_type: None,
+            _payload: PhantomData,
+        }
+    }
+
+    /// 要求 `token_type` 声明一致，如只接受 access token
+    pub fn require_token_type(mut self, token_type: TokenType) -> Self {
+        self.token_type = Some(token_type);
+        self
+    }
+}
+
+impl<T> Clone for JwtLayer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            manager: self.manager.clone(),
+            token_type: self.token_type,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<S, T> Layer<S> for JwtLayer<T> {
+    type Service = JwtService<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JwtService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// [`JwtLayer`] 生成的服务
+pub struct JwtService<S, T> {
+    inner: S,
+    layer: JwtLayer<T>,
+}
+
+impl<S: Clone, T> Clone for JwtService<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            layer: self.layer.clone(),
+        }
+    }
+}
+
+impl<S, T, B> Service<Request<B>> for JwtService<S, T>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    T: DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        match self.layer.authenticate(request.headers()) {
+            Ok(payload) => {
+                request.extensions_mut().insert(Claims(payload));
+                Box::pin(self.inner.call(request))
+            }
+            Err(err) => {
+                let response = rejection(err);
+                Box::pin(async move { Ok(response) })
+            }
+        }
+    }
+}
+
+impl<T: DeserializeOwned> JwtLayer<T> {
+    fn authenticate(&self, headers: &HeaderMap) -> crate::Result<T> {
+        let token = bearer_token(headers).ok_or_else(|| ClamberError::JwtError {
+            message: "缺少 Authorization: Bearer 请求头".to_string(),
+        })?;
+        match self.token_type {
+            Some(token_type) => self.manager.verify_token_of_type(token, token_type),
+            None => self.manager.verify_token(token),
+        }
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+fn rejection(err: ClamberError) -> Response {
+    let status = match err.root_cause() {
+        ClamberError::JwtAudienceMismatchError { .. }
+        | ClamberError::JwtTokenTypeMismatchError { .. } => StatusCode::FORBIDDEN,
+        _ => StatusCode::from_u16(err.http_status()).unwrap_or(StatusCode::UNAUTHORIZED),
+    };
+    let mut response = (status, ::axum::Json(err.to_error_response())).into_response();
+    if status == StatusCode::UNAUTHORIZED {
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    }
+    response
+}
+
+/// 经 [`JwtLayer`] 校验后的 payload，可作为 axum 处理函数的参数
+///
+/// 路由未经过对应类型的 `JwtLayer<T>` 时提取失败，返回 401。
+#[derive(Debug, Clone)]
+pub struct Claims<T>(pub T);
+
+impl<T> Deref for Claims<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Claims<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<S, T> FromRequestParts<S> for Claims<T>
+where
+    S: Send + Sync,
+    T: Clone + Send + Sync + 'static,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts.extensions.get::<Claims<T>>().cloned().ok_or_else(|| {
+            rejection(ClamberError::JwtError {
+                message: "请求未经过 JwtLayer 认证".to_string(),
+            })
+        })
+    }
+}
@@ -0,0 +1,282 @@
+//! claims 读写：兼容默认的嵌套模式（payload 为 JSON 字符串、时间戳为字符串）与平铺模式。
+//!
+//! `exp` 在两种模式下均写为数字；读取时间戳时仍兼容旧 token 的字符串写法。
+use super::{JwtConfig, deflate};
+use crate::error::{ClamberError, Result, ResultExt};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+pub(super) type Claims = BTreeMap<String, Value>;
+
+/// 平铺模式下由本库写入的声明，解析 payload 时排除
+const REGISTERED_CLAIMS: &[&str] = &[
+    "exp",
+    "iat",
+    "nbf",
+    "iss",
+    "aud",
+    "sub",
+    "jti",
+    "token_type",
+];
+
+/// 嵌套模式额外写入的声明
+const NESTED_CLAIMS: &[&str] = &["payload", "payload_enc", "createAt"];
+
+/// 压缩后的 payload 在 `payload_enc` 声明中的编码名
+const DEFLATE_ENCODING: &str = "deflate";
+/// 解压 payload 的上限，防止伪造的压缩数据占用过多内存
+const MAX_INFLATED_BYTES: usize = 1024 * 1024;
+
+/// 是否为本库写入的保留声明，自定义声明不能占用
+pub(super) fn is_reserved(name: &str) -> bool {
+    REGISTERED_CLAIMS.contains(&name) || NESTED_CLAIMS.contains(&name)
+}
+
+/// 保留声明之外的其余声明；非字符串值以 JSON 文本返回
+pub(super) fn custom_claims(claims: &Claims) -> BTreeMap<String, String> {
+    claims
+        .iter()
+        .filter(|(name, _)| !is_reserved(name))
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(value) => value.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// 写入 payload 与签发时间
+///
+/// 嵌套模式：`payload` 为 JSON 字符串（开启压缩时为 deflate + base64url，并写入 `payload_enc`），签发时间为 `createAt`；
+/// 平铺模式：payload 字段直接作为顶层声明，签发时间为 `iat`。写入的 payload 超过 `max_payload_bytes` 时返回 `JwtError`。
+pub(super) fn insert_payload<T>(
+    claims: &mut Claims,
+    payload: &T,
+    now: i64,
+    config: &JwtConfig,
+) -> Result<()>
+where
+    T: Serialize,
+{
+    let flat = config.flat_claims;
+    if !flat {
+        let json = serde_json::to_string(payload)?;
+        if config.compress_payload {
+            let encoded = URL_SAFE_NO_PAD.encode(deflate::compress(json.as_bytes()));
+            check_size(encoded.len(), config)?;
+            claims.insert("payload".to_string(), Value::String(encoded));
+            claims.insert("payload_enc".to_string(), DEFLATE_ENCODING.into());
+        } else {
+            check_size(json.len(), config)?;
+            claims.insert("payload".to_string(), Value::String(json));
+        }
+        claims.insert("createAt".to_string(), timestamp_value(now, flat));
+        return Ok(());
+    }
+
+    let value = serde_json::to_value(payload)?;
+    check_size(serde_json::to_vec(&value)?.len(), config)?;
+    let fields = match value {
+        Value::Object(fields) => fields,
+        other => {
+            return Err(ClamberError::SerializationError {
+                details: format!(
+                    "flat_claims 模式要求 payload 序列化为 JSON 对象，实际为 {}",
+                    other
+                ),
+                source: None,
+            });
+        }
+    };
+    for (key, value) in fields {
+        if REGISTERED_CLAIMS.contains(&key.as_str()) {
+            return Err(ClamberError::SerializationError {
+                details: format!("payload 字段 {} 与保留声明冲突", key),
+                source: None,
+            });
+        }
+        claims.insert(key, value);
+    }
+    claims.insert("iat".to_string(), timestamp_value(now, flat));
+    Ok(())
+}
+
+fn check_size(actual: usize, config: &JwtConfig) -> Result<()> {
+    match config.max_payload_bytes {
+        Some(allowed) if actual > allowed => Err(ClamberError::JwtError {
+            message: format!(
+                "payload 大小 {} 字节超过上限 {} 字节，请精简 payload 或开启 compress_payload",
+                actual, allowed
+            ),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// [`super::JwtManager::is_valid_token`] 只解析的声明，其余声明（包括 payload）被跳过
+#[derive(Deserialize)]
+pub(super) struct ValidityClaims {
+    pub(super) exp: Option<Timestamp>,
+    pub(super) nbf: Option<Timestamp>,
+    pub(super) iss: Option<String>,
+    pub(super) aud: Option<String>,
+    pub(super) jti: Option<String>,
+    #[serde(rename = "createAt")]
+    pub(super) create_at: Option<Timestamp>,
+    pub(super) iat: Option<Timestamp>,
+}
+
+impl ValidityClaims {
+    /// 签发时间：嵌套模式为 `createAt`，平铺模式为 `iat`
+    pub(super) fn created_at(&self, flat: bool) -> Option<i64> {
+        let timestamp = if flat { &self.iat } else { &self.create_at };
+        timestamp.as_ref().map(|timestamp| timestamp.0)
+    }
+}
+
+/// 时间戳声明，接受数字与旧格式的数字字符串，规则同 [`timestamp`]
+pub(super) struct Timestamp(pub(super) i64);
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct TimestampVisitor;
+
+        impl Visitor<'_> for TimestampVisitor {
+            type Value = Timestamp;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("整数时间戳或数字字符串")
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> std::result::Result<Timestamp, E> {
+                Ok(Timestamp(value))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> std::result::Result<Timestamp, E> {
+                i64::try_from(value)
+                    .map(Timestamp)
+                    .map_err(|_| E::custom("时间戳超出范围"))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Timestamp, E> {
+                value.parse().map(Timestamp).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+/// 时间戳声明的值：嵌套模式沿用字符串，平铺模式使用数字（与其他 JWT 库一致）
+pub(super) fn timestamp_value(timestamp: i64, flat: bool) -> Value {
+    if flat {
+        Value::from(timestamp)
+    } else {
+        Value::String(timestamp.to_string())
+    }
+}
+
+/// 取出 payload 的 JSON 值
+pub(super) fn payload_value(claims: &Claims, flat: bool) -> Result<Value> {
+    if flat {
+        let fields: Map<String, Value> = claims
+            .iter()
+            .filter(|(key, _)| !REGISTERED_CLAIMS.contains(&key.as_str()))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        return Ok(Value::Object(fields));
+    }
+
+    let payload = required_str(claims, "payload")?;
+    let json = match claims.get("payload_enc") {
+        None => Cow::Borrowed(payload),
+        Some(Value::String(encoding)) if encoding == DEFLATE_ENCODING => {
+            Cow::Owned(inflate_payload(payload)?)
+        }
+        Some(other) => {
+            return Err(ClamberError::JwtError {
+                message: format!("不支持的 payload 编码: {}", other),
+            });
+        }
+    };
+    serde_json::from_str(&json).map_err(|e| ClamberError::DeserializationError {
+        details: e.to_string(),
+        source: Some(e.into()),
+    })
+}
+
+fn inflate_payload(encoded: &str) -> Result<String> {
+    let invalid = |details: String| ClamberError::DeserializationError {
+        details: format!("无法解压 payload: {}", details),
+        source: None,
+    };
+    let compressed = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| invalid(e.to_string()))?;
+    let bytes = deflate::decompress(&compressed, MAX_INFLATED_BYTES).map_err(invalid)?;
+    String::from_utf8(bytes).map_err(|e| invalid(e.to_string()))
+}
+
+pub(super) fn decode_payload<T>(claims: &Claims, flat: bool) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    payload_value(claims, flat)
+        .and_then(|value| {
+            serde_json::from_value::<T>(value).map_err(|e| ClamberError::DeserializationError {
+                details: e.to_string(),
+                source: Some(e.into()),
+            })
+        })
+        .context("解析 token payload")
+}
+
+pub(super) fn required_str<'a>(claims: &'a Claims, field: &str) -> Result<&'a str> {
+    match required(claims, field)? {
+        Value::String(value) => Ok(value),
+        other => Err(ClamberError::JwtError {
+            message: format!("声明 {} 不是字符串: {}", field, other),
+        }),
+    }
+}
+
+pub(super) fn required_timestamp(claims: &Claims, field: &str) -> Result<i64> {
+    timestamp(claims, field)?.ok_or_else(|| ClamberError::JwtMissingFieldError {
+        field: field.to_string(),
+    })
+}
+
+/// 读取时间戳声明，兼容字符串与数字两种写法
+pub(super) fn timestamp(claims: &Claims, field: &str) -> Result<Option<i64>> {
+    match claims.get(field) {
+        None => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.parse::<i64>()?)),
+        Some(Value::Number(value)) => {
+            value
+                .as_i64()
+                .map(Some)
+                .ok_or_else(|| ClamberError::JwtError {
+                    message: format!("声明 {} 不是有效的时间戳: {}", field, value),
+                })
+        }
+        Some(other) => Err(ClamberError::JwtError {
+            message: format!("声明 {} 不是有效的时间戳: {}", field, other),
+        }),
+    }
+}
+
+fn required<'a>(claims: &'a Claims, field: &str) -> Result<&'a Value> {
+    claims
+        .get(field)
+        .ok_or_else(|| ClamberError::JwtMissingFieldError {
+            field: field.to_string(),
+        })
+}
@@ -0,0 +1,342 @@
+//! Cookie 辅助：把 token 写成 `Set-Cookie` 响应头的值，从 `Cookie` 请求头中取出 token，不依赖具体 Web 框架。
+use crate::error::{ClamberError, Result};
+use chrono::{DateTime, Duration, Utc};
+
+/// `SameSite` 属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SameSite {
+    /// 仅同站请求携带
+    Strict,
+    /// 同站请求与跨站的顶级导航携带
+    #[default]
+    Lax,
+    /// 所有请求都携带，浏览器要求同时设置 `Secure`
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+/// token cookie 的属性，见 [`token_to_set_cookie`]
+///
+/// [`CookieOptions::new`] 默认 `Path=/`、`Secure`、`HttpOnly`、`SameSite=Lax`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CookieOptions {
+    /// cookie 名称
+    pub name: String,
+    /// `Domain`，`None` 时只发送给当前主机
+    pub domain: Option<String>,
+    /// `Path`
+    pub path: Option<String>,
+    /// 仅通过 HTTPS 发送
+    pub secure: bool,
+    /// 禁止脚本读取
+    pub http_only: bool,
+    /// `SameSite`，`None` 时不写入，由浏览器决定默认值
+    pub same_site: Option<SameSite>,
+}
+
+impl CookieOptions {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            domain: None,
+            path: Some("/".to_string()),
+            secure: true,
+            http_only: true,
+            same_site: Some(SameSite::Lax),
+        }
+    }
+
+    /// 设置 `Domain`
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// 设置 `Path`，`None` 表示不写入
+    pub fn path(mut self, path: Option<String>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// 设置是否仅通过 HTTPS 发送
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// 设置是否禁止脚本读取
+    pub fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+
+    /// 设置 `SameSite`，`None` 表示不写入
+    pub fn same_site(mut self, same_site: Option<SameSite>) -> Self {
+        self.same_site = same_site;
+        self
+    }
+}
+
+/// 生成 `Set-Cookie` 响应头的值，同时写入 `Max-Age` 与 `Expires`（兼容不支持 `Max-Age` 的旧客户端）
+///
+/// 属性按 RFC 6265 的顺序排列：`Expires`、`Max-Age`、`Domain`、`Path`、`Secure`、`HttpOnly`、`SameSite`。
+/// `max_age` 不为正时写入 `Max-Age=0`，浏览器立即删除该 cookie，可用于登出。
+/// 名称不是合法的 cookie 名、token 含有 cookie 值不允许的字符（或 `%`）、属性含有 `;` 或控制字符、
+/// `SameSite=None` 未同时设置 `Secure` 时返回 [`ClamberError::JwtError`]。
+pub fn token_to_set_cookie(token: &str, opts: &CookieOptions, max_age: Duration) -> Result<String> {
+    set_cookie_at(token, opts, max_age, Utc::now())
+}
+
+fn set_cookie_at(
+    token: &str,
+    opts: &CookieOptions,
+    max_age: Duration,
+    now: DateTime<Utc>,
+) -> Result<String> {
+    if opts.name.is_empty() || !opts.name.bytes().all(is_token_char) {
+        return Err(cookie_error(format!("无效的 cookie 名称: {:?}", opts.name)));
+    }
+    // JWT 只含 base64url 字符与 `.`，不需要也不做百分号编码
+    if let Some(c) = token.chars().find(|&c| !is_cookie_octet(c) || c == '%') {
+        return Err(cookie_error(format!(
+            "token 含有 cookie 值不允许的字符 {:?}",
+            c
+        )));
+    }
+    if opts.same_site == Some(SameSite::None) && !opts.secure {
+        return Err(cookie_error(
+            "SameSite=None 需要同时设置 Secure".to_string(),
+        ));
+    }
+
+    let max_age = max_age.num_seconds().max(0);
+    let expires = now + Duration::seconds(max_age);
+    let mut cookie = format!(
+        "{}={}; Expires={}; Max-Age={}",
+        opts.name,
+        token,
+        expires.format("%a, %d %b %Y %H:%M:%S GMT"),
+        max_age
+    );
+    if let Some(domain) = &opts.domain {
+        cookie.push_str("; Domain=");
+        cookie.push_str(attribute_value("Domain", domain)?);
+    }
+    if let Some(path) = &opts.path {
+        cookie.push_str("; Path=");
+        cookie.push_str(attribute_value("Path", path)?);
+    }
+    if opts.secure {
+        cookie.push_str("; Secure");
+    }
+    if opts.http_only {
+        cookie.push_str("; HttpOnly");
+    }
+    if let Some(same_site) = opts.same_site {
+        cookie.push_str("; SameSite=");
+        cookie.push_str(same_site.as_str());
+    }
+    Ok(cookie)
+}
+
+/// 从 `Cookie` 请求头（`a=1; b=2`）中取出名为 `name` 的 cookie 值
+///
+/// 名称区分大小写，允许分隔符前后的多余空格与包裹值的双引号；同名 cookie 取第一个，
+/// 值为空时返回 `None`。
+pub fn extract_token_from_cookie_header<'a>(header: &'a str, name: &str) -> Option<&'a str> {
+    header
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| key.trim() == name)
+        .map(|(_, value)| {
+            let value = value.trim();
+            value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value)
+        })
+        .filter(|value| !value.is_empty())
+}
+
+fn attribute_value<'a>(attribute: &str, value: &'a str) -> Result<&'a str> {
+    if value.is_empty() || value.chars().any(|c| c == ';' || c.is_control()) {
+        return Err(cookie_error(format!(
+            "无效的 {} 属性: {:?}",
+            attribute, value
+        )));
+    }
+    Ok(value)
+}
+
+/// RFC 6265 cookie-name：可见 ASCII 字符，不含分隔符
+fn is_token_char(b: u8) -> bool {
+    b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b)
+}
+
+/// RFC 6265 cookie-octet：可见 ASCII 字符，不含 `"`、`,`、`;`、`\`
+fn is_cookie_octet(c: char) -> bool {
+    c.is_ascii_graphic() && !matches!(c, '"' | ',' | ';' | '\\')
+}
+
+fn cookie_error(message: String) -> ClamberError {
+    ClamberError::JwtError { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const TOKEN: &str = "eyJhbGciOiJIUzI1NiJ9.eyJzdWIiOiIxIn0.c2ln-_";
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_set_cookie_attributes() {
+        let opts = CookieOptions::new("access_token");
+        assert_eq!(
+            set_cookie_at(TOKEN, &opts, Duration::minutes(15), now()).unwrap(),
+            format!(
+                "access_token={}; Expires=Mon, 01 Jan 2024 00:15:00 GMT; Max-Age=900; Path=/; Secure; HttpOnly; SameSite=Lax",
+                TOKEN
+            )
+        );
+
+        let cases = [
+            (
+                CookieOptions::new("rt")
+                    .domain("example.com")
+                    .path(Some("/auth".to_string()))
+                    .same_site(Some(SameSite::Strict)),
+                "; Domain=example.com; Path=/auth; Secure; HttpOnly; SameSite=Strict",
+            ),
+            (
+                CookieOptions::new("rt").same_site(Some(SameSite::None)),
+                "; Path=/; Secure; HttpOnly; SameSite=None",
+            ),
+            (
+                CookieOptions::new("rt")
+                    .path(None)
+                    .secure(false)
+                    .http_only(false)
+                    .same_site(None),
+                "",
+            ),
+            (
+                CookieOptions::new("rt").secure(false).same_site(None),
+                "; Path=/; HttpOnly",
+            ),
+            (
+                CookieOptions::new("rt").http_only(false),
+                "; Path=/; Secure; SameSite=Lax",
+            ),
+        ];
+        for (opts, attributes) in cases {
+            assert_eq!(
+                set_cookie_at("t", &opts, Duration::days(1), now()).unwrap(),
+                format!(
+                    "rt=t; Expires=Tue, 02 Jan 2024 00:00:00 GMT; Max-Age=86400{}",
+                    attributes
+                )
+            );
+        }
+
+        // 非正的有效期：立即过期
+        assert_eq!(
+            set_cookie_at(
+                "",
+                &CookieOptions::new("rt").path(None),
+                Duration::seconds(-5),
+                now()
+            )
+            .unwrap(),
+            "rt=; Expires=Mon, 01 Jan 2024 00:00:00 GMT; Max-Age=0; Secure; HttpOnly; SameSite=Lax"
+        );
+        assert!(token_to_set_cookie(TOKEN, &opts, Duration::hours(1)).is_ok());
+    }
+
+    #[test]
+    fn test_set_cookie_rejects_invalid_input() {
+        let opts = CookieOptions::new("access_token");
+        for token in ["a b", "a;b", "a,b", "a\"b", "a\\b", "a%2Eb", "令牌"] {
+            assert!(
+                matches!(
+                    set_cookie_at(token, &opts, Duration::hours(1), now()),
+                    Err(ClamberError::JwtError { .. })
+                ),
+                "{}",
+                token
+            );
+        }
+        for name in ["", "a b", "a=b", "a;b", "(a)", "名称"] {
+            assert!(
+                set_cookie_at(TOKEN, &CookieOptions::new(name), Duration::hours(1), now()).is_err(),
+                "{}",
+                name
+            );
+        }
+        for opts in [
+            CookieOptions::new("a").domain("example.com; Secure"),
+            CookieOptions::new("a").domain(""),
+            CookieOptions::new("a").path(Some("/\n".to_string())),
+            CookieOptions::new("a")
+                .secure(false)
+                .same_site(Some(SameSite::None)),
+        ] {
+            assert!(
+                set_cookie_at(TOKEN, &opts, Duration::hours(1), now()).is_err(),
+                "{:?}",
+                opts
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_token_from_cookie_header() {
+        let cases = [
+            ("access_token=abc", Some("abc")),
+            ("theme=dark; access_token=abc; lang=zh", Some("abc")),
+            ("theme=dark;access_token=abc", Some("abc")),
+            ("  access_token = abc  ;theme=dark", Some("abc")),
+            ("access_token=\"abc\"", Some("abc")),
+            ("access_token=a=b", Some("a=b")),
+            ("access_token=first; access_token=second", Some("first")),
+            ("my_access_token=abc; access_token=xyz", Some("xyz")),
+            ("Access_Token=abc", None),
+            ("access_token_x=abc", None),
+            ("access_token=", None),
+            ("access_token", None),
+            ("theme=dark", None),
+            ("", None),
+            (";;", None),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(
+                extract_token_from_cookie_header(header, "access_token"),
+                expected,
+                "{}",
+                header
+            );
+        }
+
+        // 与 token_to_set_cookie 往返
+        let set_cookie =
+            token_to_set_cookie(TOKEN, &CookieOptions::new("rt"), Duration::hours(1)).unwrap();
+        let pair = set_cookie.split(';').next().unwrap();
+        assert_eq!(
+            extract_token_from_cookie_header(&format!("a=1; {}", pair), "rt"),
+            Some(TOKEN)
+        );
+    }
+}
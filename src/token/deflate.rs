@@ -0,0 +1,75 @@
+//! 原始 DEFLATE（RFC 1951，无 zlib 头）编解码，用于压缩 payload 声明，由 flate2 实现。
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use std::io::{Read, Write};
+
+/// 压缩为原始 DEFLATE 数据
+pub(super) fn compress(input: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(input)
+        .and_then(|_| encoder.finish())
+        .expect("写入 Vec 不会失败")
+}
+
+/// 解压原始 DEFLATE 数据，输出超过 `limit` 字节时返回错误（防止压缩炸弹）
+pub(super) fn decompress(input: &[u8], limit: usize) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    DeflateDecoder::new(input)
+        .take(limit as u64 + 1)
+        .read_to_end(&mut output)
+        .map_err(|e| e.to_string())?;
+    if output.len() > limit {
+        return Err(format!("解压后超过 {} 字节", limit));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let json = br#"{"permissions":["orders:read","orders:write","orders:read"]}"#.repeat(200);
+        let samples: [&[u8]; 5] = [b"", b"a", b"abcabcabcabcabc", &[0xFF; 1000], &json];
+        for input in samples {
+            let compressed = compress(input);
+            assert_eq!(decompress(&compressed, input.len()).unwrap(), input);
+        }
+        assert!(compress(&json).len() < json.len() / 10);
+    }
+
+    #[test]
+    fn test_decompress_other_encoders() {
+        // Python zlib.compressobj(9, zlib.DEFLATED, -15) 输出的存储块与动态 Huffman 块
+        assert_eq!(
+            decompress(
+                &[0x01, 0x05, 0x00, 0xFA, 0xFF, b'h', b'e', b'l', b'l', b'o'],
+                10
+            )
+            .unwrap(),
+            b"hello"
+        );
+        let dynamic = [
+            0x25, 0xC6, 0x41, 0x11, 0x00, 0x30, 0x0C, 0xC3, 0x30, 0x2A, 0xA1, 0x66, 0xA7, 0xE5,
+            0x4F, 0xA1, 0xBB, 0x9B, 0x5E, 0x2A, 0x91, 0x05, 0x4A, 0x02, 0xF9, 0x4F, 0x2B, 0xF3,
+            0x16, 0x05, 0x75, 0x38,
+        ];
+        assert_eq!(
+            decompress(&dynamic, 1024).unwrap(),
+            b"ca baeaaaca  aa aeaaac ccbadaaa bbaabbbda"
+        );
+    }
+
+    #[test]
+    fn test_decompress_rejects_invalid_and_oversized() {
+        assert!(decompress(&[], 10).is_err());
+        assert!(decompress(&[0x07], 10).is_err());
+        let compressed = compress(&[b'a'; 1000]);
+        assert!(decompress(&compressed, 999).is_err());
+        assert_eq!(decompress(&compressed, 1000).unwrap().len(), 1000);
+        assert!(decompress(&compressed[..compressed.len() - 1], 1000).is_err());
+    }
+}
@@ -0,0 +1,325 @@
+//! 签名密钥：HMAC 共享密钥，以及 `asymmetric` feature 下的 RSA / ECDSA PEM 密钥。
+use super::JwtAlgorithm;
+use super::claims::Claims;
+use crate::error::{ClamberError, Result};
+use hmac::{Hmac, Mac};
+use jwt::algorithm::SigningAlgorithm;
+use jwt::{Header, SignWithKey, Token, VerifyWithKey};
+use serde::de::DeserializeOwned;
+use sha2::{Sha256, Sha384, Sha512};
+
+/// JWT 签名密钥
+///
+/// HMAC 密钥按 [`JwtAlgorithm`] 选择 HS256 / HS384 / HS512；RSA 固定为 RS256，ECDSA 固定为 ES256（P-256 曲线）。
+/// 非对称密钥只配置公钥时只能验证，签发会返回 [`ClamberError::JwtKeyError`]。
+#[derive(Clone)]
+pub enum JwtKey {
+    /// HMAC 共享密钥
+    Hmac(String),
+    /// RSA 密钥（PEM）
+    #[cfg(feature = "asymmetric")]
+    RsaPem {
+        private: Option<String>,
+        public: String,
+    },
+    /// ECDSA P-256 密钥（PEM）
+    #[cfg(feature = "asymmetric")]
+    EcdsaPem {
+        private: Option<String>,
+        public: String,
+    },
+}
+
+/// 按算法预先初始化的签名密钥
+///
+/// HMAC 密钥在创建管理器时初始化一次，签发与验证时直接复用；非对称密钥仍在使用时解析 PEM，
+/// 以便密钥无效时在调用处返回 [`ClamberError::JwtKeyError`]。
+#[derive(Clone)]
+pub(super) enum PreparedKey {
+    Hs256(Hmac<Sha256>),
+    Hs384(Hmac<Sha384>),
+    Hs512(Hmac<Sha512>),
+    #[cfg(feature = "asymmetric")]
+    Rsa {
+        private: Option<String>,
+        public: String,
+    },
+    #[cfg(feature = "asymmetric")]
+    Ecdsa {
+        private: Option<String>,
+        public: String,
+    },
+}
+
+impl PreparedKey {
+    pub(super) fn new(key: &JwtKey, algorithm: JwtAlgorithm) -> Self {
+        match key {
+            JwtKey::Hmac(secret) => Self::hmac(secret, algorithm),
+            #[cfg(feature = "asymmetric")]
+            JwtKey::RsaPem { private, public } => PreparedKey::Rsa {
+                private: private.clone(),
+                public: public.clone(),
+            },
+            #[cfg(feature = "asymmetric")]
+            JwtKey::EcdsaPem { private, public } => PreparedKey::Ecdsa {
+                private: private.clone(),
+                public: public.clone(),
+            },
+        }
+    }
+
+    pub(super) fn hmac(secret: &str, algorithm: JwtAlgorithm) -> Self {
+        // HMAC 接受任意长度的密钥，new_from_slice 不会失败
+        const ANY_LENGTH: &str = "HMAC 接受任意长度的密钥";
+        let secret = secret.as_bytes();
+        match algorithm {
+            JwtAlgorithm::HS256 => {
+                PreparedKey::Hs256(Hmac::new_from_slice(secret).expect(ANY_LENGTH))
+            }
+            JwtAlgorithm::HS384 => {
+                PreparedKey::Hs384(Hmac::new_from_slice(secret).expect(ANY_LENGTH))
+            }
+            JwtAlgorithm::HS512 => {
+                PreparedKey::Hs512(Hmac::new_from_slice(secret).expect(ANY_LENGTH))
+            }
+        }
+    }
+
+    /// 是否为 HMAC 密钥
+    #[cfg(feature = "async")]
+    pub(super) fn is_hmac(&self) -> bool {
+        matches!(self, Self::Hs256(_) | Self::Hs384(_) | Self::Hs512(_))
+    }
+
+    /// 签名 claims
+    ///
+    /// `key_id` 为 `Some` 时写入 header 的 `kid`，header 字段顺序固定为 `alg`、`kid`。
+    pub(super) fn sign(&self, claims: &Claims, key_id: Option<&str>) -> Result<String> {
+        let signed = match self {
+            PreparedKey::Hs256(key) => sign_with(key, claims, key_id),
+            PreparedKey::Hs384(key) => sign_with(key, claims, key_id),
+            PreparedKey::Hs512(key) => sign_with(key, claims, key_id),
+            #[cfg(feature = "asymmetric")]
+            PreparedKey::Rsa { private, .. } => sign_with(
+                &pem::private_key(private.as_deref(), pem::Id::RSA)?,
+                claims,
+                key_id,
+            ),
+            #[cfg(feature = "asymmetric")]
+            PreparedKey::Ecdsa { private, .. } => sign_with(
+                &pem::private_key(private.as_deref(), pem::Id::EC)?,
+                claims,
+                key_id,
+            ),
+        };
+
+        // jwt::Error 默认映射为验证错误，签名阶段需显式区分
+        signed.map_err(|e| ClamberError::JwtSignError {
+            details: e.to_string(),
+            source: Some(e.into()),
+        })
+    }
+
+    /// 校验签名并把 claims 解析为 `C`；`C` 可以只包含需要的声明
+    pub(super) fn verify<C: DeserializeOwned>(&self, token: &str) -> Result<C> {
+        let token: Token<Header, C, _> = Token::parse_unverified(token)?;
+        let verified = match self {
+            PreparedKey::Hs256(key) => token.verify_with_key(key),
+            PreparedKey::Hs384(key) => token.verify_with_key(key),
+            PreparedKey::Hs512(key) => token.verify_with_key(key),
+            #[cfg(feature = "asymmetric")]
+            PreparedKey::Rsa { public, .. } => {
+                token.verify_with_key(&pem::public_key(public, pem::Id::RSA)?)
+            }
+            #[cfg(feature = "asymmetric")]
+            PreparedKey::Ecdsa { public, .. } => {
+                token.verify_with_key(&pem::public_key(public, pem::Id::EC)?)
+            }
+        };
+        let (_, claims) = verified?.into();
+        Ok(claims)
+    }
+}
+
+/// 无 `kid` 时沿用 jwt 预先编码的 `{"alg":...}` header
+fn sign_with(
+    key: &impl SigningAlgorithm,
+    claims: &Claims,
+    key_id: Option<&str>,
+) -> std::result::Result<String, jwt::Error> {
+    match key_id {
+        None => claims.sign_with_key(key),
+        Some(key_id) => {
+            let header = Header {
+                algorithm: key.algorithm_type(),
+                key_id: Some(key_id.to_string()),
+                ..Default::default()
+            };
+            let token = Token::new(header, claims).sign_with_key(key)?;
+            Ok(token.as_str().to_string())
+        }
+    }
+}
+
+#[cfg(feature = "asymmetric")]
+mod pem {
+    use crate::error::{ClamberError, Result};
+    use jwt::PKeyWithDigest;
+    use openssl::hash::MessageDigest;
+    pub(super) use openssl::pkey::Id;
+    use openssl::pkey::{PKey, Private, Public};
+
+    pub(super) fn private_key(pem: Option<&str>, id: Id) -> Result<PKeyWithDigest<Private>> {
+        let pem = pem.ok_or_else(|| ClamberError::JwtKeyError {
+            details: "仅配置了公钥，无法签发 token".to_string(),
+            source: None,
+        })?;
+        let key = PKey::private_key_from_pem(pem.as_bytes()).map_err(key_error)?;
+        with_digest(key, id)
+    }
+
+    pub(super) fn public_key(pem: &str, id: Id) -> Result<PKeyWithDigest<Public>> {
+        let key = PKey::public_key_from_pem(pem.as_bytes()).map_err(key_error)?;
+        with_digest(key, id)
+    }
+
+    /// 校验密钥类型与变体一致（jwt 遇到未知类型会 panic），并固定使用 SHA-256
+    fn with_digest<T>(key: PKey<T>, id: Id) -> Result<PKeyWithDigest<T>> {
+        if key.id() != id {
+            return Err(ClamberError::JwtKeyError {
+                details: format!("密钥类型不符: 期望 {:?}，实际 {:?}", id, key.id()),
+                source: None,
+            });
+        }
+        Ok(PKeyWithDigest {
+            digest: MessageDigest::sha256(),
+            key,
+        })
+    }
+
+    fn key_error(err: openssl::error::ErrorStack) -> ClamberError {
+        ClamberError::JwtKeyError {
+            details: format!("解析 PEM 密钥失败: {}", err),
+            source: Some(err.into()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "asymmetric"))]
+mod tests {
+    use super::*;
+    use crate::token::JwtManager;
+    use chrono::Duration;
+
+    const RSA_PRIVATE: &str = include_str!("../../tests/fixtures/rsa-private.pem");
+    const RSA_PUBLIC: &str = include_str!("../../tests/fixtures/rsa-public.pem");
+    const RSA_OTHER_PUBLIC: &str = include_str!("../../tests/fixtures/rsa-other-public.pem");
+    const EC_PRIVATE: &str = include_str!("../../tests/fixtures/ec-private.pem");
+    const EC_PUBLIC: &str = include_str!("../../tests/fixtures/ec-public.pem");
+
+    fn manager(key: JwtKey) -> JwtManager {
+        JwtManager::new_with_key(key, Duration::hours(1))
+    }
+
+    #[test]
+    fn test_asymmetric_sign_and_verify() {
+        let cases = [
+            (
+                "RS256",
+                JwtKey::RsaPem {
+                    private: Some(RSA_PRIVATE.to_string()),
+                    public: RSA_PUBLIC.to_string(),
+                },
+                JwtKey::RsaPem {
+                    private: None,
+                    public: RSA_PUBLIC.to_string(),
+                },
+            ),
+            (
+                "ES256",
+                JwtKey::EcdsaPem {
+                    private: Some(EC_PRIVATE.to_string()),
+                    public: EC_PUBLIC.to_string(),
+                },
+                JwtKey::EcdsaPem {
+                    private: None,
+                    public: EC_PUBLIC.to_string(),
+                },
+            ),
+        ];
+
+        for (alg, signing, verifying) in cases {
+            let token = manager(signing).generate_token(&"payload").unwrap();
+            let header = crate::token::decode_unverified(&token).unwrap().header;
+            assert_eq!(header["alg"], alg);
+
+            // 只持有公钥的一方可以验证，但不能签发
+            let verifier = manager(verifying);
+            assert_eq!(verifier.verify_token::<String>(&token).unwrap(), "payload");
+            assert!(matches!(
+                verifier.generate_token(&"payload"),
+                Err(ClamberError::JwtKeyError { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_asymmetric_wrong_key_rejected() {
+        let token = manager(JwtKey::RsaPem {
+            private: Some(RSA_PRIVATE.to_string()),
+            public: RSA_PUBLIC.to_string(),
+        })
+        .generate_token(&"payload")
+        .unwrap();
+
+        let other = manager(JwtKey::RsaPem {
+            private: None,
+            public: RSA_OTHER_PUBLIC.to_string(),
+        });
+        assert!(matches!(
+            other.verify_token::<String>(&token),
+            Err(ClamberError::JwtVerifyError { .. })
+        ));
+
+        // RS256 token 交给 ES256 公钥验证：算法不符
+        let ec = manager(JwtKey::EcdsaPem {
+            private: None,
+            public: EC_PUBLIC.to_string(),
+        });
+        assert!(matches!(
+            ec.verify_token::<String>(&token),
+            Err(ClamberError::JwtAlgorithmMismatchError { .. })
+        ));
+
+        // HMAC token 交给 RSA 公钥验证
+        let hmac_token = manager(JwtKey::Hmac("secret".to_string()))
+            .generate_token(&"payload")
+            .unwrap();
+        let rsa = manager(JwtKey::RsaPem {
+            private: None,
+            public: RSA_PUBLIC.to_string(),
+        });
+        assert!(matches!(
+            rsa.verify_token::<String>(&hmac_token),
+            Err(ClamberError::JwtAlgorithmMismatchError { .. })
+        ));
+
+        // 密钥类型与变体不一致、PEM 无效
+        let mismatched = manager(JwtKey::EcdsaPem {
+            private: None,
+            public: RSA_PUBLIC.to_string(),
+        });
+        assert!(matches!(
+            mismatched.verify_token::<String>(&token),
+            Err(ClamberError::JwtKeyError { .. })
+        ));
+        let invalid = manager(JwtKey::RsaPem {
+            private: None,
+            public: "not a pem".to_string(),
+        });
+        assert!(matches!(
+            invalid.verify_token::<String>(&token),
+            Err(ClamberError::JwtKeyError { .. })
+        ));
+    }
+}
@@ -1,30 +1,134 @@
 //! JWT 模块：提供令牌生成、验证和便捷函数；支持自定义密钥与过期时间。
 //! 详见根目录 JWT_README.md 获取更丰富的示例与 API 说明。
 use crate::error::{ClamberError, Result};
-use chrono::{Duration, Utc};
-use hmac::{Hmac, Mac};
-use jwt::{SignWithKey, VerifyWithKey};
-use serde::{Serialize, de::DeserializeOwned};
-use sha2::Sha256;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
+#[cfg(feature = "axum")]
+pub mod axum;
+mod claims;
+pub mod cookie;
+mod deflate;
+mod key;
+mod revocation;
 
-const DEFAULT_JWT_SECRET: &'static str = "secret";
+use claims::{
+    Claims, ValidityClaims, decode_payload, required_str, required_timestamp, timestamp_value,
+};
+
+pub use key::JwtKey;
+use key::PreparedKey;
+pub use revocation::{MemoryRevocationStore, TokenRevocationStore};
+
+const DEFAULT_JWT_SECRET: &str = "secret";
+const DEFAULT_MAX_PAYLOAD_BYTES: usize = 8192;
+const DEFAULT_MAX_EXPIRE_DAYS: i64 = 365;
 
 /// JWT配置结构
-#[derive(Debug, Clone)]
+///
+/// `Debug` 输出中密钥以 `***` 代替。
+#[derive(Clone)]
 pub struct JwtConfig {
     /// JWT密钥
     pub secret: String,
-    /// 过期时间（天数）
-    pub expire_days: i64,
+    /// 密钥轮换期间仍接受的旧密钥，仅用于验证
+    pub fallback_secrets: Vec<String>,
+    /// 过期时长
+    pub expire: Duration,
+    /// 单次指定过期时间（[`JwtManager::generate_token_with_expiry`] 等）时允许的最长有效期，`None` 表示不限制
+    ///
+    /// 默认 365 天，避免误签发几十年后才过期的 token；不影响按 `expire` 签发的 token。
+    pub max_expire: Option<Duration>,
+    /// HMAC 签名算法
+    pub algorithm: JwtAlgorithm,
+    /// 签发者（`iss`），设置后验证时要求一致
+    pub issuer: Option<String>,
+    /// 受众（`aud`），设置后验证时要求一致
+    pub audience: Option<String>,
+    /// 主题（`sub`），仅写入 token
+    pub subject: Option<String>,
+    /// 签发后多少秒开始生效（写入 `nbf`）
+    pub not_before_secs: Option<i64>,
+    /// 校验 `exp` / `nbf` 时容忍的时钟偏差（秒），默认 0
+    ///
+    /// `exp + leeway <= now` 才视为过期，`nbf - leeway > now` 才视为未生效。负值按 0 处理，
+    /// 通过 [`JwtConfig::leeway_secs`] 设置时直接拒绝。
+    pub leeway_secs: i64,
+    /// token 自签发（`createAt`，平铺模式下为 `iat`）起的最长有效时长，`None` 表示不限制
+    ///
+    /// 超过后即使未到 `exp` 也返回 [`ClamberError::JwtTooOldError`]，用于修改密码等场景强制重新登录。
+    /// 设置后缺少签发时间的旧 token 返回 [`ClamberError::JwtMissingFieldError`]。
+    pub max_token_age: Option<Duration>,
+    /// access token 过期时间（分钟），用于 [`JwtManager::generate_token_pair`]
+    pub access_expire_minutes: i64,
+    /// refresh token 过期时间（天数）
+    pub refresh_expire_days: i64,
+    /// payload 字段直接作为顶层声明，`iat` 使用数字，便于其他 JWT 库解析
+    ///
+    /// 默认关闭：payload 以 JSON 字符串写入 `payload` 声明。两种模式的 token 互不兼容。
+    pub flat_claims: bool,
+    /// 写入 token 的 payload 字节数上限（开启压缩时按压缩并编码后的长度计算），`None` 表示不限制
+    ///
+    /// 默认 8192：token 通常放在请求头中，过大的 token 会超出网关 / 代理的请求头限制。
+    pub max_payload_bytes: Option<usize>,
+    /// 以 deflate 压缩 payload 并 base64url 编码后写入，验证时自动识别解压；平铺模式下不生效
+    pub compress_payload: bool,
+    /// 密钥标识，设置后签发时写入 header 的 `kid`，供验证方选择密钥，见 [`peek_header`]
+    pub key_id: Option<String>,
+}
+
+impl std::fmt::Debug for JwtConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtConfig")
+            .field("secret", &"***")
+            .field(
+                "fallback_secrets",
+                &vec!["***"; self.fallback_secrets.len()],
+            )
+            .field("expire", &self.expire)
+            .field("max_expire", &self.max_expire)
+            .field("algorithm", &self.algorithm)
+            .field("issuer", &self.issuer)
+            .field("audience", &self.audience)
+            .field("subject", &self.subject)
+            .field("not_before_secs", &self.not_before_secs)
+            .field("leeway_secs", &self.leeway_secs)
+            .field("max_token_age", &self.max_token_age)
+            .field("access_expire_minutes", &self.access_expire_minutes)
+            .field("refresh_expire_days", &self.refresh_expire_days)
+            .field("flat_claims", &self.flat_claims)
+            .field("max_payload_bytes", &self.max_payload_bytes)
+            .field("compress_payload", &self.compress_payload)
+            .field("key_id", &self.key_id)
+            .finish()
+    }
 }
 
 impl Default for JwtConfig {
     fn default() -> Self {
         Self {
             secret: DEFAULT_JWT_SECRET.to_string(),
-            expire_days: 7,
+            fallback_secrets: Vec::new(),
+            expire: Duration::days(7),
+            max_expire: Some(Duration::days(DEFAULT_MAX_EXPIRE_DAYS)),
+            algorithm: JwtAlgorithm::default(),
+            issuer: None,
+            audience: None,
+            subject: None,
+            not_before_secs: None,
+            leeway_secs: 0,
+            max_token_age: None,
+            access_expire_minutes: 30,
+            refresh_expire_days: 30,
+            flat_claims: false,
+            max_payload_bytes: Some(DEFAULT_MAX_PAYLOAD_BYTES),
+            compress_payload: false,
+            key_id: None,
         }
     }
 }
@@ -34,7 +138,8 @@ impl JwtConfig {
     pub fn new(secret: impl Into<String>, expire_days: i64) -> Self {
         Self {
             secret: secret.into(),
-            expire_days,
+            expire: Duration::days(expire_days),
+            ..Default::default()
         }
     }
 
@@ -42,124 +147,942 @@ impl JwtConfig {
     pub fn with_secret(secret: impl Into<String>) -> Self {
         Self {
             secret: secret.into(),
-            expire_days: 7
+            ..Default::default()
         }
     }
+
+    /// 设置旧密钥：签发始终使用 `secret`，验证时主密钥失败再依次尝试旧密钥
+    pub fn with_fallback_secrets(mut self, secrets: Vec<String>) -> Self {
+        self.fallback_secrets = secrets;
+        self
+    }
+
+    /// 设置过期时长
+    pub fn expire(mut self, expire: Duration) -> Self {
+        self.expire = expire;
+        self
+    }
+
+    /// 过期时间（秒）
+    pub fn expire_seconds(self, seconds: i64) -> Self {
+        self.expire(Duration::seconds(seconds))
+    }
+
+    /// 过期时间（分钟）
+    pub fn expire_minutes(self, minutes: i64) -> Self {
+        self.expire(Duration::minutes(minutes))
+    }
+
+    /// 过期时间（小时）
+    pub fn expire_hours(self, hours: i64) -> Self {
+        self.expire(Duration::hours(hours))
+    }
+
+    /// 过期时间（天数）
+    pub fn expire_days(self, days: i64) -> Self {
+        self.expire(Duration::days(days))
+    }
+
+    /// 设置单次指定过期时间时允许的最长有效期，`None` 表示不限制
+    pub fn max_expire(mut self, max: Option<Duration>) -> Self {
+        self.max_expire = max;
+        self
+    }
+
+    /// 设置 token 自签发起的最长有效时长，超过后要求重新认证
+    pub fn max_token_age(mut self, max_age: Duration) -> Self {
+        self.max_token_age = Some(max_age);
+        self
+    }
+
+    /// 设置签名算法
+    pub fn algorithm(mut self, algorithm: JwtAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// 设置签发者
+    pub fn issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer = Some(issuer.into());
+        self
+    }
+
+    /// 设置受众
+    pub fn audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// 设置主题
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// 设置生效延迟（秒）
+    pub fn not_before_secs(mut self, secs: i64) -> Self {
+        self.not_before_secs = Some(secs);
+        self
+    }
+
+    /// 设置时钟偏差容忍（秒），负值返回 [`ClamberError::JwtError`]
+    pub fn leeway_secs(mut self, secs: i64) -> Result<Self> {
+        if secs < 0 {
+            return Err(ClamberError::JwtError {
+                message: format!("leeway_secs 不能为负数: {}", secs),
+            });
+        }
+        self.leeway_secs = secs;
+        Ok(self)
+    }
+
+    /// 设置 access token 过期时间（分钟）
+    pub fn access_expire_minutes(mut self, minutes: i64) -> Self {
+        self.access_expire_minutes = minutes;
+        self
+    }
+
+    /// 设置 refresh token 过期时间（天数）
+    pub fn refresh_expire_days(mut self, days: i64) -> Self {
+        self.refresh_expire_days = days;
+        self
+    }
+
+    /// 设置是否平铺 payload 为顶层声明（payload 须序列化为 JSON 对象）
+    pub fn flat_claims(mut self, flat: bool) -> Self {
+        self.flat_claims = flat;
+        self
+    }
+
+    /// 设置 payload 字节数上限，`None` 表示不限制
+    pub fn max_payload_bytes(mut self, limit: Option<usize>) -> Self {
+        self.max_payload_bytes = limit;
+        self
+    }
+
+    /// 设置是否压缩 payload
+    pub fn compress_payload(mut self, compress: bool) -> Self {
+        self.compress_payload = compress;
+        self
+    }
+
+    /// 设置写入 header 的密钥标识（`kid`）
+    pub fn key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.key_id = Some(key_id.into());
+        self
+    }
+}
+
+/// HMAC 签名算法（非对称密钥的算法由 [`JwtKey`] 决定）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum JwtAlgorithm {
+    #[default]
+    HS256,
+    HS384,
+    HS512,
+}
+
+/// token 类型，写入 `token_type` 声明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    /// 访问接口使用的短期 token
+    Access,
+    /// 仅用于换取新 access token 的长期 token
+    Refresh,
 }
 
+impl TokenType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenType::Access => "access",
+            TokenType::Refresh => "refresh",
+        }
+    }
+}
+
+/// access + refresh token 对
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+/// 当前时间来源，便于测试中固定时钟
+pub type JwtClock = Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>;
+
 /// JWT管理器
+///
+/// 配置与密钥在创建时初始化并以 `Arc` 共享，`clone` 只增加引用计数，可直接放入 axum 等框架的状态中。
+#[derive(Clone)]
 pub struct JwtManager {
-    config: JwtConfig,
+    config: Arc<JwtConfig>,
+    /// 按密钥与算法初始化的签名密钥
+    signer: Arc<PreparedKey>,
+    /// `fallback_secrets` 对应的验证密钥
+    fallback_keys: Arc<[PreparedKey]>,
+    clock: JwtClock,
+    revocation: Option<Arc<dyn TokenRevocationStore>>,
+}
+
+impl std::fmt::Debug for JwtManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtManager")
+            .field("config", &self.config)
+            .field("revocation", &self.revocation.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for JwtManager {
+    /// 使用默认配置创建JWT管理器
+    fn default() -> Self {
+        Self::new(JwtConfig::default())
+    }
 }
 
 impl JwtManager {
     /// 创建新的JWT管理器
     pub fn new(config: JwtConfig) -> Self {
-        Self { config }
+        let key = JwtKey::Hmac(config.secret.clone());
+        Self::with_parts(config, key)
     }
 
-    /// 使用默认配置创建JWT管理器
-    pub fn default() -> Self {
+    /// 创建以 `Arc` 包装的管理器，便于在多个组件间共享
+    pub fn shared(config: JwtConfig) -> Arc<Self> {
+        Arc::new(Self::new(config))
+    }
+
+    fn with_parts(config: JwtConfig, key: JwtKey) -> Self {
+        let fallback_keys = config
+            .fallback_secrets
+            .iter()
+            .map(|secret| PreparedKey::hmac(secret, config.algorithm))
+            .collect();
         Self {
-            config: JwtConfig::default(),
+            signer: Arc::new(PreparedKey::new(&key, config.algorithm)),
+            fallback_keys,
+            config: Arc::new(config),
+            clock: Arc::new(Utc::now),
+            revocation: None,
         }
     }
 
+    /// 使用指定密钥创建JWT管理器，其余配置取默认值
+    ///
+    /// 非对称密钥只提供公钥时可用于验证，签发返回 [`ClamberError::JwtKeyError`]。
+    pub fn new_with_key(key: JwtKey, expire: Duration) -> Self {
+        let secret = match &key {
+            JwtKey::Hmac(secret) => secret.clone(),
+            #[cfg(feature = "asymmetric")]
+            JwtKey::RsaPem { .. } | JwtKey::EcdsaPem { .. } => String::new(),
+        };
+        Self::with_parts(JwtConfig::with_secret(secret).expire(expire), key)
+    }
+
+    /// 使用自定义时钟计算签发与过期时间
+    pub fn with_clock<F>(mut self, clock: F) -> Self
+    where
+        F: Fn() -> DateTime<Utc> + Send + Sync + 'static,
+    {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// 验证时查询吊销存储，被吊销的 token 返回 [`ClamberError::JwtRevokedError`]
+    pub fn with_revocation_store(mut self, store: Arc<dyn TokenRevocationStore>) -> Self {
+        self.revocation = Some(store);
+        self
+    }
+
+    /// 吊销 token（如用户登出），需先配置吊销存储
+    ///
+    /// 只校验签名，已过期的 token 也可吊销。
+    pub fn revoke_token(&self, token: &str) -> Result<()> {
+        let store = self
+            .revocation
+            .as_ref()
+            .ok_or_else(|| ClamberError::JwtError {
+                message: "未配置 TokenRevocationStore，无法吊销 token".to_string(),
+            })?;
+        let claims = self.verify_signature(token)?;
+        let exp = required_timestamp(&claims, "exp")?;
+        // 验证时 exp 之后仍有 leeway 秒的容忍期，吊销记录需覆盖这段时间
+        store.revoke(
+            required_str(&claims, "jti")?,
+            exp.saturating_add(self.leeway()),
+        );
+        Ok(())
+    }
+
+    /// 时钟偏差容忍（秒）
+    fn leeway(&self) -> i64 {
+        self.config.leeway_secs.max(0)
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        (self.clock)()
+    }
+
+    /// 用主密钥签名，配置了 `key_id` 时写入 header
+    fn sign(&self, claims: &Claims) -> Result<String> {
+        self.signer.sign(claims, self.config.key_id.as_deref())
+    }
+
     /// 生成JWT token
     pub fn generate_token<T>(&self, payload: &T) -> Result<String>
     where
         T: Serialize,
     {
-        let expire_time = Utc::now() + Duration::days(self.config.expire_days);
+        self.issue_token(payload, self.config.expire, None)
+    }
 
-        // 将payload序列化为JSON字符串
-        let payload_json = serde_json::to_string(payload)?;
+    /// 生成 access token 与 refresh token
+    ///
+    /// 过期时间分别取 `access_expire_minutes` 与 `refresh_expire_days`。
+    pub fn generate_token_pair<T>(&self, payload: &T) -> Result<TokenPair>
+    where
+        T: Serialize,
+    {
+        Ok(TokenPair {
+            access_token: self.issue_access_token(payload)?,
+            refresh_token: self.issue_token(
+                payload,
+                Duration::days(self.config.refresh_expire_days),
+                Some(TokenType::Refresh),
+            )?,
+        })
+    }
 
-        let mut claims = BTreeMap::new();
-        claims.insert("payload".to_string(), payload_json);
-        claims.insert("exp".to_string(), expire_time.timestamp().to_string());
-        claims.insert("createAt".to_string(), Utc::now().timestamp().to_string());
-
-        let key: Hmac<Sha256> =
-            Hmac::new_from_slice(self.config.secret.as_bytes()).map_err(|e| {
-                ClamberError::JwtKeyError {
-                    details: e.to_string(),
-                }
+    /// 验证 refresh token 并以其中的 payload 签发新的 access token
+    pub fn refresh_access_token<T>(&self, refresh_token: &str) -> Result<String>
+    where
+        T: DeserializeOwned + Serialize,
+    {
+        let payload: T = self.verify_token_of_type(refresh_token, TokenType::Refresh)?;
+        self.issue_access_token(&payload)
+    }
+
+    fn issue_access_token<T>(&self, payload: &T) -> Result<String>
+    where
+        T: Serialize,
+    {
+        self.issue_token(
+            payload,
+            Duration::minutes(self.config.access_expire_minutes),
+            Some(TokenType::Access),
+        )
+    }
+
+    fn issue_token<T>(
+        &self,
+        payload: &T,
+        expire_in: Duration,
+        token_type: Option<TokenType>,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let now = self.now();
+        let claims = self.build_claims(payload, now, now + expire_in, token_type)?;
+        self.sign(&claims)
+    }
+
+    /// 生成在指定时刻过期的 token（如会议结束时）
+    ///
+    /// `expire_at` 不晚于当前时间或有效期超过 [`JwtConfig::max_expire`] 时返回 [`ClamberError::JwtError`]。
+    pub fn generate_token_with_expiry<T>(
+        &self,
+        payload: &T,
+        expire_at: DateTime<Utc>,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        self.issue_token_until(payload, self.now(), expire_at)
+    }
+
+    /// 生成有效期为 `duration` 的 token，不使用配置中的过期时长
+    ///
+    /// `duration` 不为正或超过 [`JwtConfig::max_expire`] 时返回 [`ClamberError::JwtError`]。
+    pub fn generate_token_valid_for<T>(&self, payload: &T, duration: Duration) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let now = self.now();
+        let expire_at = now
+            .checked_add_signed(duration)
+            .ok_or_else(|| ClamberError::JwtError {
+                message: format!("有效期超出范围: {}", duration),
             })?;
+        self.issue_token_until(payload, now, expire_at)
+    }
 
-        claims
-            .sign_with_key(&key)
-            .map_err(|e| ClamberError::JwtSignError {
-                details: e.to_string(),
-            })
+    fn issue_token_until<T>(
+        &self,
+        payload: &T,
+        now: DateTime<Utc>,
+        expire_at: DateTime<Utc>,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        if expire_at <= now {
+            return Err(ClamberError::JwtError {
+                message: format!("过期时间 {} 不晚于当前时间 {}", expire_at, now),
+            });
+        }
+        if let Some(max) = self.config.max_expire
+            && expire_at - now > max
+        {
+            return Err(ClamberError::JwtError {
+                message: format!(
+                    "有效期 {} 秒超过上限 {} 秒",
+                    (expire_at - now).num_seconds(),
+                    max.num_seconds()
+                ),
+            });
+        }
+        let claims = self.build_claims(payload, now, expire_at, None)?;
+        self.sign(&claims)
+    }
+
+    /// 生成携带自定义声明（如 `tenant_id`、`session_id`）的 token
+    ///
+    /// 自定义声明不能覆盖 `exp`、`payload`、`createAt` 等保留声明，否则返回 [`ClamberError::JwtError`]。
+    pub fn generate_token_with_claims<T>(
+        &self,
+        payload: &T,
+        extra: BTreeMap<String, String>,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let now = self.now();
+        let mut claims = self.build_claims(payload, now, now + self.config.expire, None)?;
+        for (name, value) in extra {
+            // 平铺模式下还需避开 payload 的字段
+            if claims::is_reserved(&name) || claims.contains_key(&name) {
+                return Err(ClamberError::JwtError {
+                    message: format!("自定义声明 {} 与保留声明冲突", name),
+                });
+            }
+            claims.insert(name, Value::String(value));
+        }
+        self.sign(&claims)
+    }
+
+    fn build_claims<T>(
+        &self,
+        payload: &T,
+        now: DateTime<Utc>,
+        expire_time: DateTime<Utc>,
+        token_type: Option<TokenType>,
+    ) -> Result<Claims>
+    where
+        T: Serialize,
+    {
+        let flat = self.config.flat_claims;
+
+        let mut claims = Claims::new();
+        claims::insert_payload(&mut claims, payload, now.timestamp(), &self.config)?;
+        // exp 在两种模式下都写为数字，校验时无需再解析字符串；旧 token 的字符串 exp 仍可读取
+        claims.insert("exp".to_string(), Value::from(expire_time.timestamp()));
+        claims.insert("jti".to_string(), Value::String(generate_jti()?));
+        if let Some(issuer) = &self.config.issuer {
+            claims.insert("iss".to_string(), Value::String(issuer.clone()));
+        }
+        if let Some(audience) = &self.config.audience {
+            claims.insert("aud".to_string(), Value::String(audience.clone()));
+        }
+        if let Some(subject) = &self.config.subject {
+            claims.insert("sub".to_string(), Value::String(subject.clone()));
+        }
+        if let Some(token_type) = token_type {
+            claims.insert("token_type".to_string(), token_type.as_str().into());
+        }
+        if let Some(secs) = self.config.not_before_secs {
+            let nbf = now + Duration::seconds(secs);
+            claims.insert("nbf".to_string(), timestamp_value(nbf.timestamp(), flat));
+        }
+        Ok(claims)
     }
 
     /// 验证并解析JWT token
+    ///
+    /// 不检查 `token_type`；需要区分 access / refresh 时使用 [`JwtManager::verify_token_of_type`]。
     pub fn verify_token<T>(&self, token: &str) -> Result<T>
     where
         T: DeserializeOwned,
     {
-        let key: Hmac<Sha256> =
-            Hmac::new_from_slice(self.config.secret.as_bytes()).map_err(|e| {
-                ClamberError::JwtKeyError {
-                    details: e.to_string(),
-                }
-            })?;
+        let claims = self.verify_claims(token)?;
+        decode_payload(&claims, self.config.flat_claims)
+    }
 
-        let claims: BTreeMap<String, String> =
-            token
-                .verify_with_key(&key)
-                .map_err(|e| ClamberError::JwtVerifyError {
-                    details: e.to_string(),
-                })?;
-
-        // 检查过期时间
-        if let Some(exp_str) = claims.get("exp") {
-            let exp_timestamp = exp_str.parse::<i64>().map_err(|_| ClamberError::JwtError {
-                message: "无效的过期时间格式".to_string(),
-            })?;
+    /// 验证 token，返回 payload 与保留声明之外的自定义声明
+    ///
+    /// 平铺模式下 payload 的字段同样位于顶层，也会出现在返回的声明中。
+    pub fn verify_token_with_claims<T>(&self, token: &str) -> Result<(T, BTreeMap<String, String>)>
+    where
+        T: DeserializeOwned,
+    {
+        let claims = self.verify_claims(token)?;
+        let payload = decode_payload(&claims, self.config.flat_claims)?;
+        Ok((payload, claims::custom_claims(&claims)))
+    }
 
-            if exp_timestamp <= Utc::now().timestamp() {
-                return Err(ClamberError::JwtExpiredError);
-            }
-        } else {
-            return Err(ClamberError::JwtMissingFieldError {
-                field: "exp".to_string(),
+    /// 验证 token 并要求 `token_type` 与 `expected` 一致
+    pub fn verify_token_of_type<T>(&self, token: &str, expected: TokenType) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let claims = self.verify_claims(token)?;
+        let actual = required_str(&claims, "token_type")?;
+        if actual != expected.as_str() {
+            return Err(ClamberError::JwtTokenTypeMismatchError {
+                expected: expected.as_str().to_string(),
+                actual: actual.to_string(),
             });
         }
+        decode_payload(&claims, self.config.flat_claims)
+    }
 
-        // 获取payload并反序列化
-        if let Some(payload_str) = claims.get("payload") {
-            serde_json::from_str::<T>(payload_str).map_err(|e| ClamberError::DeserializationError {
-                details: e.to_string(),
-            })
-        } else {
-            Err(ClamberError::JwtMissingFieldError {
-                field: "payload".to_string(),
-            })
-        }
+    /// 按 header 中的 `kid` 选择配置验证 token，适用于多个签发方共用一个验证入口
+    ///
+    /// 没有 `kid` 或与本管理器的 [`JwtConfig::key_id`] 相同时直接用本管理器验证；否则交给
+    /// `resolver` 解析出对应配置后验证（沿用本管理器的时钟与吊销存储），解析不到时返回
+    /// [`ClamberError::JwtKeyError`]。每次解析都会初始化密钥，热路径上应让 `resolver` 返回缓存的配置。
+    pub fn verify_with_kid_resolver<T, F>(&self, token: &str, resolver: F) -> Result<T>
+    where
+        T: DeserializeOwned,
+        F: Fn(&str) -> Option<JwtConfig>,
+    {
+        let header = peek_header(token)?;
+        let kid = match header.kid {
+            Some(kid) if self.config.key_id.as_deref() != Some(kid.as_str()) => kid,
+            _ => return self.verify_token(token),
+        };
+        let config = resolver(&kid).ok_or_else(|| ClamberError::JwtKeyError {
+            details: format!("未知的密钥标识 kid={}", kid),
+            source: None,
+        })?;
+        let key = JwtKey::Hmac(config.secret.clone());
+        let manager = Self {
+            clock: self.clock.clone(),
+            revocation: self.revocation.clone(),
+            ..Self::with_parts(config, key)
+        };
+        manager.verify_token(token)
+    }
+
+    /// 验证签名并返回 token 的声明信息，过期时不报错而是通过 `is_expired` 标记
+    ///
+    /// 可据此区分“已过期但真实”与“伪造”的 token，例如决定是否需要刷新。
+    pub fn decode_claims(&self, token: &str) -> Result<TokenClaims> {
+        let claims = self.verify_signature(token)?;
+        let now = self.now();
+
+        let exp = required_timestamp(&claims, "exp")?;
+        let created_at = claims::timestamp(&claims, self.created_at_claim())?
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0));
+        let payload = claims::payload_value(&claims, self.config.flat_claims)?;
+
+        Ok(TokenClaims {
+            exp,
+            created_at,
+            age: created_at.map(|created_at| now - created_at),
+            expires_in: Duration::seconds(exp - now.timestamp()),
+            is_expired: exp.saturating_add(self.leeway()) <= now.timestamp(),
+            payload,
+        })
     }
 
     /// 检查token是否有效（不解析payload）
+    ///
+    /// 结果与 [`JwtManager::validate`] 返回 `Valid` 一致，但只解析校验所需的声明，适合鉴权热路径。
     pub fn is_valid_token(&self, token: &str) -> bool {
-        let key = match Hmac::<Sha256>::new_from_slice(self.config.secret.as_bytes()) {
-            Ok(key) => key,
-            Err(_) => return false,
+        let Ok(claims) = self.verify_signature_as::<ValidityClaims>(token) else {
+            return false;
         };
+        let now = self.now().timestamp();
+        // 与 check_claims 的规则保持一致
+        let issuer_ok = self.config.issuer.is_none() || claims.iss == self.config.issuer;
+        let audience_ok = self.config.audience.is_none() || claims.aud == self.config.audience;
+        let revoked = match (&self.revocation, &claims.jti) {
+            (Some(store), Some(jti)) => store.is_revoked(jti),
+            _ => false,
+        };
+        let age_ok = self
+            .check_age(claims.created_at(self.config.flat_claims), now)
+            .is_ok();
+        let leeway = self.leeway();
+        claims
+            .exp
+            .is_some_and(|exp| exp.0.saturating_add(leeway) > now)
+            && claims
+                .nbf
+                .is_none_or(|nbf| nbf.0.saturating_sub(leeway) <= now)
+            && issuer_ok
+            && audience_ok
+            && !revoked
+            && age_ok
+    }
 
-        if let Ok(claims) = token.verify_with_key(&key) {
-            let claims: BTreeMap<String, String> = claims;
-            if let Some(exp_str) = claims.get("exp") {
-                if let Ok(exp_timestamp) = exp_str.parse::<i64>() {
-                    return exp_timestamp > Utc::now().timestamp();
+    /// 检查 token 状态，区分过期（可刷新）与伪造、格式错误（需重新登录）
+    pub fn validate(&self, token: &str) -> TokenStatus {
+        let claims = match self.verify_signature(token) {
+            Ok(claims) => claims,
+            Err(err) if is_signature_error(&err) => return TokenStatus::InvalidSignature,
+            Err(_) => return TokenStatus::Malformed,
+        };
+        let Ok(exp) = required_timestamp(&claims, "exp") else {
+            return TokenStatus::Malformed;
+        };
+
+        match self.check_claims(&claims) {
+            Ok(()) => TokenStatus::Valid {
+                expires_in: Duration::seconds(exp - self.now().timestamp()),
+            },
+            Err(ClamberError::JwtExpiredError) => TokenStatus::Expired { expired_at: exp },
+            Err(ClamberError::JwtError { .. }) => TokenStatus::Malformed,
+            Err(err) => TokenStatus::Rejected(err),
+        }
+    }
+
+    /// 仅校验签名，返回全部 claims
+    ///
+    /// header 中的算法与配置不一致时返回 [`ClamberError::JwtAlgorithmMismatchError`]。
+    /// 主密钥签名不符时依次尝试 `fallback_secrets`，全部失败返回主密钥的错误。
+    fn verify_signature(&self, token: &str) -> Result<Claims> {
+        self.verify_signature_as(token)
+    }
+
+    /// 校验签名，把 claims 解析为 `C`
+    fn verify_signature_as<C: DeserializeOwned>(&self, token: &str) -> Result<C> {
+        let err = match self.signer.verify(token) {
+            Ok(claims) => return Ok(claims),
+            Err(err) => err,
+        };
+        if is_signature_error(&err) {
+            for fallback in self.fallback_keys.iter() {
+                if let Ok(claims) = fallback.verify(token) {
+                    return Ok(claims);
                 }
             }
         }
-        false
+        Err(err)
     }
+
+    /// 校验签名与各项声明，返回全部 claims
+    fn verify_claims(&self, token: &str) -> Result<Claims> {
+        let claims = self.verify_signature(token)?;
+        self.check_claims(&claims)?;
+        Ok(claims)
+    }
+
+    /// 签发时间所在的声明
+    fn created_at_claim(&self) -> &'static str {
+        if self.config.flat_claims {
+            "iat"
+        } else {
+            "createAt"
+        }
+    }
+
+    /// 按 `max_token_age` 检查签发时间，同样容忍 leeway 秒的时钟偏差
+    fn check_age(&self, created_at: Option<i64>, now: i64) -> Result<()> {
+        let Some(max_age) = self.config.max_token_age else {
+            return Ok(());
+        };
+        let created_at = created_at.ok_or_else(|| ClamberError::JwtMissingFieldError {
+            field: self.created_at_claim().to_string(),
+        })?;
+        let max_age_secs = max_age.num_seconds();
+        if now.saturating_sub(created_at) > max_age_secs.saturating_add(self.leeway()) {
+            return Err(ClamberError::JwtTooOldError {
+                created_at,
+                max_age_secs,
+            });
+        }
+        Ok(())
+    }
+
+    /// 校验 exp / nbf / 签发时间 / iss / aud 声明与吊销状态
+    fn check_claims(&self, claims: &Claims) -> Result<()> {
+        let now = self.now().timestamp();
+        let leeway = self.leeway();
+
+        // 检查过期时间，允许 leeway 秒的时钟偏差
+        let exp_timestamp = required_timestamp(claims, "exp")?;
+        if exp_timestamp.saturating_add(leeway) <= now {
+            return Err(ClamberError::JwtExpiredError);
+        }
+
+        // 未携带 nbf 的 token 视为立即生效
+        if let Some(not_before) = claims::timestamp(claims, "nbf")?
+            && not_before.saturating_sub(leeway) > now
+        {
+            return Err(ClamberError::JwtNotYetValidError { not_before });
+        }
+
+        if self.config.max_token_age.is_some() {
+            let created_at = claims::timestamp(claims, self.created_at_claim())?;
+            self.check_age(created_at, now)?;
+        }
+
+        if let Some(expected) = &self.config.issuer {
+            let actual = required_str(claims, "iss")?;
+            if actual != expected {
+                return Err(ClamberError::JwtIssuerMismatchError {
+                    expected: expected.clone(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+
+        if let Some(expected) = &self.config.audience {
+            let actual = required_str(claims, "aud")?;
+            if actual != expected {
+                return Err(ClamberError::JwtAudienceMismatchError {
+                    expected: expected.clone(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+
+        // 未携带 jti 的旧 token 无法吊销，直接放行
+        if let Some(store) = &self.revocation
+            && let Some(jti) = claims.get("jti").and_then(Value::as_str)
+            && store.is_revoked(jti)
+        {
+            return Err(ClamberError::JwtRevokedError);
+        }
+
+        Ok(())
+    }
+}
+
+/// 签名不正确（含算法不符、密钥不可用），区别于 token 本身格式错误
+fn is_signature_error(err: &ClamberError) -> bool {
+    match err {
+        ClamberError::JwtAlgorithmMismatchError { .. } | ClamberError::JwtKeyError { .. } => true,
+        ClamberError::JwtVerifyError {
+            source: Some(source),
+            ..
+        } => matches!(
+            source.downcast_ref::<jwt::Error>(),
+            // HMAC 校验失败时 jwt 返回 RustCryptoMac
+            Some(jwt::Error::InvalidSignature | jwt::Error::RustCryptoMac(_))
+        ),
+        _ => false,
+    }
+}
+
+/// 生成唯一的 `jti`：128 位系统随机数的 base64url 编码（22 个字符）
+///
+/// 不依赖工作者ID等部署配置，多副本共享吊销存储时也不会相互冲突。
+fn generate_jti() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| ClamberError::JwtSignError {
+        details: format!("生成 jti 失败: {}", e),
+        source: None,
+    })?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+// 管理器需要能在线程间共享（如 axum 的 State）
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<JwtManager>();
+};
+
+/// payload 或 token 不超过该字节数且使用 HMAC 密钥时，异步方法直接在当前任务中执行
+#[cfg(feature = "async")]
+pub const ASYNC_INLINE_BYTES: usize = 16 * 1024;
+
+#[cfg(feature = "async")]
+impl JwtManager {
+    /// [`JwtManager::generate_token`] 的异步版本
+    ///
+    /// HMAC 密钥且 payload 序列化后不超过 [`ASYNC_INLINE_BYTES`] 时同步执行；否则（含 RSA / ECDSA 签名）
+    /// 在后台线程池中签发，不阻塞异步运行时的工作线程。
+    pub async fn generate_token_async<T>(&self, payload: T) -> Result<String>
+    where
+        T: Serialize + Send + 'static,
+    {
+        let size = serde_json::to_vec(&payload).map_or(0, |bytes| bytes.len());
+        if self.runs_inline(size) {
+            return self.generate_token(&payload);
+        }
+        let manager = self.clone();
+        crate::offload::offload(move || manager.generate_token(&payload)).await
+    }
+
+    /// [`JwtManager::verify_token`] 的异步版本，阈值同 [`JwtManager::generate_token_async`]（按 token 长度计算）
+    pub async fn verify_token_async<T>(&self, token: &str) -> Result<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        if self.runs_inline(token.len()) {
+            return self.verify_token(token);
+        }
+        let manager = self.clone();
+        let token = token.to_string();
+        crate::offload::offload(move || manager.verify_token(&token)).await
+    }
+
+    fn runs_inline(&self, size: usize) -> bool {
+        self.signer.is_hmac() && size <= ASYNC_INLINE_BYTES
+    }
+}
+
+/// token 状态，见 [`JwtManager::validate`]
+#[derive(Debug)]
+pub enum TokenStatus {
+    /// 有效
+    Valid {
+        /// 距离过期的时长
+        expires_in: Duration,
+    },
+    /// 签名正确但已过期，可用 refresh token 换取新 token
+    Expired {
+        /// 过期时间（秒级时间戳）
+        expired_at: i64,
+    },
+    /// 签名不正确，可能被伪造
+    InvalidSignature,
+    /// 无法解析（格式错误、exp 缺失或不是数字等）
+    Malformed,
+    /// 签名正确但未通过其他校验（iss / aud / nbf / 已吊销等）
+    Rejected(ClamberError),
+}
+
+/// 已验证签名的 token 声明信息，见 [`JwtManager::decode_claims`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenClaims {
+    /// 过期时间（秒级时间戳）
+    pub exp: i64,
+    /// 签发时间（对应 `createAt` 声明，平铺模式下为 `iat`），旧 token 未携带时为 `None`
+    pub created_at: Option<DateTime<Utc>>,
+    /// 自签发起经过的时长，与 [`JwtConfig::max_token_age`] 比较即可判断是否需要重新认证
+    pub age: Option<Duration>,
+    /// 距离过期的时长，已过期时为负
+    pub expires_in: Duration,
+    /// 是否已过期
+    pub is_expired: bool,
+    /// 原始 payload
+    pub payload: serde_json::Value,
+}
+
+/// 未经签名验证的 token 内容，仅用于排障与展示
+#[derive(Debug, Clone, Serialize)]
+pub struct UnverifiedToken {
+    /// JWT header
+    pub header: serde_json::Value,
+    /// JWT claims
+    pub claims: BTreeMap<String, serde_json::Value>,
+}
+
+impl UnverifiedToken {
+    /// `exp` 声明（秒级时间戳），兼容字符串与数字两种写法
+    pub fn expires_at(&self) -> Option<i64> {
+        match self.claims.get("exp")? {
+            serde_json::Value::String(s) => s.parse().ok(),
+            serde_json::Value::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// 是否已过期；缺少 `exp` 时返回 `None`
+    pub fn is_expired(&self) -> Option<bool> {
+        self.expires_at().map(|exp| exp <= Utc::now().timestamp())
+    }
+}
+
+/// 不校验签名，直接解码 token 的 header 与 claims
+///
+/// 结果不可信，切勿用于鉴权；鉴权请使用 [`JwtManager::verify_token`]。
+pub fn decode_unverified(token: &str) -> Result<UnverifiedToken> {
+    let token: jwt::Token<serde_json::Value, BTreeMap<String, serde_json::Value>, _> =
+        jwt::Token::parse_unverified(token)?;
+    let (header, claims) = token.into();
+    Ok(UnverifiedToken { header, claims })
+}
+
+/// JWT header 中的常用字段，见 [`peek_header`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwtHeader {
+    /// 签名算法，如 `HS256`
+    pub alg: String,
+    /// token 类型，本模块签发的 token 不写入
+    #[serde(default)]
+    pub typ: Option<String>,
+    /// 密钥标识，见 [`JwtConfig::key_id`]
+    #[serde(default)]
+    pub kid: Option<String>,
+}
+
+/// 不校验签名，只解码 token 的 header，用于按 `kid` / `alg` 选择 [`JwtManager`]
+///
+/// 格式错误时返回 [`ClamberError::JwtVerifyError`]。
+pub fn peek_header(token: &str) -> Result<JwtHeader> {
+    let mut segments = token.split('.');
+    let (Some(header), Some(_), Some(_), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return Err(ClamberError::JwtVerifyError {
+            details: "token 应由 header.claims.signature 三段组成".to_string(),
+            source: None,
+        });
+    };
+    Ok(<JwtHeader as jwt::FromBase64>::from_base64(header)?)
 }
 
 // 便利函数：使用默认配置
+#[cfg(not(feature = "strict-default"))]
+use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
+
+static DEFAULT_MANAGER: OnceCell<JwtManager> = OnceCell::new();
+// 未设置默认配置且关闭 strict-default 时使用，密钥为内置的不安全默认值
+#[cfg(not(feature = "strict-default"))]
+static BUILTIN_MANAGER: Lazy<JwtManager> = Lazy::new(JwtManager::default);
+
+/// 设置 `verify_token`、`is_valid_token`、`decode_claims`、`generate_token_default` 使用的默认配置
+///
+/// 在进程内仅能设置一次，重复调用返回 `JwtError`。
+pub fn set_default_config(config: JwtConfig) -> Result<()> {
+    DEFAULT_MANAGER
+        .set(JwtManager::new(config))
+        .map_err(|_| ClamberError::JwtError {
+            message: "默认 JWT 配置已设置，只能设置一次".to_string(),
+        })
+}
+
+/// 获取默认管理器
+///
+/// 启用 `strict-default`（默认启用）时，未调用 [`set_default_config`] 返回 `JwtKeyError`，
+/// 避免误用内置密钥；关闭后回退到 `JwtConfig::default()`。
+fn default_manager() -> Result<&'static JwtManager> {
+    if let Some(manager) = DEFAULT_MANAGER.get() {
+        return Ok(manager);
+    }
+    #[cfg(feature = "strict-default")]
+    {
+        Err(ClamberError::JwtKeyError {
+            details: "未设置默认 JWT 配置，拒绝使用内置密钥；请先调用 token::set_default_config"
+                .to_string(),
+            source: None,
+        })
+    }
+    #[cfg(not(feature = "strict-default"))]
+    {
+        Ok(&BUILTIN_MANAGER)
+    }
+}
+
 pub fn generate_token<T>(payload: &T, config: JwtConfig) -> Result<String>
 where
     T: Serialize,
@@ -168,23 +1091,36 @@ where
     manager.generate_token(payload)
 }
 
+/// 使用默认配置（见 [`set_default_config`]）生成 token
+pub fn generate_token_default<T>(payload: &T) -> Result<String>
+where
+    T: Serialize,
+{
+    default_manager()?.generate_token(payload)
+}
+
 pub fn verify_token<T>(token: &str) -> Result<T>
 where
     T: DeserializeOwned,
 {
-    let manager = JwtManager::default();
-    manager.verify_token(token)
+    default_manager()?.verify_token(token)
 }
 
 pub fn is_valid_token(token: &str) -> bool {
-    let manager = JwtManager::default();
-    manager.is_valid_token(token)
+    default_manager().is_ok_and(|manager| manager.is_valid_token(token))
+}
+
+pub fn decode_claims(token: &str) -> Result<TokenClaims> {
+    default_manager()?.decode_claims(token)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hmac::{Hmac, Mac};
+    use jwt::SignWithKey;
     use serde::{Deserialize, Serialize};
+    use sha2::Sha256;
 
     #[derive(Debug, Serialize, Deserialize, PartialEq)]
     struct TestUser {
@@ -193,6 +1129,39 @@ mod tests {
         pub role: String,
     }
 
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_generate_and_verify() {
+        let manager = JwtManager::new(JwtConfig::new("async_secret", 1).max_payload_bytes(None));
+        let user = TestUser {
+            id: "1".to_string(),
+            name: "small".to_string(),
+            role: "user".to_string(),
+        };
+        let token = manager.generate_token_async(user).await.unwrap();
+        let decoded: TestUser = manager.verify_token_async(&token).await.unwrap();
+        assert_eq!(decoded.name, "small");
+
+        // 超过阈值的 payload 在后台线程池中处理，结果一致
+        let large = TestUser {
+            id: "2".to_string(),
+            name: "x".repeat(ASYNC_INLINE_BYTES * 2),
+            role: "user".to_string(),
+        };
+        let token = manager.generate_token_async(large).await.unwrap();
+        assert!(token.len() > ASYNC_INLINE_BYTES);
+        let decoded: TestUser = manager.verify_token_async(&token).await.unwrap();
+        assert_eq!(decoded.name.len(), ASYNC_INLINE_BYTES * 2);
+
+        let tampered = format!("{}x", token);
+        assert!(
+            manager
+                .verify_token_async::<TestUser>(&tampered)
+                .await
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_jwt_generate_and_verify() {
         let config = JwtConfig::new("test_secret", 1);
@@ -224,11 +1193,13 @@ mod tests {
             role: "user".to_string(),
         };
 
-        let token = generate_token(&user, JwtConfig::default()).unwrap();
-        let decoded_user: TestUser = verify_token(&token).unwrap();
+        // 默认配置相关的便利函数见 tests/jwt_default.rs
+        let token = generate_token(&user, JwtConfig::with_secret("convenience")).unwrap();
+        let manager = JwtManager::new(JwtConfig::with_secret("convenience"));
+        let decoded_user: TestUser = manager.verify_token(&token).unwrap();
 
         assert_eq!(user, decoded_user);
-        assert!(is_valid_token(&token));
+        assert!(!JwtManager::default().is_valid_token(&token));
     }
 
     #[test]
@@ -253,4 +1224,968 @@ mod tests {
         let token = manager1.generate_token(&user).unwrap();
         assert!(manager2.verify_token::<TestUser>(&token).is_err());
     }
+
+    #[test]
+    fn test_expiry_with_fixed_clock() {
+        use crate::testing::fixed_clock_jwt;
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let token = fixed_clock_jwt("secret", issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+
+        // 过期前一秒仍有效，到达过期时间即失效
+        let exp = issued_at + JwtConfig::default().expire;
+        let before = fixed_clock_jwt("secret", exp - Duration::seconds(1));
+        assert_eq!(before.verify_token::<String>(&token).unwrap(), "payload");
+        let at = fixed_clock_jwt("secret", exp);
+        assert!(matches!(
+            at.verify_token::<String>(&token),
+            Err(ClamberError::JwtExpiredError)
+        ));
+        assert!(!at.is_valid_token(&token));
+    }
+
+    #[test]
+    fn test_per_call_expiry() {
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let at = |offset: i64| {
+            let now = issued_at + Duration::seconds(offset);
+            JwtManager::new(JwtConfig::with_secret("secret")).with_clock(move || now)
+        };
+
+        // 有效期 90 秒：89 秒时有效，90 秒时过期
+        let token = at(0)
+            .generate_token_valid_for(&"payload", Duration::seconds(90))
+            .unwrap();
+        assert_eq!(decode_claims_exp(&token), issued_at.timestamp() + 90);
+        assert_eq!(at(89).verify_token::<String>(&token).unwrap(), "payload");
+        assert!(matches!(
+            at(90).verify_token::<String>(&token),
+            Err(ClamberError::JwtExpiredError)
+        ));
+        assert!(!at(90).is_valid_token(&token));
+
+        let meeting_end = issued_at + Duration::hours(2);
+        let token = at(0)
+            .generate_token_with_expiry(&"payload", meeting_end)
+            .unwrap();
+        assert_eq!(decode_claims_exp(&token), meeting_end.timestamp());
+
+        // 过期时间不在未来
+        for expire_at in [issued_at, issued_at - Duration::seconds(1)] {
+            assert!(matches!(
+                at(0).generate_token_with_expiry(&"payload", expire_at),
+                Err(ClamberError::JwtError { .. })
+            ));
+        }
+        assert!(matches!(
+            at(0).generate_token_valid_for(&"payload", Duration::zero()),
+            Err(ClamberError::JwtError { .. })
+        ));
+
+        // 默认上限 365 天
+        assert!(
+            at(0)
+                .generate_token_valid_for(&"payload", Duration::days(365))
+                .is_ok()
+        );
+        assert!(matches!(
+            at(0).generate_token_valid_for(&"payload", Duration::days(366)),
+            Err(ClamberError::JwtError { .. })
+        ));
+        let capped =
+            JwtManager::new(JwtConfig::with_secret("secret").max_expire(Some(Duration::hours(1))))
+                .with_clock(move || issued_at);
+        assert!(matches!(
+            capped.generate_token_with_expiry(&"payload", meeting_end),
+            Err(ClamberError::JwtError { .. })
+        ));
+        let unlimited = JwtManager::new(JwtConfig::with_secret("secret").max_expire(None));
+        assert!(
+            unlimited
+                .generate_token_valid_for(&"payload", Duration::days(365 * 100))
+                .is_ok()
+        );
+    }
+
+    fn decode_claims_exp(token: &str) -> i64 {
+        decode_unverified(token).unwrap().expires_at().unwrap()
+    }
+
+    #[test]
+    fn test_leeway_tolerates_clock_skew() {
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let config = JwtConfig::with_secret("secret").expire_seconds(60);
+        let token = JwtManager::new(config.clone())
+            .with_clock(move || issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+
+        // 3 秒前已过期
+        let now = issued_at + Duration::seconds(63);
+        let tolerant =
+            JwtManager::new(config.clone().leeway_secs(5).unwrap()).with_clock(move || now);
+        assert_eq!(tolerant.verify_token::<String>(&token).unwrap(), "payload");
+        assert!(tolerant.is_valid_token(&token));
+        assert!(matches!(
+            tolerant.validate(&token),
+            TokenStatus::Valid { .. }
+        ));
+
+        let strict =
+            JwtManager::new(config.clone().leeway_secs(1).unwrap()).with_clock(move || now);
+        assert!(matches!(
+            strict.verify_token::<String>(&token),
+            Err(ClamberError::JwtExpiredError)
+        ));
+        assert!(!strict.is_valid_token(&token));
+
+        // nbf 同样容忍时钟偏差
+        let delayed = JwtManager::new(config.clone().not_before_secs(3))
+            .with_clock(move || issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+        let early = move || issued_at + Duration::seconds(1);
+        let tolerant = JwtManager::new(config.clone().leeway_secs(5).unwrap()).with_clock(early);
+        assert!(tolerant.verify_token::<String>(&delayed).is_ok());
+        assert!(tolerant.is_valid_token(&delayed));
+        let strict = JwtManager::new(config.clone().leeway_secs(1).unwrap()).with_clock(early);
+        assert!(matches!(
+            strict.verify_token::<String>(&delayed),
+            Err(ClamberError::JwtNotYetValidError { .. })
+        ));
+        assert!(!strict.is_valid_token(&delayed));
+
+        assert!(matches!(
+            config.leeway_secs(-1),
+            Err(ClamberError::JwtError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_expire_granularity() {
+        assert_eq!(JwtConfig::new("s", 2).expire, Duration::days(2));
+        let config = JwtConfig::with_secret("s").expire_minutes(15);
+        assert_eq!(config.expire, Duration::minutes(15));
+        assert_eq!(config.expire_hours(1).expire, Duration::hours(1));
+
+        let manager = JwtManager::new(JwtConfig::with_secret("secret").expire_seconds(1));
+        let token = manager.generate_token(&"payload").unwrap();
+        assert_eq!(manager.verify_token::<String>(&token).unwrap(), "payload");
+
+        // exp 精度为秒，等待 2 秒确保越过过期时间
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        assert!(matches!(
+            manager.verify_token::<String>(&token),
+            Err(ClamberError::JwtExpiredError)
+        ));
+    }
+
+    #[test]
+    fn test_standard_claims_roundtrip() {
+        let config = JwtConfig::with_secret("secret")
+            .issuer("clamber")
+            .audience("web")
+            .subject("user-1");
+        let manager = JwtManager::new(config);
+        let token = manager.generate_token(&"payload").unwrap();
+
+        let claims = decode_unverified(&token).unwrap().claims;
+        assert_eq!(claims["iss"], "clamber");
+        assert_eq!(claims["aud"], "web");
+        assert_eq!(claims["sub"], "user-1");
+        assert_eq!(manager.verify_token::<String>(&token).unwrap(), "payload");
+
+        // 未配置 iss/aud 时不校验，旧 token 也照常通过
+        let plain = JwtManager::new(JwtConfig::with_secret("secret"));
+        assert!(plain.is_valid_token(&token));
+        let legacy = plain.generate_token(&"legacy").unwrap();
+        assert_eq!(plain.verify_token::<String>(&legacy).unwrap(), "legacy");
+    }
+
+    #[test]
+    fn test_issuer_and_audience_validation() {
+        let token = JwtManager::new(JwtConfig::with_secret("secret").issuer("a").audience("web"))
+            .generate_token(&"payload")
+            .unwrap();
+
+        let other_issuer = JwtManager::new(JwtConfig::with_secret("secret").issuer("b"));
+        match other_issuer.verify_token::<String>(&token) {
+            Err(ClamberError::JwtIssuerMismatchError { expected, actual }) => {
+                assert_eq!((expected.as_str(), actual.as_str()), ("b", "a"));
+            }
+            other => panic!("expected issuer mismatch, got {:?}", other),
+        }
+        assert!(!other_issuer.is_valid_token(&token));
+
+        let other_audience = JwtManager::new(JwtConfig::with_secret("secret").audience("app"));
+        assert!(matches!(
+            other_audience.verify_token::<String>(&token),
+            Err(ClamberError::JwtAudienceMismatchError { .. })
+        ));
+
+        // 配置要求 iss/aud 但 token 未携带
+        let legacy = JwtManager::new(JwtConfig::with_secret("secret"))
+            .generate_token(&"payload")
+            .unwrap();
+        match other_issuer.verify_token::<String>(&legacy) {
+            Err(ClamberError::JwtMissingFieldError { field }) => assert_eq!(field, "iss"),
+            other => panic!("expected missing iss, got {:?}", other),
+        }
+        match other_audience.verify_token::<String>(&legacy) {
+            Err(ClamberError::JwtMissingFieldError { field }) => assert_eq!(field, "aud"),
+            other => panic!("expected missing aud, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_not_before_in_future() {
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let token = JwtManager::new(JwtConfig::with_secret("secret").not_before_secs(60))
+            .with_clock(move || issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+
+        let early = crate::testing::fixed_clock_jwt("secret", issued_at + Duration::seconds(59));
+        match early.verify_token::<String>(&token) {
+            Err(ClamberError::JwtNotYetValidError { not_before }) => {
+                assert_eq!(not_before, issued_at.timestamp() + 60);
+            }
+            other => panic!("expected not yet valid, got {:?}", other),
+        }
+        assert!(!early.is_valid_token(&token));
+
+        let later = crate::testing::fixed_clock_jwt("secret", issued_at + Duration::seconds(60));
+        assert_eq!(later.verify_token::<String>(&token).unwrap(), "payload");
+    }
+
+    #[test]
+    fn test_token_pair_and_refresh() {
+        use crate::testing::fixed_clock_jwt;
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let user = TestUser {
+            id: "1".to_string(),
+            name: "alice".to_string(),
+            role: "admin".to_string(),
+        };
+        let pair = fixed_clock_jwt("secret", issued_at)
+            .generate_token_pair(&user)
+            .unwrap();
+
+        let access = decode_unverified(&pair.access_token).unwrap();
+        assert_eq!(access.claims["token_type"], "access");
+        let default = JwtConfig::default();
+        assert_eq!(
+            access.expires_at(),
+            Some((issued_at + Duration::minutes(default.access_expire_minutes)).timestamp())
+        );
+        let refresh = decode_unverified(&pair.refresh_token).unwrap();
+        assert_eq!(refresh.claims["token_type"], "refresh");
+        assert_eq!(
+            refresh.expires_at(),
+            Some((issued_at + Duration::days(default.refresh_expire_days)).timestamp())
+        );
+
+        // access token 过期后用 refresh token 换取新的 access token
+        let later = fixed_clock_jwt("secret", issued_at + Duration::days(1));
+        assert!(matches!(
+            later.verify_token_of_type::<TestUser>(&pair.access_token, TokenType::Access),
+            Err(ClamberError::JwtExpiredError)
+        ));
+        let renewed = later
+            .refresh_access_token::<TestUser>(&pair.refresh_token)
+            .unwrap();
+        let decoded: TestUser = later
+            .verify_token_of_type(&renewed, TokenType::Access)
+            .unwrap();
+        assert_eq!(decoded, user);
+    }
+
+    #[test]
+    fn test_token_type_mismatch() {
+        let manager = JwtManager::new(JwtConfig::with_secret("secret"));
+        let pair = manager.generate_token_pair(&"payload").unwrap();
+
+        match manager.verify_token_of_type::<String>(&pair.refresh_token, TokenType::Access) {
+            Err(ClamberError::JwtTokenTypeMismatchError { expected, actual }) => {
+                assert_eq!((expected.as_str(), actual.as_str()), ("access", "refresh"));
+            }
+            other => panic!("expected token type mismatch, got {:?}", other),
+        }
+        assert!(matches!(
+            manager.refresh_access_token::<String>(&pair.access_token),
+            Err(ClamberError::JwtTokenTypeMismatchError { .. })
+        ));
+
+        // 未携带 token_type 的普通 token 无法用于区分类型的场景
+        let plain = manager.generate_token(&"payload").unwrap();
+        match manager.verify_token_of_type::<String>(&plain, TokenType::Access) {
+            Err(ClamberError::JwtMissingFieldError { field }) => assert_eq!(field, "token_type"),
+            other => panic!("expected missing token_type, got {:?}", other),
+        }
+        assert_eq!(manager.verify_token::<String>(&plain).unwrap(), "payload");
+    }
+
+    #[test]
+    fn test_decode_claims_flags_expiry() {
+        use crate::testing::fixed_clock_jwt;
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let token = fixed_clock_jwt("secret", issued_at)
+            .generate_token(&serde_json::json!({ "id": 7 }))
+            .unwrap();
+        let exp = (issued_at + JwtConfig::default().expire).timestamp();
+
+        let claims = fixed_clock_jwt("secret", issued_at + Duration::hours(1))
+            .decode_claims(&token)
+            .unwrap();
+        assert_eq!(claims.exp, exp);
+        assert_eq!(claims.created_at, Some(issued_at));
+        assert_eq!(
+            claims.expires_in,
+            JwtConfig::default().expire - Duration::hours(1)
+        );
+        assert!(!claims.is_expired);
+        assert_eq!(claims.payload["id"], 7);
+
+        // 过期但签名正确：不报错，只标记
+        let expired = fixed_clock_jwt("secret", issued_at + Duration::days(30))
+            .decode_claims(&token)
+            .unwrap();
+        assert!(expired.is_expired);
+        assert!(expired.expires_in < Duration::zero());
+
+        // 签名错误：报错
+        assert!(matches!(
+            fixed_clock_jwt("other", issued_at).decode_claims(&token),
+            Err(ClamberError::JwtVerifyError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_hmac_algorithms_roundtrip() {
+        for algorithm in [
+            JwtAlgorithm::HS256,
+            JwtAlgorithm::HS384,
+            JwtAlgorithm::HS512,
+        ] {
+            let manager = JwtManager::new(JwtConfig::with_secret("secret").algorithm(algorithm));
+            let token = manager.generate_token(&"payload").unwrap();
+
+            let header = decode_unverified(&token).unwrap().header;
+            assert_eq!(header["alg"], format!("{:?}", algorithm));
+            assert_eq!(manager.verify_token::<String>(&token).unwrap(), "payload");
+            assert!(manager.is_valid_token(&token));
+        }
+    }
+
+    #[test]
+    fn test_algorithm_mismatch_rejected() {
+        let hs512 =
+            JwtManager::new(JwtConfig::with_secret("secret").algorithm(JwtAlgorithm::HS512));
+        let token = JwtManager::new(JwtConfig::with_secret("secret"))
+            .generate_token(&"payload")
+            .unwrap();
+
+        match hs512.verify_token::<String>(&token) {
+            Err(ClamberError::JwtAlgorithmMismatchError { expected, actual }) => {
+                assert_eq!((expected.as_str(), actual.as_str()), ("HS512", "HS256"));
+            }
+            other => panic!("expected algorithm mismatch, got {:?}", other),
+        }
+        assert!(!hs512.is_valid_token(&token));
+        assert!(hs512.decode_claims(&token).is_err());
+    }
+
+    #[test]
+    fn test_revoked_token_rejected() {
+        let store = Arc::new(MemoryRevocationStore::new());
+        let manager =
+            JwtManager::new(JwtConfig::with_secret("secret")).with_revocation_store(store.clone());
+
+        let token = manager.generate_token(&"payload").unwrap();
+        let other = manager.generate_token(&"payload").unwrap();
+        let jti = |t: &str| decode_unverified(t).unwrap().claims["jti"].clone();
+        assert_ne!(jti(&token), jti(&other));
+        // 128 位随机数的 base64url 编码
+        let id = jti(&token);
+        assert_eq!(
+            URL_SAFE_NO_PAD.decode(id.as_str().unwrap()).unwrap().len(),
+            16
+        );
+
+        assert!(manager.is_valid_token(&token));
+        manager.revoke_token(&token).unwrap();
+        assert!(store.is_revoked(jti(&token).as_str().unwrap()));
+
+        assert!(matches!(
+            manager.verify_token::<String>(&token),
+            Err(ClamberError::JwtRevokedError)
+        ));
+        assert!(!manager.is_valid_token(&token));
+        // 其他 token 不受影响
+        assert_eq!(manager.verify_token::<String>(&other).unwrap(), "payload");
+
+        // 未配置吊销存储时不做检查，也无法吊销
+        let plain = JwtManager::new(JwtConfig::with_secret("secret"));
+        assert!(plain.is_valid_token(&token));
+        assert!(matches!(
+            plain.revoke_token(&other),
+            Err(ClamberError::JwtError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_revocation_covers_leeway() {
+        let store = Arc::new(MemoryRevocationStore::new());
+        let config = JwtConfig::with_secret("secret")
+            .expire_seconds(60)
+            .leeway_secs(30)
+            .unwrap();
+
+        // token 已在 10 秒前过期，但仍处于 leeway 容忍期内
+        let now = Utc::now();
+        let issued_at = now - Duration::seconds(70);
+        let token = JwtManager::new(config.clone())
+            .with_clock(move || issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+        let manager = JwtManager::new(config)
+            .with_clock(move || now)
+            .with_revocation_store(store.clone());
+        assert!(manager.is_valid_token(&token));
+
+        manager.revoke_token(&token).unwrap();
+        assert_eq!(store.len(), 1);
+        assert!(matches!(
+            manager.verify_token::<String>(&token),
+            Err(ClamberError::JwtRevokedError)
+        ));
+        assert!(!manager.is_valid_token(&token));
+    }
+
+    #[test]
+    fn test_validate_status() {
+        use crate::testing::fixed_clock_jwt;
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let manager = fixed_clock_jwt("secret", issued_at);
+        let token = manager.generate_token(&"payload").unwrap();
+        let exp = (issued_at + JwtConfig::default().expire).timestamp();
+
+        match manager.validate(&token) {
+            TokenStatus::Valid { expires_in } => {
+                assert_eq!(expires_in, JwtConfig::default().expire)
+            }
+            other => panic!("expected valid, got {:?}", other),
+        }
+
+        let later = fixed_clock_jwt("secret", issued_at + Duration::days(30));
+        match later.validate(&token) {
+            TokenStatus::Expired { expired_at } => assert_eq!(expired_at, exp),
+            other => panic!("expected expired, got {:?}", other),
+        }
+        assert!(!later.is_valid_token(&token));
+
+        let forged = fixed_clock_jwt("other", issued_at);
+        assert!(matches!(
+            forged.validate(&token),
+            TokenStatus::InvalidSignature
+        ));
+        assert!(matches!(
+            manager.validate("not-a-token"),
+            TokenStatus::Malformed
+        ));
+
+        // 签名正确但 exp 不是数字
+        let key: Hmac<Sha256> = Hmac::new_from_slice(b"secret").unwrap();
+        let mut claims = BTreeMap::new();
+        claims.insert("exp".to_string(), "soon".to_string());
+        let bad_exp = claims.sign_with_key(&key).unwrap();
+        assert!(matches!(manager.validate(&bad_exp), TokenStatus::Malformed));
+
+        let wrong_issuer = JwtManager::new(JwtConfig::with_secret("secret").issuer("other"))
+            .with_clock(move || issued_at);
+        assert!(matches!(
+            wrong_issuer.validate(&token),
+            TokenStatus::Rejected(ClamberError::JwtMissingFieldError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_legacy_string_exp_still_valid() {
+        let manager = JwtManager::new(JwtConfig::with_secret("secret"));
+        let user = TestUser {
+            id: "1".to_string(),
+            name: "alice".to_string(),
+            role: "admin".to_string(),
+        };
+        let token = manager.generate_token(&user).unwrap();
+        let mut claims = decode_unverified(&token).unwrap().claims;
+        assert!(claims["exp"].is_i64());
+
+        // 旧版本把 exp 写为字符串
+        let exp = claims["exp"].as_i64().unwrap();
+        claims.insert("exp".to_string(), Value::String(exp.to_string()));
+        let key: Hmac<Sha256> = Hmac::new_from_slice(b"secret").unwrap();
+        let legacy = claims.clone().sign_with_key(&key).unwrap();
+        assert!(manager.is_valid_token(&legacy));
+        assert_eq!(manager.verify_token::<TestUser>(&legacy).unwrap(), user);
+
+        claims.insert("exp".to_string(), Value::String("soon".to_string()));
+        let bad_exp = claims.sign_with_key(&key).unwrap();
+        assert!(!manager.is_valid_token(&bad_exp));
+    }
+
+    #[test]
+    fn test_max_token_age() {
+        use crate::testing::fixed_clock_jwt;
+
+        // 10 小时前签发、7 天后才过期的 token
+        let issued_at = Utc::now() - Duration::hours(10);
+        let old_token = fixed_clock_jwt("secret", issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+        let lenient = JwtManager::new(JwtConfig::with_secret("secret"));
+        assert_eq!(
+            lenient.verify_token::<String>(&old_token).unwrap(),
+            "payload"
+        );
+        let claims = lenient.decode_claims(&old_token).unwrap();
+        assert_eq!(
+            claims.created_at.unwrap().timestamp(),
+            issued_at.timestamp()
+        );
+        assert!(claims.age.unwrap() >= Duration::hours(10));
+
+        let strict =
+            JwtManager::new(JwtConfig::with_secret("secret").max_token_age(Duration::hours(8)));
+        match strict.verify_token::<String>(&old_token) {
+            Err(ClamberError::JwtTooOldError {
+                created_at,
+                max_age_secs,
+            }) => {
+                assert_eq!(created_at, issued_at.timestamp());
+                assert_eq!(max_age_secs, 8 * 3600);
+            }
+            other => panic!("expected too old error, got {:?}", other),
+        }
+        assert!(!strict.is_valid_token(&old_token));
+        assert!(matches!(
+            strict.validate(&old_token),
+            TokenStatus::Rejected(ClamberError::JwtTooOldError { .. })
+        ));
+        let fresh = strict.generate_token(&"payload").unwrap();
+        assert!(strict.is_valid_token(&fresh));
+
+        // 未携带 createAt 的旧 token 只在设置了 max_token_age 时被拒绝
+        let mut claims = decode_unverified(&fresh).unwrap().claims;
+        claims.remove("createAt");
+        let key: Hmac<Sha256> = Hmac::new_from_slice(b"secret").unwrap();
+        let legacy = claims.sign_with_key(&key).unwrap();
+        assert!(lenient.is_valid_token(&legacy));
+        assert_eq!(lenient.decode_claims(&legacy).unwrap().created_at, None);
+        assert!(!strict.is_valid_token(&legacy));
+        assert!(matches!(
+            strict.verify_token::<String>(&legacy),
+            Err(ClamberError::JwtMissingFieldError { field }) if field == "createAt"
+        ));
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Permissions {
+        user_id: String,
+        permissions: Vec<String>,
+    }
+
+    fn large_permissions() -> Permissions {
+        Permissions {
+            user_id: "42".to_string(),
+            permissions: (0..2000)
+                .map(|i| format!("orders:{}:read", i % 50))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_payload_size_limit() {
+        let payload = large_permissions();
+        let size = serde_json::to_string(&payload).unwrap().len();
+        assert!(size > 8192);
+
+        let manager = JwtManager::new(JwtConfig::with_secret("secret"));
+        let err = manager.generate_token(&payload).unwrap_err();
+        assert!(matches!(err, ClamberError::JwtError { .. }));
+        let message = err.to_string();
+        assert!(message.contains(&size.to_string()), "{}", message);
+        assert!(message.contains("8192"), "{}", message);
+
+        let flat = JwtManager::new(JwtConfig::with_secret("secret").flat_claims(true));
+        assert!(flat.generate_token(&payload).is_err());
+
+        let unlimited = JwtManager::new(JwtConfig::with_secret("secret").max_payload_bytes(None));
+        let token = unlimited.generate_token(&payload).unwrap();
+        assert_eq!(
+            unlimited.verify_token::<Permissions>(&token).unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_compressed_payload_roundtrip() {
+        let payload = large_permissions();
+        let manager = JwtManager::new(JwtConfig::with_secret("secret").compress_payload(true));
+        let token = manager.generate_token(&payload).unwrap();
+        assert!(token.len() < 8192);
+        assert_eq!(
+            manager.verify_token::<Permissions>(&token).unwrap(),
+            payload
+        );
+
+        let claims = decode_unverified(&token).unwrap().claims;
+        assert_eq!(claims["payload_enc"], "deflate");
+        // 识别依据为 payload_enc 声明，未开启压缩的管理器同样可以验证
+        let plain = JwtManager::new(JwtConfig::with_secret("secret"));
+        assert_eq!(plain.verify_token::<Permissions>(&token).unwrap(), payload);
+        // payload_enc 为保留声明
+        let (_, custom): (Permissions, _) = manager.verify_token_with_claims(&token).unwrap();
+        assert!(custom.is_empty());
+    }
+
+    #[test]
+    fn test_uncompressed_tokens_still_verify() {
+        let user = TestUser {
+            id: "1".to_string(),
+            name: "legacy".to_string(),
+            role: "user".to_string(),
+        };
+        let legacy = JwtManager::new(JwtConfig::with_secret("secret"))
+            .generate_token(&user)
+            .unwrap();
+        let manager = JwtManager::new(JwtConfig::with_secret("secret").compress_payload(true));
+        let decoded: TestUser = manager.verify_token(&legacy).unwrap();
+        assert_eq!(decoded.name, "legacy");
+    }
+
+    #[test]
+    fn test_flat_claims_roundtrip() {
+        use crate::testing::fixed_clock_jwt;
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let config = JwtConfig::with_secret("secret")
+            .flat_claims(true)
+            .issuer("clamber");
+        let manager = JwtManager::new(config).with_clock(move || issued_at);
+        let user = TestUser {
+            id: "1".to_string(),
+            name: "alice".to_string(),
+            role: "admin".to_string(),
+        };
+        let token = manager.generate_token(&user).unwrap();
+
+        let claims = decode_unverified(&token).unwrap().claims;
+        assert_eq!(claims["name"], "alice");
+        assert_eq!(claims["iat"], issued_at.timestamp());
+        assert!(claims["exp"].is_i64());
+        assert!(!claims.contains_key("payload"));
+        assert_eq!(manager.verify_token::<TestUser>(&token).unwrap(), user);
+
+        let decoded = manager.decode_claims(&token).unwrap();
+        assert_eq!(decoded.created_at, Some(issued_at));
+        assert_eq!(decoded.payload, serde_json::to_value(&user).unwrap());
+
+        // payload 必须是对象，且不能占用保留声明
+        assert!(matches!(
+            manager.generate_token(&"payload"),
+            Err(ClamberError::SerializationError { .. })
+        ));
+        assert!(matches!(
+            manager.generate_token(&serde_json::json!({ "exp": 1 })),
+            Err(ClamberError::SerializationError { .. })
+        ));
+
+        // 两种模式互不兼容
+        let nested = fixed_clock_jwt("secret", issued_at);
+        assert!(nested.verify_token::<TestUser>(&token).is_err());
+    }
+
+    #[test]
+    fn test_flat_claims_interop_with_jsonwebtoken() {
+        use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+
+        let manager = JwtManager::new(JwtConfig::with_secret("secret").flat_claims(true));
+        let user = TestUser {
+            id: "2".to_string(),
+            name: "bob".to_string(),
+            role: "user".to_string(),
+        };
+
+        // 本库签发，jsonwebtoken 验证（含 exp 校验）
+        let token = manager.generate_token(&user).unwrap();
+        let decoded = jsonwebtoken::decode::<TestUser>(
+            &token,
+            &DecodingKey::from_secret(b"secret"),
+            &Validation::new(Algorithm::HS256),
+        )
+        .unwrap();
+        assert_eq!(decoded.claims, user);
+
+        // jsonwebtoken 签发，本库验证
+        let exp = (Utc::now() + Duration::hours(1)).timestamp();
+        let foreign = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &serde_json::json!({ "id": "3", "name": "carol", "role": "user", "exp": exp }),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        let parsed: TestUser = manager.verify_token(&foreign).unwrap();
+        assert_eq!(parsed.name, "carol");
+    }
+
+    #[test]
+    fn test_key_id_header_and_resolver() {
+        let issuer_a = JwtManager::new(JwtConfig::with_secret("secret-a").key_id("key-a"));
+        let issuer_b = JwtManager::new(JwtConfig::with_secret("secret-b").key_id("key-b"));
+
+        // kid 写入 header 而非 claims，header 字段顺序固定
+        let token = issuer_b.generate_token(&"payload").unwrap();
+        assert_eq!(
+            peek_header(&token).unwrap(),
+            JwtHeader {
+                alg: "HS256".to_string(),
+                typ: None,
+                kid: Some("key-b".to_string()),
+            }
+        );
+        let unverified = decode_unverified(&token).unwrap();
+        assert_eq!(
+            unverified.header.to_string(),
+            r#"{"alg":"HS256","kid":"key-b"}"#
+        );
+        assert!(!unverified.claims.contains_key("kid"));
+        assert_eq!(issuer_b.verify_token::<String>(&token).unwrap(), "payload");
+
+        // 未设置 key_id 时 header 只有 alg
+        let plain = JwtManager::new(JwtConfig::with_secret("secret-a"))
+            .generate_token(&"payload")
+            .unwrap();
+        assert_eq!(peek_header(&plain).unwrap().kid, None);
+
+        for garbage in ["garbage", "a.b", "!!!.e30.sig", "a.b.c.d"] {
+            assert!(
+                matches!(
+                    peek_header(garbage),
+                    Err(ClamberError::JwtVerifyError { .. })
+                ),
+                "{}",
+                garbage
+            );
+        }
+
+        // 按 kid 选择第二个密钥验证
+        let resolver = |kid: &str| match kid {
+            "key-a" => Some(JwtConfig::with_secret("secret-a").key_id("key-a")),
+            "key-b" => Some(JwtConfig::with_secret("secret-b").key_id("key-b")),
+            _ => None,
+        };
+        assert_eq!(
+            issuer_a
+                .verify_with_kid_resolver::<String, _>(&token, resolver)
+                .unwrap(),
+            "payload"
+        );
+        assert!(matches!(
+            issuer_a.verify_token::<String>(&token),
+            Err(ClamberError::JwtVerifyError { .. })
+        ));
+
+        // 解析不到 kid，或解析出的密钥与签名不符
+        let unknown = JwtManager::new(JwtConfig::with_secret("secret-c").key_id("key-c"))
+            .generate_token(&"payload")
+            .unwrap();
+        assert!(matches!(
+            issuer_a.verify_with_kid_resolver::<String, _>(&unknown, resolver),
+            Err(ClamberError::JwtKeyError { .. })
+        ));
+        let forged = JwtManager::new(JwtConfig::with_secret("secret-c").key_id("key-b"))
+            .generate_token(&"payload")
+            .unwrap();
+        assert!(matches!(
+            issuer_a.verify_with_kid_resolver::<String, _>(&forged, resolver),
+            Err(ClamberError::JwtVerifyError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fallback_secrets_rotation() {
+        use crate::testing::fixed_clock_jwt;
+        use chrono::TimeZone;
+
+        let issued_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let old_token = fixed_clock_jwt("old", issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+        let config =
+            JwtConfig::with_secret("new").with_fallback_secrets(vec!["older".into(), "old".into()]);
+        let rotated = JwtManager::new(config.clone()).with_clock(move || issued_at);
+
+        assert_eq!(
+            rotated.verify_token::<String>(&old_token).unwrap(),
+            "payload"
+        );
+        assert!(rotated.is_valid_token(&old_token));
+
+        // 新 token 使用主密钥签发，不带旧密钥的管理器也能验证
+        let new_token = rotated.generate_token(&"payload").unwrap();
+        assert!(fixed_clock_jwt("new", issued_at).is_valid_token(&new_token));
+        assert!(!fixed_clock_jwt("old", issued_at).is_valid_token(&new_token));
+
+        // 未知密钥签发：返回主密钥的验证错误
+        let unknown = fixed_clock_jwt("unknown", issued_at)
+            .generate_token(&"payload")
+            .unwrap();
+        assert!(matches!(
+            rotated.verify_token::<String>(&unknown),
+            Err(ClamberError::JwtVerifyError { .. })
+        ));
+        assert!(matches!(
+            rotated.validate(&unknown),
+            TokenStatus::InvalidSignature
+        ));
+
+        // 旧密钥签发且已过期：仍区分为过期
+        let later = JwtManager::new(config).with_clock(move || issued_at + Duration::days(30));
+        assert!(matches!(
+            later.verify_token::<String>(&old_token),
+            Err(ClamberError::JwtExpiredError)
+        ));
+        assert!(matches!(
+            later.validate(&old_token),
+            TokenStatus::Expired { .. }
+        ));
+    }
+
+    #[test]
+    fn test_debug_redacts_secrets_and_shared_clone() {
+        let config = JwtConfig::with_secret("top-secret-key")
+            .with_fallback_secrets(vec!["old-secret-key".into()])
+            .issuer("clamber");
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("secret: \"***\""), "{}", debug);
+        assert!(!debug.contains("top-secret-key"), "{}", debug);
+        assert!(!debug.contains("old-secret-key"), "{}", debug);
+        assert!(debug.contains("clamber"), "{}", debug);
+
+        let manager = JwtManager::shared(config);
+        let debug = format!("{:?}", manager);
+        assert!(!debug.contains("top-secret-key"), "{}", debug);
+
+        // 克隆共享同一份配置与密钥
+        let cloned = (*manager).clone();
+        assert!(Arc::ptr_eq(&manager.config, &cloned.config));
+        assert!(Arc::ptr_eq(&manager.signer, &cloned.signer));
+        let token = cloned.generate_token(&"payload").unwrap();
+        assert_eq!(manager.verify_token::<String>(&token).unwrap(), "payload");
+    }
+
+    #[test]
+    fn test_custom_claims_roundtrip() {
+        let manager = JwtManager::new(JwtConfig::with_secret("secret").issuer("clamber"));
+        let extra = BTreeMap::from([
+            ("tenant_id".to_string(), "t-1".to_string()),
+            ("session_id".to_string(), "s-1".to_string()),
+        ]);
+        let token = manager
+            .generate_token_with_claims(&"payload", extra.clone())
+            .unwrap();
+
+        let (payload, claims) = manager.verify_token_with_claims::<String>(&token).unwrap();
+        assert_eq!(payload, "payload");
+        assert_eq!(claims, extra);
+        // 普通验证不受自定义声明影响
+        assert_eq!(manager.verify_token::<String>(&token).unwrap(), "payload");
+
+        for reserved in ["exp", "payload", "createAt", "iss", "jti"] {
+            let extra = BTreeMap::from([(reserved.to_string(), "x".to_string())]);
+            assert!(
+                matches!(
+                    manager.generate_token_with_claims(&"payload", extra),
+                    Err(ClamberError::JwtError { .. })
+                ),
+                "{} should be reserved",
+                reserved
+            );
+        }
+
+        // 平铺模式下不能与 payload 字段重名
+        let flat = JwtManager::new(JwtConfig::with_secret("secret").flat_claims(true));
+        let extra = BTreeMap::from([("id".to_string(), "x".to_string())]);
+        assert!(matches!(
+            flat.generate_token_with_claims(&serde_json::json!({ "id": 1 }), extra),
+            Err(ClamberError::JwtError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_error_variants_unchanged() {
+        let user = TestUser {
+            id: "1".to_string(),
+            name: "n".to_string(),
+            role: "r".to_string(),
+        };
+        let manager = JwtManager::new(JwtConfig::new("secret1", 1));
+
+        let err = manager
+            .verify_token::<TestUser>("invalid.jwt.token")
+            .unwrap_err();
+        assert!(matches!(err, ClamberError::JwtVerifyError { .. }));
+
+        let token = manager.generate_token(&user).unwrap();
+        let other = JwtManager::new(JwtConfig::new("secret2", 1));
+        let err = other.verify_token::<TestUser>(&token).unwrap_err();
+        assert!(matches!(err, ClamberError::JwtVerifyError { .. }));
+
+        // 手工签发 exp 非数字的 token
+        let key: Hmac<Sha256> = Hmac::new_from_slice(b"secret1").unwrap();
+        let mut claims = BTreeMap::new();
+        claims.insert("exp".to_string(), "soon".to_string());
+        let token = claims.sign_with_key(&key).unwrap();
+        let err = manager.verify_token::<TestUser>(&token).unwrap_err();
+        assert!(matches!(err, ClamberError::JwtError { .. }));
+
+        let mut claims = BTreeMap::new();
+        claims.insert("exp".to_string(), "1".to_string());
+        let token = claims.sign_with_key(&key).unwrap();
+        let err = manager.verify_token::<TestUser>(&token).unwrap_err();
+        assert!(matches!(err, ClamberError::JwtExpiredError));
+    }
+
+    #[test]
+    fn test_decode_unverified() {
+        let manager = JwtManager::new(JwtConfig::new("secret1", 1));
+        let token = manager.generate_token(&"hello").unwrap();
+
+        let decoded = decode_unverified(&token).unwrap();
+        assert_eq!(decoded.header["alg"], "HS256");
+        assert_eq!(decoded.claims["payload"], "\"hello\"");
+        assert_eq!(decoded.is_expired(), Some(false));
+
+        assert!(matches!(
+            decode_unverified("not-a-token"),
+            Err(ClamberError::JwtVerifyError { .. })
+        ));
+    }
 }
@@ -0,0 +1,78 @@
+//! token 吊销：按 `jti` 记录已吊销的 token，验证时拒绝。
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 已吊销 token 的存储
+///
+/// 实现方可基于 Redis、数据库等共享存储。`revoke` 收到的 `exp` 已计入
+/// [`JwtConfig::leeway_secs`](crate::token::JwtConfig::leeway_secs)，即 token 过期时间加上时钟偏差容忍；
+/// 记录必须至少保留到该时间戳（秒），之后才可以丢弃，否则 token 在容忍期内会重新被接受。
+pub trait TokenRevocationStore: Send + Sync {
+    /// `jti` 是否已被吊销
+    fn is_revoked(&self, jti: &str) -> bool;
+
+    /// 吊销 `jti`，记录保留到 `exp`（已包含 leeway）
+    fn revoke(&self, jti: &str, exp: i64);
+}
+
+/// 进程内的吊销存储，记录在 token 过期后自动失效
+#[derive(Debug, Default)]
+pub struct MemoryRevocationStore {
+    entries: Mutex<HashMap<String, i64>>,
+}
+
+impl MemoryRevocationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 未过期的吊销记录数
+    pub fn len(&self) -> usize {
+        let now = Utc::now().timestamp();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, exp| *exp > now);
+        entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl TokenRevocationStore for MemoryRevocationStore {
+    fn is_revoked(&self, jti: &str) -> bool {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(jti)
+            .is_some_and(|exp| *exp > Utc::now().timestamp())
+    }
+
+    fn revoke(&self, jti: &str, exp: i64) {
+        let now = Utc::now().timestamp();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        // 顺带清理已过期的记录
+        entries.retain(|_, exp| *exp > now);
+        if exp > now {
+            entries.insert(jti.to_string(), exp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_store_expires_entries() {
+        let store = MemoryRevocationStore::new();
+        let now = Utc::now().timestamp();
+
+        store.revoke("live", now + 60);
+        store.revoke("stale", now - 1);
+        assert!(store.is_revoked("live"));
+        assert!(!store.is_revoked("stale"));
+        assert!(!store.is_revoked("unknown"));
+        assert_eq!(store.len(), 1);
+    }
+}
@@ -0,0 +1,146 @@
+//! 后台写线程：日志先进入有界队列再由独立线程写入文件，支持按需刷新。
+use std::io::{self, Write};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread::{self, JoinHandle};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// 队列容量（条），写满时记录日志的线程阻塞等待，不丢弃日志
+const QUEUE_CAPACITY: usize = 128_000;
+
+enum Message {
+    Record(Vec<u8>),
+    /// 写完此前的所有记录并刷新后回复
+    Flush(mpsc::Sender<()>),
+    Shutdown,
+}
+
+/// 挂入 fmt layer 的 writer，每次写入作为一条记录发给后台线程
+#[derive(Clone)]
+pub(crate) struct BackgroundWriter {
+    sender: SyncSender<Message>,
+}
+
+impl Write for BackgroundWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sender
+            .send(Message::Record(buf.to_vec()))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "日志写线程已退出"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for BackgroundWriter {
+    type Writer = BackgroundWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// 后台写线程的控制端，销毁时写完队列中的记录后退出
+pub(crate) struct BackgroundGuard {
+    sender: SyncSender<Message>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl BackgroundGuard {
+    /// 阻塞直到此前提交的记录全部写入并刷新
+    pub(crate) fn flush(&self) {
+        let (ack, done) = mpsc::channel();
+        if self.sender.send(Message::Flush(ack)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+}
+
+impl Drop for BackgroundGuard {
+    fn drop(&mut self) {
+        let _ = self.sender.send(Message::Shutdown);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// 启动写入 `writer` 的后台线程
+pub(crate) fn spawn<W>(name: &str, writer: W) -> io::Result<(BackgroundWriter, BackgroundGuard)>
+where
+    W: Write + Send + 'static,
+{
+    let (sender, receiver) = mpsc::sync_channel(QUEUE_CAPACITY);
+    let thread = thread::Builder::new()
+        .name(format!("clamber-log-{}", name))
+        .spawn(move || run(writer, receiver))?;
+    Ok((
+        BackgroundWriter {
+            sender: sender.clone(),
+        },
+        BackgroundGuard {
+            sender,
+            thread: Some(thread),
+        },
+    ))
+}
+
+fn run<W: Write>(mut writer: W, receiver: Receiver<Message>) {
+    // 写文件失败时无处上报，忽略错误继续处理后续记录
+    while let Ok(message) = receiver.recv() {
+        match message {
+            Message::Record(record) => {
+                let _ = writer.write_all(&record);
+            }
+            Message::Flush(ack) => {
+                let _ = writer.flush();
+                let _ = ack.send(());
+            }
+            Message::Shutdown => break,
+        }
+    }
+    let _ = writer.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_flush_and_shutdown_drain_queue() {
+        let sink = Shared::default();
+        let (writer, guard) = spawn("test", sink.clone()).unwrap();
+        for i in 0..1000 {
+            writeln!(writer.make_writer(), "line {}", i).unwrap();
+        }
+        guard.flush();
+        assert_eq!(
+            String::from_utf8_lossy(&sink.0.lock().unwrap())
+                .lines()
+                .count(),
+            1000
+        );
+
+        writeln!(writer.make_writer(), "last").unwrap();
+        drop(guard);
+        assert!(String::from_utf8_lossy(&sink.0.lock().unwrap()).ends_with("last\n"));
+        // 线程退出后写入返回错误而不是阻塞
+        assert!(writeln!(writer.make_writer(), "after shutdown").is_err());
+    }
+}
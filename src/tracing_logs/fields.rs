@@ -0,0 +1,157 @@
+//! 请求级字段：为 span 标记 `request_id`，并在 span 层级中查找当前请求的ID。
+//!
+//! ```
+//! use clamber_core::tracing_logs::fields::{RequestSpan, current_request_id};
+//!
+//! let span = RequestSpan::with_id("req-1");
+//! let _enter = span.enter();
+//! // 需要安装含 RequestIdLayer 的 subscriber（logger_start_with_config 已自动包含）
+//! let _ = current_request_id();
+//! ```
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Span, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+/// 请求ID的字段名
+pub const REQUEST_ID_FIELD: &str = "request_id";
+
+/// 保存在 span 扩展中的请求ID
+#[derive(Debug, Clone)]
+struct RequestId(String);
+
+/// 记录 span 上的 `request_id` 字段，供 [`current_request_id`] 查找
+///
+/// 本库的日志初始化函数已自动安装；自行组合 subscriber 时通过 [`with_request_id`] 添加。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+/// 创建 [`RequestIdLayer`]
+pub fn with_request_id() -> RequestIdLayer {
+    RequestIdLayer
+}
+
+impl<S> Layer<S> for RequestIdLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = RequestIdVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(request_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(RequestId(request_id));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = RequestIdVisitor(None);
+        values.record(&mut visitor);
+        if let (Some(request_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().replace(RequestId(request_id));
+        }
+    }
+}
+
+struct RequestIdVisitor(Option<String>);
+
+impl Visit for RequestIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == REQUEST_ID_FIELD {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == REQUEST_ID_FIELD {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == REQUEST_ID_FIELD {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+/// 创建带 `request_id` 字段的 INFO 级 `request` span
+pub struct RequestSpan;
+
+impl RequestSpan {
+    /// 生成 128 位随机数的十六进制请求ID（32 个字符）；系统随机源不可用时退化为纳秒时间戳
+    ///
+    /// 不经过默认 Snowflake 管理器，不影响之后调用 `snowflake_utils::init_default`。
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> Span {
+        let mut bytes = [0u8; 16];
+        let request_id = match getrandom::fill(&mut bytes) {
+            Ok(()) => bytes.iter().map(|b| format!("{:02x}", b)).collect(),
+            Err(_) => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+                .to_string(),
+        };
+        Self::with_id(request_id)
+    }
+
+    /// 使用已有的请求ID（如上游传入的 `X-Request-Id`）
+    pub fn with_id(request_id: impl Into<String>) -> Span {
+        let request_id = request_id.into();
+        tracing::info_span!("request", request_id = %request_id)
+    }
+}
+
+/// 从当前 span 向上查找最近的 `request_id`，不在请求 span 内或未安装 [`RequestIdLayer`] 时返回 None
+pub fn current_request_id() -> Option<String> {
+    tracing::dispatcher::get_default(|dispatch| {
+        let registry = dispatch.downcast_ref::<Registry>()?;
+        let current = dispatch.current_span();
+        let span = registry.span(current.id()?)?;
+        span.scope()
+            .find_map(|span| span.extensions().get::<RequestId>().map(|id| id.0.clone()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_current_request_id_walks_parents() {
+        let subscriber = tracing_subscriber::registry().with(with_request_id());
+        tracing::subscriber::with_default(subscriber, || {
+            assert_eq!(current_request_id(), None);
+
+            let request = RequestSpan::with_id("req-42");
+            let _request = request.enter();
+            assert_eq!(current_request_id().as_deref(), Some("req-42"));
+
+            let child = tracing::info_span!("db_query");
+            let _child = child.enter();
+            assert_eq!(current_request_id().as_deref(), Some("req-42"));
+
+            // 内层请求 span 覆盖外层
+            let late = tracing::info_span!("retry", request_id = tracing::field::Empty);
+            let _late = late.enter();
+            late.record(REQUEST_ID_FIELD, "req-43");
+            assert_eq!(current_request_id().as_deref(), Some("req-43"));
+        });
+    }
+
+    #[test]
+    fn test_generated_request_ids_differ() {
+        let subscriber = tracing_subscriber::registry().with(with_request_id());
+        tracing::subscriber::with_default(subscriber, || {
+            let first = RequestSpan::new();
+            let first = first.in_scope(current_request_id).unwrap();
+            let second = RequestSpan::new();
+            let second = second.in_scope(current_request_id).unwrap();
+            assert_ne!(first, second);
+            assert_eq!(first.len(), 32);
+            assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+        });
+    }
+}
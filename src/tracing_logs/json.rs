@@ -0,0 +1,283 @@
+//! JSON 事件格式化：支持时间/消息字段改名、span 字段平铺与字段排除。
+use super::time::LogTimer;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{JsonFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// JSON 输出字段定制选项
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonOptions {
+    /// 是否将 span 字段平铺到顶层（否则输出到 `span` / `spans`）
+    pub flatten_spans: bool,
+    /// 时间字段名
+    pub timestamp_key: String,
+    /// 消息字段名
+    pub message_key: String,
+    /// 需要排除的字段名（对顶层、fields 与 span 字段均生效）
+    pub exclude_fields: Vec<String>,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            flatten_spans: false,
+            timestamp_key: "timestamp".to_string(),
+            message_key: "message".to_string(),
+            exclude_fields: Vec::new(),
+        }
+    }
+}
+
+impl JsonOptions {
+    /// 创建默认的 JSON 选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置是否平铺 span 字段
+    pub fn flatten_spans(mut self, flatten: bool) -> Self {
+        self.flatten_spans = flatten;
+        self
+    }
+
+    /// 设置时间字段名
+    pub fn timestamp_key(mut self, key: impl Into<String>) -> Self {
+        self.timestamp_key = key.into();
+        self
+    }
+
+    /// 设置消息字段名
+    pub fn message_key(mut self, key: impl Into<String>) -> Self {
+        self.message_key = key.into();
+        self
+    }
+
+    /// 添加需要排除的字段
+    pub fn exclude_field(mut self, field: impl Into<String>) -> Self {
+        self.exclude_fields.push(field.into());
+        self
+    }
+
+    fn is_excluded(&self, key: &str) -> bool {
+        self.exclude_fields.iter().any(|f| f == key)
+    }
+}
+
+/// 基于 [`JsonOptions`] 的 JSON 事件格式化器
+pub(crate) struct JsonFormat {
+    options: JsonOptions,
+    timer: LogTimer,
+    show_target: bool,
+    show_thread_ids: bool,
+}
+
+impl JsonFormat {
+    pub(crate) fn new(
+        options: JsonOptions,
+        timer: LogTimer,
+        show_target: bool,
+        show_thread_ids: bool,
+    ) -> Self {
+        Self {
+            options,
+            timer,
+            show_target,
+            show_thread_ids,
+        }
+    }
+
+    fn insert(&self, map: &mut Map<String, Value>, key: &str, value: Value) {
+        if !self.options.is_excluded(key) {
+            map.insert(key.to_string(), value);
+        }
+    }
+}
+
+impl<S> FormatEvent<S, JsonFields> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut root = Map::new();
+
+        let timestamp = self.timer.now_string();
+        self.insert(&mut root, &self.options.timestamp_key, timestamp.into());
+        self.insert(&mut root, "level", metadata.level().as_str().into());
+        if self.show_target {
+            self.insert(&mut root, "target", metadata.target().into());
+        }
+        if self.show_thread_ids {
+            let thread_id = format!("{:?}", std::thread::current().id());
+            self.insert(&mut root, "threadId", thread_id.into());
+        }
+
+        // 事件字段：message 单独提升到顶层，其余放入 fields
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        let mut fields = visitor.fields;
+        if let Some(message) = fields.remove("message") {
+            self.insert(&mut root, &self.options.message_key, message);
+        }
+        fields.retain(|key, _| !self.options.is_excluded(key));
+        if !fields.is_empty() {
+            self.insert(&mut root, "fields", Value::Object(fields));
+        }
+
+        // span 字段：由 JsonFields 以 JSON 字符串形式存放在扩展中
+        let mut spans = Vec::new();
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let mut span_fields = Map::new();
+                if let Some(formatted) = span.extensions().get::<FormattedFields<JsonFields>>()
+                    && let Ok(Value::Object(parsed)) = serde_json::from_str(formatted)
+                {
+                    span_fields = parsed;
+                }
+                span_fields.retain(|key, _| !self.options.is_excluded(key));
+
+                if self.options.flatten_spans {
+                    for (key, value) in span_fields {
+                        // 平铺时不覆盖已有的核心字段
+                        root.entry(key).or_insert(value);
+                    }
+                } else {
+                    span_fields.insert("name".to_string(), span.name().into());
+                    spans.push(Value::Object(span_fields));
+                }
+            }
+        }
+        if !spans.is_empty() {
+            if let Some(current) = spans.last().cloned() {
+                self.insert(&mut root, "span", current);
+            }
+            self.insert(&mut root, "spans", Value::Array(spans));
+        }
+
+        let line = serde_json::to_string(&root).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// 将事件字段收集为 JSON 对象
+#[derive(Default)]
+struct JsonVisitor {
+    fields: Map<String, Value>,
+}
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.fields
+            .insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::CapturingLogger;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn capture_lines(options: JsonOptions, emit: impl FnOnce()) -> Vec<Map<String, Value>> {
+        let buffer = CapturingLogger::new();
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(buffer.clone())
+            .with_ansi(false)
+            .fmt_fields(JsonFields::new())
+            .event_format(JsonFormat::new(
+                options,
+                LogTimer::new(Default::default(), "%Y-%m-%d %H:%M:%S"),
+                true,
+                false,
+            ));
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, emit);
+
+        buffer
+            .contents()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_default_keys() {
+        let lines = capture_lines(JsonOptions::default(), || {
+            let span = tracing::info_span!("request", request_id = "r-1");
+            let _enter = span.enter();
+            tracing::info!(user_id = 1, "hello");
+        });
+
+        let line = &lines[0];
+        assert!(line.contains_key("timestamp"));
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "hello");
+        assert_eq!(line["fields"]["user_id"], 1);
+        assert_eq!(line["span"]["name"], "request");
+        assert_eq!(line["span"]["request_id"], "r-1");
+        assert_eq!(line["spans"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_renamed_flattened_and_excluded_keys() {
+        let options = JsonOptions::new()
+            .flatten_spans(true)
+            .timestamp_key("@timestamp")
+            .message_key("msg")
+            .exclude_field("password")
+            .exclude_field("target");
+
+        let lines = capture_lines(options, || {
+            let outer = tracing::info_span!("outer", tenant = "t-42");
+            let _outer = outer.enter();
+            let inner = tracing::info_span!("inner", request_id = "r-1", password = "x");
+            let _inner = inner.enter();
+            tracing::warn!(password = "secret", attempts = 3, "login failed");
+        });
+
+        let line = &lines[0];
+        assert!(line.contains_key("@timestamp"));
+        assert!(!line.contains_key("timestamp"));
+        assert_eq!(line["msg"], "login failed");
+        assert!(!line.contains_key("message"));
+        assert_eq!(line["tenant"], "t-42");
+        assert_eq!(line["request_id"], "r-1");
+        assert!(!line.contains_key("span"));
+        assert!(!line.contains_key("spans"));
+        assert!(!line.contains_key("password"));
+        assert!(!line.contains_key("target"));
+        assert_eq!(line["fields"]["attempts"], 3);
+        assert!(!line["fields"].as_object().unwrap().contains_key("password"));
+    }
+}
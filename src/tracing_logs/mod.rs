@@ -1,45 +1,342 @@
-//! tracing 日志模块：提供控制台+文件输出、按日滚动、可配置格式/级别的初始化函数。
+//! tracing 日志模块：提供控制台+文件输出、按日滚动（时区与切割时间点可配置）、可配置格式（文本/紧凑/JSON）与级别的初始化函数。
 //! 参见 README 以及 examples/beautiful_logs_custom.rs。
+mod background;
+pub mod fields;
+mod json;
+mod panic;
+mod rotation;
+pub mod serde_level;
+mod time;
+mod writer;
+
+pub use json::JsonOptions;
+pub use rotation::Rotation;
+pub use time::LogTimezone;
+pub use writer::{ExtraWriter, RingBufferHandle, RingBufferWriter};
+
 use crate::error::{ClamberError, Result};
+use background::BackgroundGuard;
+use json::JsonFormat;
+use rotation::{FileName, RollingFileWriter, RotationPolicy};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt as std_fmt;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use time::LogTimer;
 use tracing::metadata::LevelFilter;
-use tracing_appender::non_blocking::WorkerGuard;
-use tracing_appender::rolling;
+use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::filter_fn;
-use tracing_subscriber::fmt::time::ChronoUtc;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::fmt::format::JsonFields;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::{Layer, fmt};
+use tracing_subscriber::{Layer, Registry, fmt};
+
+/// 可注入日志系统的 layer，见 [`logger_start_with_layers`]
+pub type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync + 'static>;
+
+type ShutdownHook = Box<dyn FnOnce() + Send + 'static>;
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum LogFormat {
+    /// 完整文本格式
+    Text,
+    /// 紧凑文本格式
+    #[default]
+    Compact,
+    /// JSON 行格式（每行一个 JSON 对象）
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = ClamberError;
+
+    /// 忽略大小写解析 `text` / `compact` / `json`
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "compact" => Ok(LogFormat::Compact),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(ClamberError::ConfigValidationError {
+                details: format!("无效的日志格式 {}，可选 text / compact / json", value),
+            }),
+        }
+    }
+}
+
+impl std_fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        f.write_str(match self {
+            LogFormat::Text => "text",
+            LogFormat::Compact => "compact",
+            LogFormat::Json => "json",
+        })
+    }
+}
+
+/// info 文件的级别过滤方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum InfoFileMode {
+    /// 仅记录与 `file_level` 完全相同的级别（旧行为）
+    Exact,
+    /// 记录 `file_level` 及更严重的级别
+    #[default]
+    Threshold,
+}
+
+impl FromStr for InfoFileMode {
+    type Err = ClamberError;
+
+    /// 忽略大小写解析 `exact` / `threshold`
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "exact" => Ok(InfoFileMode::Exact),
+            "threshold" => Ok(InfoFileMode::Threshold),
+            _ => Err(ClamberError::ConfigValidationError {
+                details: format!("无效的 info 文件过滤方式 {}，可选 exact / threshold", value),
+            }),
+        }
+    }
+}
+
+impl std_fmt::Display for InfoFileMode {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        f.write_str(match self {
+            InfoFileMode::Exact => "exact",
+            InfoFileMode::Threshold => "threshold",
+        })
+    }
+}
+
+/// 控制台输出目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ConsoleTarget {
+    /// 全部写入 stdout
+    #[default]
+    Stdout,
+    /// 全部写入 stderr
+    Stderr,
+    /// 按级别拆分：`error_to_stderr` 为 true 时 WARN 与 ERROR 写入 stderr，其余写入 stdout；
+    /// 为 false 时等同于 [`ConsoleTarget::Stdout`]
+    SplitByLevel { error_to_stderr: bool },
+}
+
+impl FromStr for ConsoleTarget {
+    type Err = ClamberError;
+
+    /// 忽略大小写解析 `stdout` / `stderr` / `split`（WARN 与 ERROR 写入 stderr）
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "stdout" => Ok(ConsoleTarget::Stdout),
+            "stderr" => Ok(ConsoleTarget::Stderr),
+            "split" => Ok(ConsoleTarget::SplitByLevel {
+                error_to_stderr: true,
+            }),
+            _ => Err(ClamberError::ConfigValidationError {
+                details: format!(
+                    "无效的控制台输出目标 {}，可选 stdout / stderr / split",
+                    value
+                ),
+            }),
+        }
+    }
+}
+
+impl std_fmt::Display for ConsoleTarget {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        f.write_str(match self {
+            ConsoleTarget::Stdout
+            | ConsoleTarget::SplitByLevel {
+                error_to_stderr: false,
+            } => "stdout",
+            ConsoleTarget::Stderr => "stderr",
+            ConsoleTarget::SplitByLevel {
+                error_to_stderr: true,
+            } => "split",
+        })
+    }
+}
+
+/// 为实现了 `FromStr` 与 `Display` 的枚举提供 serde 所需的字符串转换
+macro_rules! string_conversions {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<String> for $ty {
+            type Error = ClamberError;
+
+            fn try_from(value: String) -> Result<Self> {
+                value.parse()
+            }
+        }
+
+        impl From<$ty> for String {
+            fn from(value: $ty) -> String {
+                value.to_string()
+            }
+        }
+    )*};
+}
+
+string_conversions!(
+    LogFormat,
+    InfoFileMode,
+    Rotation,
+    LogTimezone,
+    ConsoleTarget
+);
+
+/// 日志文件路由：级别在 `max_level`（最严重）到 `min_level`（最不严重）之间的记录写入 `{服务名}-{suffix}.log`
+///
+/// 多个路由的级别范围可以重叠，同一条记录会写入所有匹配的文件。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileRoute {
+    /// 文件名后缀，如 `warn` 对应 `{服务名}-warn.log`
+    pub suffix: String,
+    /// 最不严重的级别，如 INFO 表示 INFO 及更严重的级别
+    #[serde(with = "serde_level")]
+    pub min_level: LevelFilter,
+    /// 最严重的级别，如 WARN 表示不记录 ERROR
+    #[serde(with = "serde_level")]
+    pub max_level: LevelFilter,
+}
+
+impl FileRoute {
+    pub fn new(suffix: impl Into<String>, min_level: LevelFilter, max_level: LevelFilter) -> Self {
+        Self {
+            suffix: suffix.into(),
+            min_level,
+            max_level,
+        }
+    }
+
+    /// 只记录单一级别
+    pub fn exact(suffix: impl Into<String>, level: LevelFilter) -> Self {
+        Self::new(suffix, level, level)
+    }
+
+    /// 该路由是否接收此级别的记录
+    pub fn accepts(&self, level: &tracing::Level) -> bool {
+        self.min_level >= *level && *level >= self.max_level
+    }
+}
 
 /// 日志配置结构
-#[derive(Debug, Clone)]
+///
+/// 可直接从配置文件的 `log` 段反序列化，未出现的字段使用默认值；级别、格式等均为字符串，
+/// 如 `console_level: debug`、`timezone: "+08:00"`。`extra_writers` 只能在代码中添加。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LogConfig {
     /// 时间格式字符串
     pub time_format: String,
+    /// 时间戳与日志轮转边界使用的时区
+    pub timezone: LogTimezone,
+    /// 轮转时间点相对时区零点的偏移（如 4 小时表示凌晨 4 点切割），配置文件中为 `rotation_offset_secs`
+    #[serde(
+        rename = "rotation_offset_secs",
+        serialize_with = "serialize_secs",
+        deserialize_with = "deserialize_secs"
+    )]
+    pub rotation_offset: chrono::Duration,
+    /// 日志文件轮转周期
+    pub rotation: Rotation,
+    /// 每类日志文件最多保留的个数，超出时删除最旧的文件；None 表示不清理
+    pub max_files: Option<usize>,
+    /// 日志目录，设置后原样使用；未设置时为 `logs`
+    #[serde(alias = "dir")]
+    pub log_dir: Option<PathBuf>,
+    /// 日志文件名模板，支持 `{service}`、`{level}` 占位符；如 `{service}.{level}` 生成
+    /// `api.info.2024-05-01.log`。未设置时为 `{service}-{level}.log.2024-05-01`
+    pub file_name_template: Option<String>,
+    /// 在日志目录下按服务名创建子目录，日志写入 `{log_dir}/{service}/`
+    pub nest_service_dir: bool,
     /// 是否启用ANSI颜色（控制台）
+    #[serde(alias = "ansi")]
     pub enable_ansi: bool,
+    /// 是否输出到控制台
+    pub console_enabled: bool,
+    /// 控制台写入 stdout、stderr 或按级别拆分
+    pub console_target: ConsoleTarget,
+    /// 是否输出到 info / error 文件；关闭时不创建日志目录
+    pub file_enabled: bool,
+    /// 初始化时安装 panic 钩子，将 panic 信息记录到 error 日志
+    pub capture_panics: bool,
     /// 是否显示目标模块
     pub show_target: bool,
     /// 是否显示线程ID
     pub show_thread_ids: bool,
-    /// 是否使用紧凑格式
-    pub compact_format: bool,
+    /// 控制台输出格式
+    pub console_format: LogFormat,
+    /// 文件输出格式
+    pub file_format: LogFormat,
+    /// JSON 格式下的字段定制
+    pub json_options: JsonOptions,
     /// 控制台日志级别
+    #[serde(with = "serde_level")]
     pub console_level: LevelFilter,
     /// 文件日志级别
+    #[serde(with = "serde_level")]
     pub file_level: LevelFilter,
+    /// 控制台的 EnvFilter 指令（如 `my_app=debug,hyper=warn`），设置后取代 `console_level`
+    pub console_env_filter: Option<String>,
+    /// info 文件的 EnvFilter 指令，设置后取代 `file_level` 与 `info_file_mode`
+    pub file_env_filter: Option<String>,
+    /// 自定义文件路由；None 时为 info / warn / error 三个文件
+    pub file_routes: Option<Vec<FileRoute>>,
+    /// info 文件的级别过滤方式
+    pub info_file_mode: InfoFileMode,
+    /// 额外挂载的输出目标
+    #[serde(skip)]
+    pub extra_writers: Vec<ExtraWriter>,
+}
+
+fn serialize_secs<S: Serializer>(
+    duration: &chrono::Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_i64(duration.num_seconds())
+}
+
+fn deserialize_secs<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<chrono::Duration, D::Error> {
+    let seconds = i64::deserialize(deserializer)?;
+    chrono::Duration::try_seconds(seconds)
+        .ok_or_else(|| serde::de::Error::custom(format!("轮转偏移秒数超出范围: {}", seconds)))
 }
 
 impl Default for LogConfig {
     fn default() -> Self {
         Self {
             time_format: "%Y-%m-%d %H:%M:%S".to_string(),
+            timezone: LogTimezone::Utc,
+            rotation_offset: chrono::Duration::zero(),
+            rotation: Rotation::Daily,
+            max_files: None,
+            log_dir: None,
+            file_name_template: None,
+            nest_service_dir: false,
             enable_ansi: true,
+            console_enabled: true,
+            console_target: ConsoleTarget::Stdout,
+            file_enabled: true,
+            capture_panics: true,
             show_target: false,
             show_thread_ids: false,
-            compact_format: true,
+            console_format: LogFormat::Compact,
+            file_format: LogFormat::Compact,
+            json_options: JsonOptions::default(),
             console_level: LevelFilter::INFO,
             file_level: LevelFilter::INFO,
+            console_env_filter: None,
+            file_env_filter: None,
+            file_routes: None,
+            info_file_mode: InfoFileMode::Threshold,
+            extra_writers: Vec::new(),
         }
     }
 }
@@ -56,12 +353,81 @@ impl LogConfig {
         self
     }
 
+    /// 设置时间戳与轮转边界使用的时区
+    pub fn timezone(mut self, timezone: LogTimezone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// 设置轮转时间点偏移
+    pub fn rotation_offset(mut self, offset: chrono::Duration) -> Self {
+        self.rotation_offset = offset;
+        self
+    }
+
+    /// 设置日志文件轮转周期
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// 设置每类日志文件最多保留的个数
+    pub fn max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files);
+        self
+    }
+
     /// 启用/禁用ANSI颜色
     pub fn ansi(mut self, enable: bool) -> Self {
         self.enable_ansi = enable;
         self
     }
 
+    /// 设置日志目录（绝对或相对路径均原样使用）
+    pub fn log_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.log_dir = Some(path.into());
+        self
+    }
+
+    /// 设置日志文件名模板，`{level}` 为文件路由的名称（info / warn / error 等）
+    ///
+    /// 文件名为 `{模板}.{时间后缀}.log`，不轮转时为 `{模板}.log`；模板中出现未知占位符时
+    /// 初始化返回 [`ClamberError::LoggingError`]。
+    pub fn file_name_template(mut self, template: impl Into<String>) -> Self {
+        self.file_name_template = Some(template.into());
+        self
+    }
+
+    /// 启用/关闭按服务名创建的日志子目录
+    pub fn nest_service_dir(mut self, enable: bool) -> Self {
+        self.nest_service_dir = enable;
+        self
+    }
+
+    /// 启用/关闭控制台输出
+    pub fn console_enabled(mut self, enable: bool) -> Self {
+        self.console_enabled = enable;
+        self
+    }
+
+    /// 设置控制台输出目标，如容器中按级别拆分到 stdout / stderr
+    pub fn console_target(mut self, target: ConsoleTarget) -> Self {
+        self.console_target = target;
+        self
+    }
+
+    /// 启用/关闭文件输出（如只读文件系统中只输出到 stdout）
+    pub fn file_enabled(mut self, enable: bool) -> Self {
+        self.file_enabled = enable;
+        self
+    }
+
+    /// 是否将 panic 信息（消息、位置，RUST_BACKTRACE 允许时包含调用栈）记录为 ERROR 日志，默认开启
+    pub fn capture_panics(mut self, capture: bool) -> Self {
+        self.capture_panics = capture;
+        self
+    }
+
     /// 显示/隐藏目标模块
     pub fn target(mut self, show: bool) -> Self {
         self.show_target = show;
@@ -74,9 +440,37 @@ impl LogConfig {
         self
     }
 
-    /// 设置紧凑格式
-    pub fn compact(mut self, enable: bool) -> Self {
-        self.compact_format = enable;
+    /// 设置紧凑格式（false 时使用完整文本格式），同时作用于控制台与文件
+    pub fn compact(self, enable: bool) -> Self {
+        self.format(if enable {
+            LogFormat::Compact
+        } else {
+            LogFormat::Text
+        })
+    }
+
+    /// 同时设置控制台与文件的输出格式
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.console_format = format;
+        self.file_format = format;
+        self
+    }
+
+    /// 设置控制台输出格式
+    pub fn console_format(mut self, format: LogFormat) -> Self {
+        self.console_format = format;
+        self
+    }
+
+    /// 设置文件输出格式
+    pub fn file_format(mut self, format: LogFormat) -> Self {
+        self.file_format = format;
+        self
+    }
+
+    /// 设置 JSON 格式下的字段定制
+    pub fn json_options(mut self, options: JsonOptions) -> Self {
+        self.json_options = options;
         self
     }
 
@@ -91,107 +485,1295 @@ impl LogConfig {
         self.file_level = level;
         self
     }
+
+    /// 控制台按 EnvFilter 指令过滤（如 `my_app=debug,hyper=warn,sqlx=info`）
+    ///
+    /// 指令在初始化日志时解析，无效时返回 [`ClamberError::LoggingError`]。
+    pub fn with_env_filter(mut self, directives: impl Into<String>) -> Self {
+        self.console_env_filter = Some(directives.into());
+        self
+    }
+
+    /// 从环境变量读取控制台的 EnvFilter 指令，`None` 表示 `RUST_LOG`；变量未设置或为空时保持原配置
+    pub fn with_env_filter_from_env(self, var_name: Option<&str>) -> Self {
+        match std::env::var(var_name.unwrap_or(EnvFilter::DEFAULT_ENV)) {
+            Ok(directives) if !directives.trim().is_empty() => self.with_env_filter(directives),
+            _ => self,
+        }
+    }
+
+    /// `info` 路由按 EnvFilter 指令过滤，其他路由仍按级别范围过滤
+    pub fn file_env_filter(mut self, directives: impl Into<String>) -> Self {
+        self.file_env_filter = Some(directives.into());
+        self
+    }
+
+    /// 设置 info 文件的级别过滤方式
+    pub fn info_file_mode(mut self, mode: InfoFileMode) -> Self {
+        self.info_file_mode = mode;
+        self
+    }
+
+    /// 自定义文件路由，取代默认的 info / warn / error 文件；`file_level` 与 `info_file_mode` 不再生效
+    pub fn file_routes(mut self, routes: Vec<FileRoute>) -> Self {
+        self.file_routes = Some(routes);
+        self
+    }
+
+    /// 实际使用的文件路由
+    ///
+    /// 默认 info 文件按 `file_level` 与 `info_file_mode` 过滤，warn 文件只记录 WARN，error 文件只记录 ERROR。
+    pub fn effective_file_routes(&self) -> Vec<FileRoute> {
+        if let Some(routes) = &self.file_routes {
+            return routes.clone();
+        }
+        let info = match self.info_file_mode {
+            InfoFileMode::Threshold => FileRoute::new("info", self.file_level, LevelFilter::ERROR),
+            InfoFileMode::Exact => FileRoute::exact("info", self.file_level),
+        };
+        vec![
+            info,
+            FileRoute::exact("warn", LevelFilter::WARN),
+            FileRoute::exact("error", LevelFilter::ERROR),
+        ]
+    }
+
+    /// 额外挂载一个输出目标（如内存缓冲区、网络 socket）
+    pub fn add_writer<W>(mut self, writer: W, level: LevelFilter, format: LogFormat) -> Self
+    where
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        self.extra_writers
+            .push(ExtraWriter::new(writer, level, format));
+        self
+    }
+}
+
+#[cfg(feature = "config")]
+impl LogConfig {
+    /// 从已构建的配置中反序列化 `key` 指向的段（如 `log`）
+    ///
+    /// 段中的 `format` 同时设置控制台与文件格式，单独写出的 `console_format` / `file_format` 优先。
+    /// 段不存在返回 [`ClamberError::ConfigValidationError`]，字段无效（如未知的级别）返回
+    /// [`ClamberError::ConfigParseError`]。
+    pub fn from_config_section(config: &config::Config, key: &str) -> Result<Self> {
+        let view = crate::config::ConfigView::new(config.clone());
+        let mut log: Self = view.get_section(key)?;
+        let format_key = format!("{}.format", key);
+        if view.contains(&format_key) {
+            let format: LogFormat = view.get_section(&format_key)?;
+            if !view.contains(&format!("{}.console_format", key)) {
+                log.console_format = format;
+            }
+            if !view.contains(&format!("{}.file_format", key)) {
+                log.file_format = format;
+            }
+        }
+        Ok(log)
+    }
+}
+
+/// 日志系统句柄：持有文件的后台写线程
+///
+/// 句柄被销毁时会写完缓冲中的日志，应在 main 中保持其存活；短生命周期的命令行工具
+/// 在退出前调用 [`LoggerHandle::flush`] 或 [`LoggerHandle::shutdown`]，避免丢失最后几行。
+#[must_use = "句柄被销毁后文件日志不再写入"]
+pub struct LoggerHandle {
+    log_dir: PathBuf,
+    writers: Vec<BackgroundGuard>,
+    output: LogOutput,
+    shutdown_hooks: Vec<ShutdownHook>,
+}
+
+/// 已启用的日志输出目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LogOutput {
+    /// 是否输出到控制台
+    pub console: bool,
+    /// 是否输出到 info / error 文件
+    pub files: bool,
+    /// 额外挂载的输出目标个数
+    pub extra_writers: usize,
+}
+
+impl LoggerHandle {
+    /// 阻塞直到此前记录的日志全部写入文件
+    pub fn flush(&self) {
+        for writer in &self.writers {
+            writer.flush();
+        }
+    }
+
+    /// 写完缓冲中的日志并停止后台写线程，之后的文件日志被丢弃
+    pub fn shutdown(self) {
+        drop(self);
+    }
+
+    /// 停止将 panic 记录到日志；钩子为进程级，对所有句柄生效
+    pub fn remove_panic_hook(&self) {
+        panic::uninstall();
+    }
+
+    /// 注册关闭时执行的回调（如关闭 OpenTelemetry 的 tracer provider 以导出剩余 span）
+    ///
+    /// 回调在文件写线程停止后按注册顺序执行，句柄被销毁时同样会执行。
+    pub fn on_shutdown(&mut self, hook: impl FnOnce() + Send + 'static) {
+        self.shutdown_hooks.push(Box::new(hook));
+    }
+
+    /// 日志文件所在目录；文件输出关闭时该目录不会被创建
+    pub fn log_directory(&self) -> &Path {
+        &self.log_dir
+    }
+
+    /// 已启用的输出目标
+    pub fn output(&self) -> LogOutput {
+        self.output
+    }
+}
+
+impl Drop for LoggerHandle {
+    fn drop(&mut self) {
+        self.writers.clear();
+        for hook in self.shutdown_hooks.drain(..) {
+            hook();
+        }
+    }
+}
+
+impl std_fmt::Debug for LoggerHandle {
+    fn fmt(&self, f: &mut std_fmt::Formatter<'_>) -> std_fmt::Result {
+        f.debug_struct("LoggerHandle")
+            .field("log_dir", &self.log_dir)
+            .field("output", &self.output)
+            .field("shutdown_hooks", &self.shutdown_hooks.len())
+            .finish()
+    }
 }
 
 /// 使用自定义配置初始化日志系统
+///
+/// 日志目录优先使用 [`LogConfig::log_dir`]；`path` 参数已废弃，传入时作为父目录，
+/// 日志写入 `{path}/logs`，请改用 `LogConfig::log_dir`。
+///
+/// 全局日志只能初始化一次，重复调用返回 [`ClamberError::LoggingError`]；
+/// 不确定是否已初始化时使用 [`logger_start_once`]。
 pub fn logger_start_with_config(
     service_name: &str,
     path: Option<String>,
     config: LogConfig,
-) -> Result<(WorkerGuard, WorkerGuard)> {
-    let log_dir = match path {
-        Some(p) => format!("logs/{}", p),
-        None => "logs".to_string(),
-    };
+) -> Result<LoggerHandle> {
+    let log_dir = resolve_log_dir(path.as_deref(), &config);
+    init_logger(service_name, &log_dir, &config)
+}
 
-    fs::create_dir_all(&log_dir).map_err(|_| ClamberError::DirectoryCreationError {
-        path: log_dir.clone(),
-    })?;
+/// 与 [`logger_start_with_config`] 相同，但已安装全局 subscriber 时直接返回空句柄
+///
+/// 适用于库与二进制都可能初始化日志的场景；空句柄不持有任何文件。
+pub fn logger_start_once(
+    service_name: &str,
+    path: Option<String>,
+    config: LogConfig,
+) -> Result<LoggerHandle> {
+    let log_dir = resolve_log_dir(path.as_deref(), &config);
+    if is_initialized() {
+        return Ok(LoggerHandle {
+            log_dir,
+            writers: Vec::new(),
+            output: LogOutput::default(),
+            shutdown_hooks: Vec::new(),
+        });
+    }
+    init_logger(service_name, Path::new(&log_dir), &config)
+}
 
-    let info_file = rolling::daily(&log_dir, format!("{}-info.log", service_name));
-    let error_file = rolling::daily(&log_dir, format!("{}-error.log", service_name));
+/// 日志目录：`config.log_dir` > `{path}/logs` > `logs`
+fn resolve_log_dir(path: Option<&str>, config: &LogConfig) -> PathBuf {
+    match (&config.log_dir, path) {
+        (Some(dir), _) => dir.clone(),
+        (None, Some(parent)) => Path::new(parent).join("logs"),
+        (None, None) => PathBuf::from("logs"),
+    }
+}
 
-    let (info_writer, info_guard) = tracing_appender::non_blocking(info_file);
-    let (error_writer, error_guard) = tracing_appender::non_blocking(error_file);
+/// 与 [`logger_start_with_config`] 相同，并将 `extra_layers` 组合进同一个 subscriber
+///
+/// 用于接入 `tracing-opentelemetry` 等本库不直接依赖的 layer，日志目录取 [`LogConfig::log_dir`]。
+/// 需要在退出时关闭的资源（如 tracer provider）通过 [`LoggerHandle::on_shutdown`] 注册。
+pub fn logger_start_with_layers(
+    service_name: &str,
+    config: LogConfig,
+    extra_layers: Vec<BoxedLayer>,
+) -> Result<LoggerHandle> {
+    let log_dir = resolve_log_dir(None, &config);
+    let console = console_layers(&config, std::io::stdout, std::io::stderr)?;
+    init_logger_with(service_name, &log_dir, &config, console, extra_layers)
+}
 
-    // 使用用户配置的时间格式
-    let timer = ChronoUtc::new(config.time_format.clone());
-
-    // 根据配置选择格式类型
-    if config.compact_format {
-        // 使用紧凑格式
-        let info_layer = fmt::layer()
-            .compact()
-            .with_writer(info_writer)
-            .with_ansi(false)
-            .with_level(true)
-            .with_target(config.show_target)
-            .with_thread_ids(config.show_thread_ids)
-            .with_timer(timer.clone())
-            .with_filter(filter_fn(move |metadata| {
-                metadata.level() == &tracing::Level::INFO
-            }));
-
-        let error_layer = fmt::layer()
-            .compact()
-            .with_writer(error_writer)
-            .with_ansi(false)
-            .with_level(true)
-            .with_target(config.show_target)
-            .with_thread_ids(config.show_thread_ids)
-            .with_timer(timer.clone())
-            .with_filter(LevelFilter::ERROR);
-
-        let console_layer = fmt::layer()
-            .compact()
-            .with_ansi(config.enable_ansi)
-            .with_level(true)
-            .with_target(config.show_target)
-            .with_thread_ids(config.show_thread_ids)
-            .with_timer(timer)
-            .with_filter(config.console_level);
+/// 与 [`logger_start_with_config`] 相同，但控制台输出写入 `make_writer`（如测试缓冲区、syslog）
+///
+/// `make_writer` 接收原本输出到控制台的全部记录（仍按 `console_level` 等过滤），
+/// [`LogConfig::console_target`] 不再生效；`console_enabled(false)` 时不使用该 writer。
+pub fn logger_start_with_writer<W>(
+    service_name: &str,
+    config: LogConfig,
+    make_writer: W,
+) -> Result<LoggerHandle>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let log_dir = resolve_log_dir(None, &config);
+    let console = console_layer(&config, make_writer, None)?;
+    init_logger_with(service_name, &log_dir, &config, console, Vec::new())
+}
+
+/// 从配置文件的 `log` 段读取日志配置并初始化日志系统，文件中没有 `log` 段时使用默认配置
+#[cfg(feature = "config")]
+pub fn logger_start_from_config_file(
+    service_name: &str,
+    path: impl AsRef<Path>,
+) -> Result<LoggerHandle> {
+    let view = crate::config::ConfigBuilder::new()
+        .add_file(path, None)
+        .build_view()?;
+    let config = if view.contains("log") {
+        view.get_section("log")?
+    } else {
+        LogConfig::default()
+    };
+    logger_start_with_config(service_name, None, config)
+}
 
+/// 是否已安装全局 subscriber（无论是否由本库安装）
+pub fn is_initialized() -> bool {
+    tracing::dispatcher::has_been_set()
+}
+
+/// 在当前线程安装写入内存缓冲区的 subscriber（紧凑格式、无颜色、记录所有级别），不设置全局默认值
+///
+/// guard 被销毁后恢复之前的 subscriber；缓冲区保留最近 10000 行。
+pub fn logger_for_tests() -> (RingBufferWriter, tracing::subscriber::DefaultGuard) {
+    let buffer = RingBufferWriter::new(10_000);
+    let config = LogConfig::new().ansi(false);
+    let layer = fmt_layer(&config, LogFormat::Compact, buffer.clone(), false)
+        .with_filter(LevelFilter::TRACE)
+        .boxed();
+    let guard = tracing::subscriber::set_default(
         tracing_subscriber::registry()
-            .with(info_layer)
-            .with(error_layer)
-            .with(console_layer)
-            .init();
+            .with(layer)
+            .with(fields::with_request_id()),
+    );
+    (buffer, guard)
+}
+
+/// 在指定日志目录下初始化全局日志系统，已安装全局 subscriber 时返回 [`ClamberError::LoggingError`]
+pub(crate) fn init_logger(
+    service_name: &str,
+    log_dir: &Path,
+    config: &LogConfig,
+) -> Result<LoggerHandle> {
+    let console = console_layers(config, std::io::stdout, std::io::stderr)?;
+    init_logger_with(service_name, log_dir, config, console, Vec::new())
+}
+
+fn init_logger_with(
+    service_name: &str,
+    log_dir: &Path,
+    config: &LogConfig,
+    console: Vec<BoxedLayer>,
+    extra_layers: Vec<BoxedLayer>,
+) -> Result<LoggerHandle> {
+    // 提前检查，避免创建日志文件与写线程后才失败
+    if is_initialized() {
+        return Err(already_initialized());
+    }
+    let (mut layers, handle) = build_layers_with(service_name, log_dir, config, console)?;
+    layers.extend(extra_layers);
+
+    tracing_subscriber::registry()
+        .with(layers)
+        .try_init()
+        .map_err(|_| already_initialized())?;
+    if config.capture_panics {
+        panic::install();
+    }
+
+    Ok(handle)
+}
+
+fn already_initialized() -> ClamberError {
+    ClamberError::LoggingError {
+        message: "global subscriber already set".to_string(),
+    }
+}
+
+/// 构建文件与控制台 layer；控制台全部写入 `console_writer`（不区分 `console_target`），文件写入 `log_dir`
+#[cfg(test)]
+fn build_layers<W>(
+    service_name: &str,
+    log_dir: &Path,
+    config: &LogConfig,
+    console_writer: W,
+) -> Result<(Vec<BoxedLayer>, LoggerHandle)>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let console = console_layer(config, console_writer, None)?;
+    build_layers_with(service_name, log_dir, config, console)
+}
+
+/// 按 `console_target` 构建写入 `stdout` / `stderr` 的控制台 layer，关闭控制台输出时为空
+fn console_layers<O, E>(config: &LogConfig, stdout: O, stderr: E) -> Result<Vec<BoxedLayer>>
+where
+    O: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    E: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match config.console_target {
+        ConsoleTarget::Stdout
+        | ConsoleTarget::SplitByLevel {
+            error_to_stderr: false,
+        } => console_layer(config, stdout, None),
+        ConsoleTarget::Stderr => console_layer(config, stderr, None),
+        ConsoleTarget::SplitByLevel {
+            error_to_stderr: true,
+        } => {
+            let mut layers = console_layer(
+                config,
+                stdout,
+                Some(FileRoute::new(
+                    "stdout",
+                    LevelFilter::TRACE,
+                    LevelFilter::INFO,
+                )),
+            )?;
+            layers.extend(console_layer(
+                config,
+                stderr,
+                Some(FileRoute::new(
+                    "stderr",
+                    LevelFilter::WARN,
+                    LevelFilter::ERROR,
+                )),
+            )?);
+            Ok(layers)
+        }
+    }
+}
+
+/// 单个控制台 layer，按 `console_env_filter` 或 `console_level` 过滤，`levels` 进一步限定级别范围
+fn console_layer<W>(
+    config: &LogConfig,
+    writer: W,
+    levels: Option<FileRoute>,
+) -> Result<Vec<BoxedLayer>>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    if !config.console_enabled {
+        return Ok(Vec::new());
+    }
+    let layer = fmt_layer(config, config.console_format, writer, config.enable_ansi);
+    let layer = match &config.console_env_filter {
+        Some(directives) => layer.with_filter(env_filter(directives)?).boxed(),
+        None => layer.with_filter(config.console_level).boxed(),
+    };
+    Ok(vec![match levels {
+        Some(route) => layer
+            .with_filter(filter_fn(move |metadata| route.accepts(metadata.level())))
+            .boxed(),
+        None => layer,
+    }])
+}
+
+/// 构建文件 layer，并与已构建的控制台 layer 组合
+fn build_layers_with(
+    service_name: &str,
+    log_dir: &Path,
+    config: &LogConfig,
+    console: Vec<BoxedLayer>,
+) -> Result<(Vec<BoxedLayer>, LoggerHandle)> {
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+    let mut writers = Vec::new();
+    let log_dir = if config.nest_service_dir {
+        log_dir.join(service_name)
     } else {
-        // 使用完整格式
-        let info_layer = fmt::layer()
-            .with_writer(info_writer)
-            .with_ansi(false)
-            .with_level(true)
-            .with_target(config.show_target)
-            .with_thread_ids(config.show_thread_ids)
-            .with_timer(timer.clone())
-            .with_filter(filter_fn(move |metadata| {
-                metadata.level() == &tracing::Level::INFO
-            }));
-
-        let error_layer = fmt::layer()
-            .with_writer(error_writer)
+        log_dir.to_path_buf()
+    };
+
+    // 关闭文件输出时不创建目录与写线程，适用于只读文件系统
+    if config.file_enabled {
+        // 先校验文件名模板，避免留下空目录
+        let routes = file_names(service_name, config)?;
+        fs::create_dir_all(&log_dir).map_err(|e| ClamberError::DirectoryCreationError {
+            path: log_dir.display().to_string(),
+            source: e,
+        })?;
+
+        for (route, name) in routes {
+            let file = rolling_file(&log_dir, name, config)?;
+            let (writer, guard) = spawn_writer(&route.suffix, file)?;
+            let layer = fmt_layer(config, config.file_format, writer, false);
+            let layer = match &config.file_env_filter {
+                Some(directives) if route.suffix == "info" => {
+                    layer.with_filter(env_filter(directives)?).boxed()
+                }
+                _ => {
+                    let hint = route.min_level;
+                    layer
+                        .with_filter(
+                            filter_fn(move |metadata| route.accepts(metadata.level()))
+                                .with_max_level_hint(hint),
+                        )
+                        .boxed()
+                }
+            };
+            layers.push(layer);
+            writers.push(guard);
+        }
+    }
+
+    layers.extend(console);
+
+    layers.push(fields::with_request_id().boxed());
+
+    for extra in &config.extra_writers {
+        layers.push(
+            fmt_layer(config, extra.format, extra.make_writer(), false)
+                .with_filter(extra.level)
+                .boxed(),
+        );
+    }
+
+    let handle = LoggerHandle {
+        log_dir,
+        writers,
+        output: LogOutput {
+            console: config.console_enabled,
+            files: config.file_enabled,
+            extra_writers: config.extra_writers.len(),
+        },
+        shutdown_hooks: Vec::new(),
+    };
+    Ok((layers, handle))
+}
+
+/// 每个文件路由对应的文件名，按模板渲染后的文件名重复时返回错误
+fn file_names(service_name: &str, config: &LogConfig) -> Result<Vec<(FileRoute, FileName)>> {
+    let mut names: Vec<(FileRoute, FileName)> = Vec::new();
+    for route in config.effective_file_routes() {
+        let Some(template) = &config.file_name_template else {
+            let name = FileName::from(format!("{}-{}.log", service_name, route.suffix));
+            names.push((route, name));
+            continue;
+        };
+        let name = FileName::new(
+            render_file_name(template, service_name, &route.suffix)?,
+            ".log",
+        );
+        if names.iter().any(|(_, existing)| existing == &name) {
+            return Err(ClamberError::LoggingError {
+                message: format!(
+                    "日志文件名模板 {:?} 为多个文件路由生成了同一文件名 {}，请加入 {{level}}",
+                    template, name.prefix
+                ),
+            });
+        }
+        names.push((route, name));
+    }
+    Ok(names)
+}
+
+/// 替换模板中的 `{service}` 与 `{level}`
+fn render_file_name(template: &str, service: &str, level: &str) -> Result<String> {
+    let invalid = |reason: String| ClamberError::LoggingError {
+        message: format!("无效的日志文件名模板 {:?}: {}", template, reason),
+    };
+
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        rendered.push_str(&rest[..start]);
+        if rest[start..].starts_with('}') {
+            return Err(invalid("多余的 }".to_string()));
+        }
+        let Some(len) = rest[start + 1..].find('}') else {
+            return Err(invalid("占位符缺少 }".to_string()));
+        };
+        match &rest[start + 1..start + 1 + len] {
+            "service" => rendered.push_str(service),
+            "level" => rendered.push_str(level),
+            other => {
+                return Err(invalid(format!(
+                    "未知占位符 {{{}}}，可选 {{service}} / {{level}}",
+                    other
+                )));
+            }
+        }
+        rest = &rest[start + len + 2..];
+    }
+    rendered.push_str(rest);
+
+    if rendered.is_empty() || rendered.contains(['/', '\\']) {
+        return Err(invalid("文件名为空或包含路径分隔符".to_string()));
+    }
+    Ok(rendered)
+}
+
+/// 解析 EnvFilter 指令，不读取环境变量
+fn env_filter(directives: &str) -> Result<EnvFilter> {
+    EnvFilter::builder()
+        .parse(directives)
+        .map_err(|e| ClamberError::LoggingError {
+            message: format!("无效的日志过滤指令 {:?}: {}", directives, e),
+        })
+}
+
+fn spawn_writer(
+    name: &str,
+    file: RollingFileWriter,
+) -> Result<(background::BackgroundWriter, BackgroundGuard)> {
+    background::spawn(name, file).map_err(|e| ClamberError::LoggingError {
+        message: format!("启动日志写线程失败: {}", e),
+    })
+}
+
+/// 创建按配置周期、时区与偏移滚动的日志文件 writer
+fn rolling_file(log_dir: &Path, name: FileName, config: &LogConfig) -> Result<RollingFileWriter> {
+    let policy = RotationPolicy {
+        rotation: config.rotation,
+        timezone: config.timezone,
+        offset: config.rotation_offset,
+        max_files: config.max_files,
+    };
+    RollingFileWriter::new(log_dir, name, policy).map_err(|e| ClamberError::LoggingError {
+        message: format!("创建日志文件失败: {}", e),
+    })
+}
+
+/// 按指定的输出格式构建 fmt layer
+fn fmt_layer<W>(config: &LogConfig, format: LogFormat, writer: W, ansi: bool) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    // 使用用户配置的时间格式
+    let timer = LogTimer::new(config.timezone, config.time_format.clone());
+    let layer = fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi)
+        .with_level(true)
+        .with_target(config.show_target)
+        .with_thread_ids(config.show_thread_ids)
+        .with_timer(timer);
+
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Compact => layer.compact().boxed(),
+        // JSON 输出强制关闭 ANSI 颜色
+        LogFormat::Json => layer
             .with_ansi(false)
-            .with_level(true)
-            .with_target(config.show_target)
-            .with_thread_ids(config.show_thread_ids)
-            .with_timer(timer.clone())
-            .with_filter(LevelFilter::ERROR);
-
-        let console_layer = fmt::layer()
-            .with_ansi(config.enable_ansi)
-            .with_level(true)
-            .with_target(config.show_target)
-            .with_thread_ids(config.show_thread_ids)
-            .with_timer(timer)
-            .with_filter(config.console_level);
+            .fmt_fields(JsonFields::new())
+            .event_format(JsonFormat::new(
+                config.json_options.clone(),
+                LogTimer::new(config.timezone, config.time_format.clone()),
+                config.show_target,
+                config.show_thread_ids,
+            ))
+            .boxed(),
+    }
+}
 
-        tracing_subscriber::registry()
-            .with(info_layer)
-            .with(error_layer)
-            .with(console_layer)
-            .init();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{CapturingLogger, EnvGuard};
+    use tempfile::tempdir;
+
+    /// 读取目录下以指定前缀开头的日志文件内容
+    fn read_log_files(dir: &Path, prefix: &str) -> String {
+        let mut contents = String::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            if name.starts_with(prefix) {
+                contents.push_str(&fs::read_to_string(&path).unwrap());
+            }
+        }
+        contents
+    }
+
+    #[test]
+    fn test_console_text_and_file_json() {
+        let dir = tempdir().unwrap();
+        let console = CapturingLogger::new();
+        let config = LogConfig::new()
+            .ansi(false)
+            .console_format(LogFormat::Text)
+            .file_format(LogFormat::Json);
+
+        let (layers, guards) = build_layers("mixed", dir.path(), &config, console.clone()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(user_id = 7, "mixed output");
+        });
+        drop(guards);
+
+        let console_output = console.contents();
+        assert!(console_output.contains("mixed output"));
+        assert!(serde_json::from_str::<serde_json::Value>(console_output.trim()).is_err());
+
+        let file_output = read_log_files(dir.path(), "mixed-info.log");
+        let line: serde_json::Value = serde_json::from_str(file_output.trim()).unwrap();
+        assert_eq!(line["message"], "mixed output");
+        assert_eq!(line["fields"]["user_id"], 7);
+    }
+
+    #[test]
+    fn test_json_file_lines_with_ansi_console() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new()
+            .ansi(true)
+            .target(true)
+            .format(LogFormat::Json);
+
+        let (layers, guards) =
+            build_layers("json", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = "r-9");
+            let _enter = span.enter();
+            tracing::info!(user_id = 1, "msg");
+            tracing::warn!(user_id = 2, "second");
+        });
+        drop(guards);
+
+        let file_output = read_log_files(dir.path(), "json-info.log");
+        assert!(!file_output.contains('\u{1b}'));
+        let lines: Vec<serde_json::Value> = file_output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            assert!(line["fields"]["user_id"].is_u64());
+            assert!(line["timestamp"].is_string());
+            assert_eq!(line["span"]["request_id"], "r-9");
+            assert!(line["target"].as_str().unwrap().contains("tracing_logs"));
+        }
+        assert_eq!(lines[1]["level"], "WARN");
+    }
+
+    #[test]
+    fn test_request_span_on_every_json_line() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new().ansi(false).file_format(LogFormat::Json);
+
+        let (layers, handle) =
+            build_layers("req", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        let request_id = tracing::subscriber::with_default(subscriber, || {
+            let span = fields::RequestSpan::with_id("req-7");
+            let _enter = span.enter();
+            tracing::info!("handling");
+            tracing::info_span!("db").in_scope(|| tracing::warn!("slow query"));
+            fields::current_request_id()
+        });
+        handle.flush();
+        assert_eq!(request_id.as_deref(), Some("req-7"));
+
+        let lines: Vec<serde_json::Value> = read_log_files(dir.path(), "req-info.log")
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            let spans = line["spans"].as_array().unwrap();
+            assert!(spans.iter().any(|span| span["request_id"] == "req-7"));
+        }
+    }
+
+    #[test]
+    fn test_extra_writer_receives_events() {
+        let dir = tempdir().unwrap();
+        let ring = RingBufferWriter::new(2);
+        let config = LogConfig::new().ansi(false).add_writer(
+            ring.clone(),
+            LevelFilter::WARN,
+            LogFormat::Json,
+        );
+
+        let (layers, guards) =
+            build_layers("extra", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("below extra level");
+            tracing::warn!("warn one");
+            tracing::warn!("warn two");
+            tracing::error!("error three");
+        });
+        drop(guards);
+
+        let lines = ring.snapshot();
+        assert_eq!(lines.len(), 2);
+        let last: serde_json::Value = serde_json::from_str(&lines[1]).unwrap();
+        assert_eq!(last["message"], "error three");
+        assert!(lines.iter().all(|l| !l.contains("below extra level")));
+    }
+
+    #[test]
+    fn test_warn_lands_in_file() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new().ansi(false);
+
+        let (layers, guards) =
+            build_layers("threshold", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!("warn must not be dropped");
+        });
+        drop(guards);
+
+        let info = read_log_files(dir.path(), "threshold-info.log");
+        let error = read_log_files(dir.path(), "threshold-error.log");
+        assert!(info.contains("warn must not be dropped"));
+        assert!(!error.contains("warn must not be dropped"));
+    }
+
+    #[test]
+    fn test_directory_creation_error_keeps_io_source() {
+        use std::error::Error;
+
+        let dir = tempdir().unwrap();
+        let blocker = dir.path().join("blocker");
+        fs::write(&blocker, "not a directory").unwrap();
+        let log_dir = blocker.join("logs");
+
+        let err = build_layers("svc", &log_dir, &LogConfig::new(), CapturingLogger::new())
+            .err()
+            .unwrap();
+        let message = err.to_string();
+        match &err {
+            ClamberError::DirectoryCreationError { path, source } => {
+                assert_eq!(path, &log_dir.display().to_string());
+                assert!(message.contains(&source.kind().to_string()));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(message.contains("创建目录失败"));
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn test_handle_flush_without_drop() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new().ansi(false);
+
+        let (layers, handle) =
+            build_layers("flush", dir.path(), &config, CapturingLogger::new()).unwrap();
+        assert_eq!(handle.log_directory(), dir.path());
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("before flush");
+            tracing::error!("error before flush");
+            handle.flush();
+            assert!(read_log_files(dir.path(), "flush-info.log").contains("before flush"));
+            assert!(read_log_files(dir.path(), "flush-error.log").contains("error before flush"));
+
+            tracing::info!("before shutdown");
+        });
+        handle.shutdown();
+        assert!(read_log_files(dir.path(), "flush-info.log").contains("before shutdown"));
+    }
+
+    #[test]
+    fn test_file_name_template_and_service_dir() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new()
+            .ansi(false)
+            .file_name_template("{service}.{level}")
+            .nest_service_dir(true);
+
+        let (layers, handle) =
+            build_layers("api", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let service_dir = dir.path().join("api");
+        assert_eq!(handle.log_directory(), service_dir);
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("templated info");
+            tracing::error!("templated error");
+        });
+        handle.flush();
+
+        let (date, _) = rotation::period(
+            Rotation::Daily,
+            LogTimezone::Utc,
+            chrono::Duration::zero(),
+            chrono::Utc::now(),
+        )
+        .unwrap();
+        let mut names: Vec<String> = fs::read_dir(&service_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            ["error", "info", "warn"].map(|level| format!("api.{}.{}.log", level, date))
+        );
+        let info = fs::read_to_string(service_dir.join(format!("api.info.{}.log", date))).unwrap();
+        assert!(info.contains("templated info"));
+        let error =
+            fs::read_to_string(service_dir.join(format!("api.error.{}.log", date))).unwrap();
+        assert!(error.contains("templated error"));
+
+        // 不轮转时没有时间后缀
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new()
+            .console_enabled(false)
+            .rotation(Rotation::Never)
+            .file_name_template("{level}-{service}")
+            .file_routes(vec![FileRoute::new(
+                "all",
+                LevelFilter::TRACE,
+                LevelFilter::ERROR,
+            )]);
+        let (_layers, handle) = build_layers("api", dir.path(), &config, std::io::sink).unwrap();
+        handle.flush();
+        assert!(dir.path().join("all-api.log").exists());
+    }
+
+    #[test]
+    fn test_invalid_file_name_template() {
+        let dir = tempdir().unwrap();
+        for (template, expected) in [
+            ("{service}.{host}", "未知占位符 {host}"),
+            ("{service", "缺少 }"),
+            ("service}", "多余的 }"),
+            ("logs/{service}.{level}", "路径分隔符"),
+            ("{service}", "同一文件名 app"),
+        ] {
+            let log_dir = dir.path().join("logs");
+            let config = LogConfig::new().file_name_template(template);
+            match build_layers("app", &log_dir, &config, std::io::sink) {
+                Err(ClamberError::LoggingError { message }) => {
+                    assert!(message.contains(expected), "{}", message)
+                }
+                Err(other) => panic!("expected logging error, got {other}"),
+                Ok(_) => panic!("template {:?} should be rejected", template),
+            }
+            assert!(!log_dir.exists());
+        }
+    }
+
+    #[test]
+    fn test_env_filter_per_target() {
+        let dir = tempdir().unwrap();
+        let console = CapturingLogger::new();
+        let config = LogConfig::new()
+            .ansi(false)
+            .with_env_filter("test_target=debug,warn")
+            .file_env_filter("test_target=debug");
+
+        let (layers, guards) =
+            build_layers("filter", dir.path(), &config, console.clone()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(target: "test_target", "wanted debug");
+            tracing::debug!(target: "other_target", "unwanted debug");
+            tracing::info!(target: "other_target", "unwanted info");
+            tracing::warn!(target: "other_target", "other warn");
+        });
+        drop(guards);
+
+        let info = read_log_files(dir.path(), "filter-info.log");
+        assert!(info.contains("wanted debug"));
+        assert!(!info.contains("unwanted debug"));
+        assert!(!info.contains("other warn"));
+
+        let console_output = console.contents();
+        assert!(console_output.contains("wanted debug"));
+        assert!(console_output.contains("other warn"));
+        assert!(!console_output.contains("unwanted"));
+    }
+
+    #[test]
+    fn test_invalid_env_filter() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new().with_env_filter("my_app=loud");
+        let err = build_layers("invalid", dir.path(), &config, CapturingLogger::new())
+            .err()
+            .unwrap();
+        assert!(matches!(err, ClamberError::LoggingError { .. }));
+        assert!(err.to_string().contains("my_app=loud"));
+    }
+
+    #[test]
+    fn test_env_filter_from_env() {
+        let env = EnvGuard::new().set("CLAMBER_TEST_LOG", "my_app=trace");
+        let config = LogConfig::new().with_env_filter_from_env(Some("CLAMBER_TEST_LOG"));
+        assert_eq!(config.console_env_filter.as_deref(), Some("my_app=trace"));
+
+        let _env = env.remove("CLAMBER_TEST_LOG");
+        let config = LogConfig::new().with_env_filter_from_env(Some("CLAMBER_TEST_LOG"));
+        assert!(config.console_env_filter.is_none());
     }
 
-    Ok((info_guard, error_guard))
+    #[test]
+    fn test_logger_for_tests_is_thread_local() {
+        let (buffer, guard) = logger_for_tests();
+        tracing::debug!(attempt = 1, "captured locally");
+        std::thread::spawn(|| tracing::info!("other thread"))
+            .join()
+            .unwrap();
+        drop(guard);
+        tracing::info!("after guard");
+
+        let lines = buffer.snapshot();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("DEBUG"));
+        assert!(lines[0].contains("captured locally"));
+        assert!(lines[0].contains("attempt=1"));
+    }
+
+    #[test]
+    fn test_shutdown_hooks_run_after_flush() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new().ansi(false);
+        let (layers, mut handle) =
+            build_layers("hooks", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || tracing::info!("before hook"));
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let log_dir = dir.path().to_path_buf();
+        handle.on_shutdown(move || {
+            // 回调执行时文件已写完
+            sender
+                .send(read_log_files(&log_dir, "hooks-info.log"))
+                .unwrap();
+        });
+        handle.shutdown();
+
+        assert!(receiver.try_recv().unwrap().contains("before hook"));
+    }
+
+    #[cfg(feature = "config")]
+    mod config_section {
+        use super::*;
+        use crate::config::{ConfigBuilder, ConfigFormat, config_to_string};
+
+        #[derive(Serialize)]
+        struct App {
+            log: LogConfig,
+        }
+
+        fn round_trip(format: ConfigFormat) {
+            let original = LogConfig::new()
+                .console_level(LevelFilter::DEBUG)
+                .file_level(LevelFilter::WARN)
+                .format(LogFormat::Json)
+                .timezone(LogTimezone::Fixed(8 * 3600))
+                .rotation(Rotation::Hourly)
+                .rotation_offset(chrono::Duration::hours(4))
+                .max_files(24)
+                .log_dir("/var/log/app")
+                .info_file_mode(InfoFileMode::Exact)
+                .file_routes(vec![FileRoute::exact("warn", LevelFilter::WARN)]);
+            let text = config_to_string(&App { log: original }, format).unwrap();
+
+            let raw = ConfigBuilder::new()
+                .add_str(&text, format)
+                .build_raw()
+                .unwrap();
+            let config = LogConfig::from_config_section(&raw, "log").unwrap();
+            assert_eq!(config.console_level, LevelFilter::DEBUG);
+            assert_eq!(config.file_level, LevelFilter::WARN);
+            assert_eq!(config.file_format, LogFormat::Json);
+            assert_eq!(config.timezone, LogTimezone::Fixed(8 * 3600));
+            assert_eq!(config.rotation, Rotation::Hourly);
+            assert_eq!(config.rotation_offset, chrono::Duration::hours(4));
+            assert_eq!(config.max_files, Some(24));
+            assert_eq!(config.log_dir.as_deref(), Some(Path::new("/var/log/app")));
+            assert_eq!(config.info_file_mode, InfoFileMode::Exact);
+            assert_eq!(
+                config.file_routes,
+                Some(vec![FileRoute::exact("warn", LevelFilter::WARN)])
+            );
+        }
+
+        #[test]
+        fn test_yaml_round_trip() {
+            round_trip(ConfigFormat::Yaml);
+        }
+
+        #[test]
+        fn test_toml_round_trip() {
+            round_trip(ConfigFormat::Toml);
+        }
+
+        #[test]
+        fn test_section_strings() {
+            let raw = ConfigBuilder::new()
+                .add_yaml_str("log:\n  console_level: DEBUG\n  ansi: false\n  rotation: Never\n")
+                .build_raw()
+                .unwrap();
+            let config = LogConfig::from_config_section(&raw, "log").unwrap();
+            assert_eq!(config.console_level, LevelFilter::DEBUG);
+            assert!(!config.enable_ansi);
+            assert_eq!(config.rotation, Rotation::Never);
+            assert_eq!(config.file_level, LevelFilter::INFO);
+
+            let raw = ConfigBuilder::new()
+                .add_yaml_str("log:\n  console_level: loud\n")
+                .build_raw()
+                .unwrap();
+            match LogConfig::from_config_section(&raw, "log") {
+                Err(ClamberError::ConfigParseError { details, .. }) => {
+                    assert!(details.contains("loud"), "{}", details)
+                }
+                other => panic!("expected parse error, got {:?}", other),
+            }
+            assert!(matches!(
+                LogConfig::from_config_section(&raw, "missing"),
+                Err(ClamberError::ConfigValidationError { .. })
+            ));
+
+            // format 同时设置两者，单独的 console_format 优先
+            let raw = ConfigBuilder::new()
+                .add_yaml_str("log:\n  format: json\n  console_format: text\n")
+                .build_raw()
+                .unwrap();
+            let config = LogConfig::from_config_section(&raw, "log").unwrap();
+            assert_eq!(config.console_format, LogFormat::Text);
+            assert_eq!(config.file_format, LogFormat::Json);
+            let raw = ConfigBuilder::new()
+                .add_yaml_str("log:\n  format: xml\n")
+                .build_raw()
+                .unwrap();
+            assert!(matches!(
+                LogConfig::from_config_section(&raw, "log"),
+                Err(ClamberError::ConfigParseError { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_resolve_log_dir() {
+        let config = LogConfig::new();
+        assert_eq!(resolve_log_dir(None, &config), Path::new("logs"));
+        assert_eq!(
+            resolve_log_dir(Some("/var/log/myapp"), &config),
+            Path::new("/var/log/myapp/logs")
+        );
+        assert_eq!(
+            resolve_log_dir(Some("/var/log/myapp/"), &config),
+            Path::new("/var/log/myapp/logs")
+        );
+        assert_eq!(
+            resolve_log_dir(Some("data"), &config),
+            Path::new("data/logs")
+        );
+
+        let config = LogConfig::new().log_dir("/var/log/myapp/");
+        assert_eq!(
+            resolve_log_dir(Some("ignored"), &config),
+            Path::new("/var/log/myapp")
+        );
+    }
+
+    #[test]
+    fn test_log_dir_used_verbatim() {
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("custom");
+        let config = LogConfig::new().ansi(false).log_dir(&target);
+
+        let log_dir = resolve_log_dir(None, &config);
+        let (layers, handle) = build_layers("exact", &log_dir, &config, std::io::sink).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || tracing::error!("placed"));
+        handle.flush();
+
+        assert_eq!(handle.log_directory(), target);
+        assert!(read_log_files(&target, "exact-info.log").contains("placed"));
+        assert!(read_log_files(&target, "exact-error.log").contains("placed"));
+        assert!(!target.join("logs").exists());
+    }
+
+    #[test]
+    fn test_console_only_skips_directory() {
+        let dir = tempdir().unwrap();
+        // 日志目录的父路径是普通文件，任何创建尝试都会失败
+        let blocker = dir.path().join("read-only");
+        fs::write(&blocker, "not a directory").unwrap();
+        let log_dir = blocker.join("logs");
+        let console = CapturingLogger::new();
+        let config = LogConfig::new().ansi(false).file_enabled(false);
+
+        let (layers, handle) = build_layers("console", &log_dir, &config, console.clone()).unwrap();
+        assert_eq!(
+            handle.output(),
+            LogOutput {
+                console: true,
+                files: false,
+                extra_writers: 0
+            }
+        );
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || tracing::error!("stdout only"));
+        handle.flush();
+
+        assert!(console.contains("stdout only"));
+        assert!(!log_dir.exists());
+    }
+
+    #[test]
+    fn test_console_target_routes_by_level() {
+        let dir = tempdir().unwrap();
+        let stdout = CapturingLogger::new();
+        let stderr = CapturingLogger::new();
+        let config = LogConfig::new()
+            .ansi(false)
+            .file_enabled(false)
+            .console_target("split".parse().unwrap());
+        let console = console_layers(&config, stdout.clone(), stderr.clone()).unwrap();
+        let (layers, _handle) = build_layers_with("split", dir.path(), &config, console).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("below console level");
+            tracing::info!("info to stdout");
+            tracing::warn!("warn to stderr");
+            tracing::error!("error to stderr");
+        });
+        assert_eq!(stdout.lines().len(), 1);
+        assert!(stdout.contains("info to stdout"));
+        assert_eq!(stderr.lines().len(), 2);
+        assert!(stderr.contains("warn to stderr") && stderr.contains("error to stderr"));
+
+        for (target, to_stderr) in [
+            (ConsoleTarget::Stdout, false),
+            (ConsoleTarget::Stderr, true),
+            (
+                ConsoleTarget::SplitByLevel {
+                    error_to_stderr: false,
+                },
+                false,
+            ),
+        ] {
+            let stdout = CapturingLogger::new();
+            let stderr = CapturingLogger::new();
+            let config = LogConfig::new().ansi(false).console_target(target);
+            let console = console_layers(&config, stdout.clone(), stderr.clone()).unwrap();
+            let subscriber = tracing_subscriber::registry().with(console);
+            tracing::subscriber::with_default(subscriber, || {
+                tracing::info!("info record");
+                tracing::error!("error record");
+            });
+            let (used, unused) = if to_stderr {
+                (stderr, stdout)
+            } else {
+                (stdout, stderr)
+            };
+            assert_eq!(used.lines().len(), 2, "{}", target);
+            assert!(unused.contents().is_empty(), "{}", target);
+        }
+
+        assert_eq!(
+            serde_json::to_value(ConsoleTarget::SplitByLevel {
+                error_to_stderr: true
+            })
+            .unwrap(),
+            "split"
+        );
+        assert!("syslog".parse::<ConsoleTarget>().is_err());
+    }
+
+    #[test]
+    fn test_file_only_skips_console() {
+        let dir = tempdir().unwrap();
+        let console = CapturingLogger::new();
+        let config = LogConfig::new().ansi(false).console_enabled(false);
+
+        let (layers, handle) = build_layers("batch", dir.path(), &config, console.clone()).unwrap();
+        assert!(!handle.output().console);
+        assert!(handle.output().files);
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || tracing::info!("file only"));
+        handle.flush();
+
+        assert!(console.contents().is_empty());
+        assert!(read_log_files(dir.path(), "batch-info.log").contains("file only"));
+    }
+
+    #[test]
+    fn test_default_routes_include_warn_file() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new().ansi(false);
+
+        let (layers, handle) =
+            build_layers("routes", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("routed info");
+            tracing::warn!("routed warn");
+            tracing::error!("routed error");
+        });
+        handle.flush();
+
+        let warn = read_log_files(dir.path(), "routes-warn.log");
+        assert!(warn.contains("routed warn"));
+        assert!(!warn.contains("routed info"));
+        assert!(!warn.contains("routed error"));
+        let info = read_log_files(dir.path(), "routes-info.log");
+        assert!(info.contains("routed warn") && info.contains("routed error"));
+        assert!(!read_log_files(dir.path(), "routes-error.log").contains("routed warn"));
+    }
+
+    #[test]
+    fn test_single_custom_route() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new()
+            .ansi(false)
+            .file_routes(vec![FileRoute::new(
+                "all",
+                LevelFilter::TRACE,
+                LevelFilter::ERROR,
+            )]);
+
+        let (layers, handle) =
+            build_layers("single", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::trace!("single trace");
+            tracing::info!("single info");
+            tracing::error!("single error");
+        });
+        handle.flush();
+
+        let all = read_log_files(dir.path(), "single-all.log");
+        for record in ["single trace", "single info", "single error"] {
+            assert!(all.contains(record));
+        }
+        let files: Vec<_> = fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(files.len(), 1);
+    }
+
+    #[test]
+    fn test_exact_info_file_mode() {
+        let dir = tempdir().unwrap();
+        let config = LogConfig::new()
+            .ansi(false)
+            .info_file_mode(InfoFileMode::Exact);
+
+        let (layers, guards) =
+            build_layers("exact", dir.path(), &config, CapturingLogger::new()).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layers);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("exact info");
+            tracing::warn!("exact warn");
+        });
+        drop(guards);
+
+        let info = read_log_files(dir.path(), "exact-info.log");
+        assert!(info.contains("exact info"));
+        assert!(!info.contains("exact warn"));
+    }
 }
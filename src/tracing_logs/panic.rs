@@ -0,0 +1,63 @@
+//! panic 钩子：将 panic 信息记录为 ERROR 日志，再交给之前的钩子处理（默认打印到 stderr）。
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALL: Once = Once::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 启用 panic 记录；钩子在进程内只安装一次，重复调用不会层层嵌套
+pub(crate) fn install() {
+    ENABLED.store(true, Ordering::SeqCst);
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ENABLED.load(Ordering::SeqCst) {
+                log_panic(info);
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// 停止记录 panic，之前的钩子照常执行
+pub(crate) fn uninstall() {
+    ENABLED.store(false, Ordering::SeqCst);
+}
+
+fn log_panic(info: &PanicHookInfo<'_>) {
+    let message = payload_message(info.payload());
+    let location = info
+        .location()
+        .map(|location| {
+            format!(
+                "{}:{}:{}",
+                location.file(),
+                location.line(),
+                location.column()
+            )
+        })
+        .unwrap_or_else(|| "<unknown>".to_string());
+    let thread = std::thread::current();
+    let thread = thread.name().unwrap_or("<unnamed>");
+
+    // 是否采集调用栈遵循 RUST_BACKTRACE / RUST_LIB_BACKTRACE
+    let backtrace = Backtrace::capture();
+    if backtrace.status() == BacktraceStatus::Captured {
+        tracing::error!(target: "panic", thread, location = %location, backtrace = %backtrace, "panic: {}", message);
+    } else {
+        tracing::error!(target: "panic", thread, location = %location, "panic: {}", message);
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
+}
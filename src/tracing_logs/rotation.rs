@@ -0,0 +1,482 @@
+//! 按时区与偏移计算轮转边界的滚动文件 writer，可按文件数保留最近的日志。
+use super::time::LogTimezone;
+use crate::error::{ClamberError, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 时钟函数，便于测试中控制当前时间
+pub(crate) type ClockFn = Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>;
+
+/// 日志文件轮转周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Rotation {
+    /// 每分钟，文件后缀 `YYYY-MM-DD-HH-mm`
+    Minutely,
+    /// 每小时，文件后缀 `YYYY-MM-DD-HH`
+    Hourly,
+    /// 每天，文件后缀 `YYYY-MM-DD`
+    #[default]
+    Daily,
+    /// 不轮转，始终写入无后缀的同一个文件
+    Never,
+}
+
+impl FromStr for Rotation {
+    type Err = ClamberError;
+
+    /// 忽略大小写解析 `minutely` / `hourly` / `daily` / `never`
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "minutely" => Ok(Rotation::Minutely),
+            "hourly" => Ok(Rotation::Hourly),
+            "daily" => Ok(Rotation::Daily),
+            "never" => Ok(Rotation::Never),
+            _ => Err(ClamberError::ConfigValidationError {
+                details: format!(
+                    "无效的日志轮转周期 {}，可选 minutely / hourly / daily / never",
+                    value
+                ),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rotation::Minutely => "minutely",
+            Rotation::Hourly => "hourly",
+            Rotation::Daily => "daily",
+            Rotation::Never => "never",
+        })
+    }
+}
+
+/// 计算 `now` 所在轮转周期的文件后缀与下一次轮转时刻，[`Rotation::Never`] 返回 None
+///
+/// 周期按 `timezone` 计算，并整体后移 `offset`（如 4 小时表示凌晨 4 点切割）。
+pub(crate) fn period(
+    rotation: Rotation,
+    timezone: LogTimezone,
+    offset: Duration,
+    now: DateTime<Utc>,
+) -> Option<(String, DateTime<Utc>)> {
+    let local = timezone.convert(now);
+    let shifted = (local - offset).naive_local();
+    let (start, length, format): (NaiveDateTime, Duration, &str) = match rotation {
+        Rotation::Never => return None,
+        Rotation::Minutely => (
+            truncate(shifted.with_second(0)),
+            Duration::minutes(1),
+            "%Y-%m-%d-%H-%M",
+        ),
+        Rotation::Hourly => (
+            truncate(shifted.with_minute(0).and_then(|t| t.with_second(0))),
+            Duration::hours(1),
+            "%Y-%m-%d-%H",
+        ),
+        Rotation::Daily => (
+            shifted.date().and_hms_opt(0, 0, 0).unwrap_or_default(),
+            Duration::days(1),
+            "%Y-%m-%d",
+        ),
+    };
+
+    let next_rollover = (start + length + offset)
+        .and_local_timezone(*local.offset())
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or(now + length);
+
+    Some((start.format(format).to_string(), next_rollover))
+}
+
+fn truncate(time: Option<NaiveDateTime>) -> NaiveDateTime {
+    time.and_then(|t| t.with_nanosecond(0)).unwrap_or_default()
+}
+
+/// 按日轮转的便捷形式
+#[cfg(test)]
+fn daily_period(
+    timezone: LogTimezone,
+    offset: Duration,
+    now: DateTime<Utc>,
+) -> (String, DateTime<Utc>) {
+    period(Rotation::Daily, timezone, offset, now).unwrap()
+}
+
+/// 轮转策略：周期、时区、切割偏移与保留的文件数
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RotationPolicy {
+    pub(crate) rotation: Rotation,
+    pub(crate) timezone: LogTimezone,
+    pub(crate) offset: Duration,
+    pub(crate) max_files: Option<usize>,
+}
+
+/// 日志文件名：`{prefix}.{后缀}{extension}`，不轮转时为 `{prefix}{extension}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileName {
+    pub(crate) prefix: String,
+    /// 追加在时间后缀之后的扩展名（如 `.log`），为空时不追加
+    pub(crate) extension: String,
+}
+
+impl FileName {
+    pub(crate) fn new(prefix: impl Into<String>, extension: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            extension: extension.into(),
+        }
+    }
+
+    fn for_period(&self, suffix: Option<&str>) -> String {
+        match suffix {
+            Some(suffix) => format!("{}.{}{}", self.prefix, suffix, self.extension),
+            None => format!("{}{}", self.prefix, self.extension),
+        }
+    }
+
+    /// 是否为本 writer 写出的文件（当前或轮转后的）
+    fn matches(&self, name: &str) -> bool {
+        if name == self.for_period(None) {
+            return true;
+        }
+        name.strip_prefix(&self.prefix)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .and_then(|rest| rest.strip_suffix(&self.extension))
+            .is_some_and(|suffix| !suffix.is_empty())
+    }
+}
+
+impl From<String> for FileName {
+    fn from(prefix: String) -> Self {
+        Self::new(prefix, "")
+    }
+}
+
+impl From<&str> for FileName {
+    fn from(prefix: &str) -> Self {
+        Self::new(prefix, "")
+    }
+}
+
+/// 滚动文件 writer，文件名见 [`FileName`]
+pub(crate) struct RollingFileWriter {
+    dir: PathBuf,
+    name: FileName,
+    policy: RotationPolicy,
+    clock: ClockFn,
+    file: File,
+    next_rollover: Option<DateTime<Utc>>,
+}
+
+impl RollingFileWriter {
+    /// 创建使用系统时钟的 writer
+    pub(crate) fn new(
+        dir: &Path,
+        name: impl Into<FileName>,
+        policy: RotationPolicy,
+    ) -> io::Result<Self> {
+        Self::with_clock(dir, name, policy, Arc::new(Utc::now))
+    }
+
+    /// 创建使用自定义时钟的 writer
+    pub(crate) fn with_clock(
+        dir: &Path,
+        name: impl Into<FileName>,
+        policy: RotationPolicy,
+        clock: ClockFn,
+    ) -> io::Result<Self> {
+        let dir = dir.to_path_buf();
+        let name = name.into();
+        let (file, next_rollover) = open_period_file(&dir, &name, &policy, clock())?;
+        let writer = Self {
+            dir,
+            name,
+            policy,
+            clock,
+            file,
+            next_rollover,
+        };
+        writer.prune()?;
+        Ok(writer)
+    }
+
+    fn roll_if_needed(&mut self) -> io::Result<()> {
+        let now = (self.clock)();
+        if self.next_rollover.is_some_and(|next| now >= next) {
+            self.file.flush()?;
+            let (file, next_rollover) = open_period_file(&self.dir, &self.name, &self.policy, now)?;
+            self.file = file;
+            self.next_rollover = next_rollover;
+            self.prune()?;
+        }
+        Ok(())
+    }
+
+    /// 按文件名（后缀即时间）排序，只保留最新的 `max_files` 个（至少保留当前文件）
+    fn prune(&self) -> io::Result<()> {
+        let Some(max_files) = self.policy.max_files else {
+            return Ok(());
+        };
+        let mut files: Vec<String> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| self.name.matches(name))
+            .collect();
+        if files.len() <= max_files.max(1) {
+            return Ok(());
+        }
+        files.sort();
+        let excess = files.len() - max_files.max(1);
+        for name in &files[..excess] {
+            fs::remove_file(self.dir.join(name))?;
+        }
+        Ok(())
+    }
+}
+
+/// 打开 `now` 所在周期的文件，返回文件与下一次轮转时刻
+fn open_period_file(
+    dir: &Path,
+    name: &FileName,
+    policy: &RotationPolicy,
+    now: DateTime<Utc>,
+) -> io::Result<(File, Option<DateTime<Utc>>)> {
+    let period = period(policy.rotation, policy.timezone, policy.offset, now);
+    let name = name.for_period(period.as_ref().map(|(suffix, _)| suffix.as_str()));
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(name))?;
+    Ok((file, period.map(|(_, next)| next)))
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.roll_if_needed()?;
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::fs;
+    use std::sync::Mutex;
+    use tempfile::tempdir;
+
+    const BEIJING: LogTimezone = LogTimezone::Fixed(8 * 3600);
+
+    fn policy(rotation: Rotation, max_files: Option<usize>) -> RotationPolicy {
+        RotationPolicy {
+            rotation,
+            timezone: BEIJING,
+            offset: Duration::zero(),
+            max_files,
+        }
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    #[test]
+    fn test_daily_period_utc() {
+        let (suffix, next) = daily_period(
+            LogTimezone::Utc,
+            Duration::zero(),
+            utc(2024, 5, 1, 15, 0, 0),
+        );
+        assert_eq!(suffix, "2024-05-01");
+        assert_eq!(next, utc(2024, 5, 2, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_daily_period_follows_timezone() {
+        // 北京时间 2024-05-01 23:59:59
+        let (suffix, next) = daily_period(BEIJING, Duration::zero(), utc(2024, 5, 1, 15, 59, 59));
+        assert_eq!(suffix, "2024-05-01");
+        assert_eq!(next, utc(2024, 5, 1, 16, 0, 0));
+
+        // 北京时间 2024-05-02 00:00:00
+        let (suffix, _) = daily_period(BEIJING, Duration::zero(), utc(2024, 5, 1, 16, 0, 0));
+        assert_eq!(suffix, "2024-05-02");
+    }
+
+    #[test]
+    fn test_daily_period_with_offset() {
+        // 北京时间凌晨 4 点切割：05-02 03:59 仍属于 05-01
+        let offset = Duration::hours(4);
+        let (suffix, next) = daily_period(BEIJING, offset, utc(2024, 5, 1, 19, 59, 0));
+        assert_eq!(suffix, "2024-05-01");
+        assert_eq!(next, utc(2024, 5, 1, 20, 0, 0));
+
+        let (suffix, _) = daily_period(BEIJING, offset, utc(2024, 5, 1, 20, 0, 0));
+        assert_eq!(suffix, "2024-05-02");
+    }
+
+    #[test]
+    fn test_writer_rolls_at_boundary() {
+        let dir = tempdir().unwrap();
+        let now = Arc::new(Mutex::new(utc(2024, 5, 1, 15, 59, 59)));
+        let clock_now = now.clone();
+        let clock: ClockFn = Arc::new(move || *clock_now.lock().unwrap());
+
+        let mut writer = RollingFileWriter::with_clock(
+            dir.path(),
+            "svc.log",
+            policy(Rotation::Daily, None),
+            clock,
+        )
+        .unwrap();
+        writer.write_all(b"before midnight\n").unwrap();
+
+        *now.lock().unwrap() = utc(2024, 5, 1, 16, 0, 0);
+        writer.write_all(b"after midnight\n").unwrap();
+        writer.flush().unwrap();
+
+        let first = fs::read_to_string(dir.path().join("svc.log.2024-05-01")).unwrap();
+        let second = fs::read_to_string(dir.path().join("svc.log.2024-05-02")).unwrap();
+        assert_eq!(first, "before midnight\n");
+        assert_eq!(second, "after midnight\n");
+    }
+
+    #[test]
+    fn test_hourly_and_minutely_periods() {
+        let now = utc(2024, 5, 1, 15, 59, 30);
+        let (suffix, next) = period(Rotation::Hourly, BEIJING, Duration::zero(), now).unwrap();
+        assert_eq!(suffix, "2024-05-01-23");
+        assert_eq!(next, utc(2024, 5, 1, 16, 0, 0));
+
+        let (suffix, next) = period(Rotation::Minutely, BEIJING, Duration::zero(), now).unwrap();
+        assert_eq!(suffix, "2024-05-01-23-59");
+        assert_eq!(next, utc(2024, 5, 1, 16, 0, 0));
+
+        assert!(period(Rotation::Never, BEIJING, Duration::zero(), now).is_none());
+    }
+
+    #[test]
+    fn test_never_rotation_writes_single_file() {
+        let dir = tempdir().unwrap();
+        let now = Arc::new(Mutex::new(utc(2024, 5, 1, 15, 0, 0)));
+        let clock_now = now.clone();
+        let clock: ClockFn = Arc::new(move || *clock_now.lock().unwrap());
+
+        let mut writer = RollingFileWriter::with_clock(
+            dir.path(),
+            "svc-info.log",
+            policy(Rotation::Never, None),
+            clock,
+        )
+        .unwrap();
+        writer.write_all(b"day one\n").unwrap();
+        *now.lock().unwrap() = utc(2024, 5, 3, 15, 0, 0);
+        writer.write_all(b"day three\n").unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(file_names(dir.path()), ["svc-info.log"]);
+        let contents = fs::read_to_string(dir.path().join("svc-info.log")).unwrap();
+        assert_eq!(contents, "day one\nday three\n");
+    }
+
+    #[test]
+    fn test_max_files_retention() {
+        let dir = tempdir().unwrap();
+        // 已有的旧文件与其他前缀的文件
+        for name in [
+            "svc-info.log.2024-04-28",
+            "svc-info.log.2024-04-29",
+            "svc-error.log.2024-04-28",
+        ] {
+            fs::write(dir.path().join(name), "old\n").unwrap();
+        }
+        let now = Arc::new(Mutex::new(utc(2024, 5, 1, 15, 0, 0)));
+        let clock_now = now.clone();
+        let clock: ClockFn = Arc::new(move || *clock_now.lock().unwrap());
+
+        // 启动时清理
+        let mut writer = RollingFileWriter::with_clock(
+            dir.path(),
+            "svc-info.log",
+            policy(Rotation::Daily, Some(2)),
+            clock,
+        )
+        .unwrap();
+        assert_eq!(
+            file_names(dir.path()),
+            [
+                "svc-error.log.2024-04-28",
+                "svc-info.log.2024-04-29",
+                "svc-info.log.2024-05-01"
+            ]
+        );
+
+        // 轮转时清理
+        *now.lock().unwrap() = utc(2024, 5, 1, 16, 0, 0);
+        writer.write_all(b"next day\n").unwrap();
+        assert_eq!(
+            file_names(dir.path()),
+            [
+                "svc-error.log.2024-04-28",
+                "svc-info.log.2024-05-01",
+                "svc-info.log.2024-05-02"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extension_after_period_suffix() {
+        let dir = tempdir().unwrap();
+        for name in [
+            "api.info.2024-04-29.log",
+            "api.info.2024-04-30.log",
+            "api.error.2024-04-29.log",
+        ] {
+            fs::write(dir.path().join(name), "old\n").unwrap();
+        }
+        let clock: ClockFn = Arc::new(|| utc(2024, 5, 1, 15, 0, 0));
+
+        let mut writer = RollingFileWriter::with_clock(
+            dir.path(),
+            FileName::new("api.info", ".log"),
+            policy(Rotation::Daily, Some(2)),
+            clock,
+        )
+        .unwrap();
+        writer.write_all(b"templated\n").unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(
+            file_names(dir.path()),
+            [
+                "api.error.2024-04-29.log",
+                "api.info.2024-04-30.log",
+                "api.info.2024-05-01.log"
+            ]
+        );
+    }
+}
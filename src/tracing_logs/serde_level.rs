@@ -0,0 +1,33 @@
+//! 将 [`LevelFilter`] 序列化为小写级别字符串，反序列化时忽略大小写。
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use tracing::metadata::LevelFilter;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Section {
+//!     #[serde(with = "clamber_core::tracing_logs::serde_level")]
+//!     level: LevelFilter,
+//! }
+//!
+//! let section: Section = serde_json::from_str(r#"{"level":"DEBUG"}"#).unwrap();
+//! assert_eq!(section.level, LevelFilter::DEBUG);
+//! assert_eq!(serde_json::to_string(&section).unwrap(), r#"{"level":"debug"}"#);
+//! ```
+use serde::de::{self, Deserialize};
+use serde::{Deserializer, Serializer};
+use tracing::metadata::LevelFilter;
+
+pub fn serialize<S: Serializer>(level: &LevelFilter, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&level.to_string().to_lowercase())
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LevelFilter, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value.parse().map_err(|_| {
+        de::Error::custom(format!(
+            "无效的日志级别 {}，可选 off / error / warn / info / debug / trace",
+            value
+        ))
+    })
+}
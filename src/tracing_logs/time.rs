@@ -0,0 +1,95 @@
+//! 日志时区与时间戳格式化。
+use crate::error::{ClamberError, Result};
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::time::FormatTime;
+
+/// 日志时间戳与轮转边界使用的时区
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum LogTimezone {
+    /// UTC
+    #[default]
+    Utc,
+    /// 系统本地时区
+    Local,
+    /// 固定偏移（相对 UTC 的秒数，如北京时间为 8 * 3600）
+    Fixed(i32),
+}
+
+impl LogTimezone {
+    /// 获取指定时刻在该时区下的偏移
+    pub(crate) fn offset_at(&self, instant: DateTime<Utc>) -> FixedOffset {
+        match self {
+            LogTimezone::Utc => Utc.fix(),
+            LogTimezone::Local => Local.offset_from_utc_datetime(&instant.naive_utc()).fix(),
+            LogTimezone::Fixed(seconds) => FixedOffset::east_opt(*seconds).unwrap_or(Utc.fix()),
+        }
+    }
+
+    /// 将 UTC 时刻转换为该时区下的时间
+    pub(crate) fn convert(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        instant.with_timezone(&self.offset_at(instant))
+    }
+}
+
+impl FromStr for LogTimezone {
+    type Err = ClamberError;
+
+    /// 解析 `utc` / `local`（忽略大小写）或 `+08:00` 形式的固定偏移
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "utc" => Ok(LogTimezone::Utc),
+            "local" => Ok(LogTimezone::Local),
+            _ => value
+                .parse::<FixedOffset>()
+                .map(|offset| LogTimezone::Fixed(offset.local_minus_utc()))
+                .map_err(|e| ClamberError::ConfigValidationError {
+                    details: format!("无效的时区 {}: {}", value, e),
+                }),
+        }
+    }
+}
+
+impl fmt::Display for LogTimezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogTimezone::Utc => f.write_str("utc"),
+            LogTimezone::Local => f.write_str("local"),
+            LogTimezone::Fixed(_) => write!(f, "{}", self.offset_at(Utc::now())),
+        }
+    }
+}
+
+/// 按配置时区与格式输出时间戳
+#[derive(Debug, Clone)]
+pub(crate) struct LogTimer {
+    timezone: LogTimezone,
+    format: String,
+}
+
+impl LogTimer {
+    pub(crate) fn new(timezone: LogTimezone, format: impl Into<String>) -> Self {
+        Self {
+            timezone,
+            format: format.into(),
+        }
+    }
+
+    /// 当前时间的格式化字符串
+    pub(crate) fn now_string(&self) -> String {
+        self.timezone
+            .convert(Utc::now())
+            .format(&self.format)
+            .to_string()
+    }
+}
+
+impl FormatTime for LogTimer {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        write!(w, "{}", self.now_string())
+    }
+}
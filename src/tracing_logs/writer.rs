@@ -0,0 +1,192 @@
+//! 自定义日志 writer：支持把日志额外写入任意 MakeWriter，并内置内存环形缓冲区。
+use super::LogFormat;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use tracing::metadata::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+/// 额外挂载的日志输出目标
+#[derive(Clone)]
+pub struct ExtraWriter {
+    writer: Arc<BoxMakeWriter>,
+    /// 该输出目标的日志级别
+    pub level: LevelFilter,
+    /// 该输出目标的输出格式
+    pub format: LogFormat,
+}
+
+impl ExtraWriter {
+    /// 创建额外输出目标
+    pub fn new<W>(writer: W, level: LevelFilter, format: LogFormat) -> Self
+    where
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        Self {
+            writer: Arc::new(BoxMakeWriter::new(writer)),
+            level,
+            format,
+        }
+    }
+
+    /// 获取可挂入 layer 的共享 writer
+    pub(crate) fn make_writer(&self) -> SharedMakeWriter {
+        SharedMakeWriter(self.writer.clone())
+    }
+}
+
+impl fmt::Debug for ExtraWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtraWriter")
+            .field("level", &self.level)
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 对 [`BoxMakeWriter`] 的共享包装，使 LogConfig 可以 Clone
+pub(crate) struct SharedMakeWriter(Arc<BoxMakeWriter>);
+
+impl<'a> MakeWriter<'a> for SharedMakeWriter {
+    type Writer = Box<dyn Write + 'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.0.make_writer()
+    }
+}
+
+/// 内存环形缓冲区 writer：只保留最近 `capacity` 行日志
+#[derive(Debug, Clone)]
+pub struct RingBufferWriter {
+    capacity: usize,
+    lines: Arc<Mutex<VecDeque<String>>>,
+}
+
+impl RingBufferWriter {
+    /// 创建保留最近 `capacity` 行的环形缓冲区
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    /// 获取当前保留的日志行（从旧到新）
+    pub fn snapshot(&self) -> Vec<String> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// 当前保留的行数
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 缓冲区是否为空
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// 清空缓冲区
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<String>> {
+        // 缓冲区内容始终保持一致，锁中毒时继续使用
+        self.lines.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push_line(&self, line: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut lines = self.lock();
+        while lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+}
+
+impl<'a> MakeWriter<'a> for RingBufferWriter {
+    type Writer = RingBufferHandle;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RingBufferHandle {
+            target: self.clone(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// 单次写入的句柄：按行切分后写入环形缓冲区
+pub struct RingBufferHandle {
+    target: RingBufferWriter,
+    pending: Vec<u8>,
+}
+
+impl RingBufferHandle {
+    fn drain_lines(&mut self) {
+        while let Some(pos) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line[..line.len() - 1]).into_owned();
+            self.target.push_line(line);
+        }
+    }
+}
+
+impl Write for RingBufferHandle {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        self.drain_lines();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for RingBufferHandle {
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            let line = String::from_utf8_lossy(&self.pending).into_owned();
+            self.pending.clear();
+            self.target.push_line(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_overwrites_oldest_lines() {
+        let ring = RingBufferWriter::new(3);
+        for i in 0..5 {
+            let mut handle = ring.make_writer();
+            writeln!(handle, "line {}", i).unwrap();
+        }
+
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.snapshot(), vec!["line 2", "line 3", "line 4"]);
+
+        ring.clear();
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_ring_buffer_splits_and_keeps_partial_lines() {
+        let ring = RingBufferWriter::new(10);
+        {
+            let mut handle = ring.make_writer();
+            handle.write_all(b"first\nsec").unwrap();
+            handle.write_all(b"ond\npartial").unwrap();
+        }
+
+        assert_eq!(ring.snapshot(), vec!["first", "second", "partial"]);
+    }
+}
@@ -0,0 +1,139 @@
+//! JwtLayer 与 Claims 提取器的集成测试：通过最小的 axum 路由发起请求
+use axum::Router;
+use axum::body::{Body, to_bytes};
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use axum::routing::get;
+use chrono::{Duration, Utc};
+use clamber_core::token::TokenType;
+use clamber_core::token::axum::{Claims, JwtLayer};
+use clamber_core::{JwtConfig, JwtManager};
+use serde::{Deserialize, Serialize};
+use tower::ServiceExt;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct User {
+    id: String,
+    role: String,
+}
+
+fn config() -> JwtConfig {
+    JwtConfig::new("axum_test_secret", 1)
+}
+
+fn user() -> User {
+    User {
+        id: "42".to_string(),
+        role: "admin".to_string(),
+    }
+}
+
+async fn me(Claims(user): Claims<User>) -> String {
+    format!("{}:{}", user.id, user.role)
+}
+
+fn app(layer: JwtLayer<User>) -> Router {
+    Router::new()
+        .route("/me", get(me))
+        .route_layer(layer)
+        .route("/health", get(|| async { "ok" }))
+}
+
+async fn send(app: Router, path: &str, token: Option<&str>) -> (StatusCode, Response<Body>) {
+    let mut request = Request::builder().uri(path);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = app
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    (response.status(), response)
+}
+
+async fn body_json(response: Response<Body>) -> serde_json::Value {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+async fn body_text(response: Response<Body>) -> String {
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_protected_and_open_routes() {
+    let manager = JwtManager::new(config());
+    let token = manager.generate_token(&user()).unwrap();
+    let app = app(JwtLayer::new(manager));
+
+    let (status, response) = send(app.clone(), "/me", Some(&token)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body_text(response).await, "42:admin");
+
+    // 开放路由不需要 token
+    let (status, response) = send(app.clone(), "/health", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body_text(response).await, "ok");
+
+    let (status, response) = send(app, "/me", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        response.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+        "Bearer"
+    );
+    assert_eq!(body_json(response).await["code"], "JWT-001");
+}
+
+#[tokio::test]
+async fn test_verification_failures() {
+    let issued_at = Utc::now() - Duration::days(30);
+    let expired = JwtManager::new(config())
+        .with_clock(move || issued_at)
+        .generate_token(&user())
+        .unwrap();
+    let app = app(JwtLayer::new(JwtManager::new(config())));
+
+    let (status, response) = send(app.clone(), "/me", Some(&expired)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body_json(response).await["code"], "JWT-003");
+
+    let forged = JwtManager::new(JwtConfig::new("other_secret", 1))
+        .generate_token(&user())
+        .unwrap();
+    let (status, response) = send(app.clone(), "/me", Some(&forged)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body_json(response).await["code"], "JWT-002");
+
+    let (status, _) = send(app, "/me", Some("not-a-token")).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_wrong_audience_and_token_type_are_forbidden() {
+    let manager = JwtManager::new(config());
+    let pair = manager.generate_token_pair(&user()).unwrap();
+    let app_access = app(JwtLayer::new(manager).require_token_type(TokenType::Access));
+
+    let (status, _) = send(app_access.clone(), "/me", Some(&pair.access_token)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, response) = send(app_access, "/me", Some(&pair.refresh_token)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body_json(response).await["code"], "JWT-010");
+
+    let mobile = JwtManager::new(config().audience("mobile"))
+        .generate_token(&user())
+        .unwrap();
+    let app_web = app(JwtLayer::new(JwtManager::new(config().audience("web"))));
+    let (status, response) = send(app_web, "/me", Some(&mobile)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body_json(response).await["code"], "JWT-008");
+}
+
+#[tokio::test]
+async fn test_claims_without_layer_is_rejected() {
+    let app = Router::new().route("/me", get(me));
+    let (status, response) = send(app, "/me", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body_json(response).await["code"], "JWT-001");
+}
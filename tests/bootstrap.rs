@@ -0,0 +1,101 @@
+//! AppContext 引导集成测试（会初始化全局日志，单独成为一个测试二进制）
+use clamber_core::AppContext;
+use clamber_core::config::ConfigBuilder;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_app_context_from_config_file() {
+    let dir = tempdir().unwrap();
+    let log_dir = dir.path().join("logs");
+    let config_path = dir.path().join("app.yaml");
+    fs::write(
+        &config_path,
+        format!(
+            r#"
+name: demo
+log:
+  dir: "{}"
+  format: json
+jwt:
+  secret: integration-secret
+  expire_days: 2
+"#,
+            log_dir.display()
+        ),
+    )
+    .unwrap();
+
+    let ctx =
+        AppContext::init_with("demo", ConfigBuilder::new().add_yaml_file(&config_path)).unwrap();
+
+    // jwt 段生效：其他密钥无法验证
+    let token = ctx.jwt().generate_token(&"payload").unwrap();
+    let decoded: String = ctx.jwt().verify_token(&token).unwrap();
+    assert_eq!(decoded, "payload");
+    let other = clamber_core::JwtManager::new(clamber_core::JwtConfig::new("other", 2));
+    assert!(other.verify_token::<String>(&token).is_err());
+
+    // 缺失 snowflake 段时使用默认配置
+    assert_eq!(ctx.snowflake().worker_id(), 1);
+    assert!(ctx.snowflake().generate_id().unwrap() > 0);
+
+    let name: String = ctx.config_raw().get("name").unwrap();
+    assert_eq!(name, "demo");
+
+    tracing::info!("bootstrap ready");
+    drop(ctx);
+
+    let info_log = fs::read_dir(&log_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| {
+            path.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("demo-info.log")
+        })
+        .expect("info 日志文件应已创建");
+    let contents = fs::read_to_string(info_log).unwrap();
+    assert!(contents.contains("bootstrap ready"));
+    assert!(
+        contents.contains("snowflake"),
+        "缺失段应记录 warn: {}",
+        contents
+    );
+}
+
+#[test]
+fn test_invalid_log_section_is_parse_error() {
+    // 在初始化日志之前失败，不影响同一进程中的其他测试
+    for log in ["console_level: loud", "format: xml", "timezone: mars"] {
+        let builder =
+            ConfigBuilder::new().add_yaml_str(&format!("log:\n  {}\njwt:\n  secret: s\n", log));
+        let err = AppContext::init_with("demo", builder).err().unwrap();
+        assert!(
+            matches!(err, clamber_core::ClamberError::ConfigParseError { .. }),
+            "{}: {}",
+            log,
+            err
+        );
+    }
+}
+
+#[cfg(feature = "strict-default")]
+#[test]
+fn test_missing_jwt_secret_is_rejected() {
+    for yaml in [
+        "name: demo\n",
+        "jwt:\n  expire_days: 1\n",
+        "jwt:\n  secret: \"\"\n",
+    ] {
+        let builder = ConfigBuilder::new().add_yaml_str(yaml);
+        let err = AppContext::init_with("demo", builder).err().unwrap();
+        assert!(
+            matches!(err, clamber_core::ClamberError::JwtKeyError { .. }),
+            "{:?}: {}",
+            yaml,
+            err
+        );
+    }
+}
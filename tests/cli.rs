@@ -0,0 +1,133 @@
+//! clamber 命令行工具的集成测试
+use assert_cmd::Command;
+use clamber_core::token::{JwtConfig, JwtManager};
+use std::fs;
+use tempfile::tempdir;
+
+fn clamber() -> Command {
+    Command::cargo_bin("clamber").unwrap()
+}
+
+fn stdout(cmd: &mut Command) -> String {
+    String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap()
+}
+
+fn json_stdout(cmd: &mut Command) -> serde_json::Value {
+    serde_json::from_str(&stdout(cmd)).unwrap()
+}
+
+fn sample_token(secret: &str) -> String {
+    JwtManager::new(JwtConfig::new(secret, 1))
+        .generate_token(&serde_json::json!({ "user": "alice" }))
+        .unwrap()
+}
+
+#[test]
+fn test_token_inspect() {
+    let token = sample_token("cli-secret");
+
+    let text = stdout(clamber().args(["token", "inspect", &token]));
+    assert!(text.contains("HS256"));
+    assert!(text.contains("未过期"));
+
+    let json = json_stdout(clamber().args(["--json", "token", "inspect", &token]));
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["result"]["expired"], false);
+    assert_eq!(json["result"]["header"]["alg"], "HS256");
+
+    clamber()
+        .args(["token", "inspect", "garbage"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("JWT-002"));
+}
+
+#[test]
+fn test_token_verify() {
+    let token = sample_token("cli-secret");
+
+    let json = json_stdout(clamber().args([
+        "token",
+        "verify",
+        "--secret",
+        "cli-secret",
+        &token,
+        "--json",
+    ]));
+    assert_eq!(json["result"]["payload"]["user"], "alice");
+
+    let output = clamber()
+        .args(["--json", "token", "verify", "--secret", "wrong", &token])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["ok"], false);
+    assert_eq!(json["error"]["code"], "JWT-002");
+}
+
+#[test]
+fn test_id_parse() {
+    // 时间戳 1000ms、工作者 5、序列号 7
+    let id = (1000u64 << 22) | (5 << 12) | 7;
+
+    let json =
+        json_stdout(clamber().args(["--json", "id", "parse", &id.to_string(), "--epoch", "0"]));
+    assert_eq!(json["result"]["worker_id"], 5);
+    assert_eq!(json["result"]["sequence"], 7);
+    assert_eq!(json["result"]["timestamp_ms"], 1000);
+    assert_eq!(json["result"]["time"], "1970-01-01 00:00:01.000");
+
+    let text = stdout(clamber().args(["id", "parse", &id.to_string()]));
+    assert!(text.contains("工作者ID: 5"));
+
+    clamber().args(["id", "parse", "abc"]).assert().failure();
+}
+
+#[test]
+fn test_config_check() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("app.yaml");
+    fs::write(
+        &path,
+        "name: demo\ndatabase:\n  host: localhost\n  port: 5432\n",
+    )
+    .unwrap();
+    let path = path.to_string_lossy().to_string();
+
+    let json = json_stdout(clamber().args(["--json", "config", "check", &path]));
+    assert_eq!(json["result"]["top_level_keys"], 2);
+    assert_eq!(json["result"]["leaf_keys"], 3);
+
+    let text =
+        stdout(clamber().args(["config", "check", &path, "--env-prefix", "CLAMBER_CLI_TEST"]));
+    assert!(text.contains("配置有效"));
+
+    let bad = dir.path().join("bad.yaml");
+    fs::write(&bad, "name: [unclosed").unwrap();
+    clamber()
+        .args(["config", "check", &bad.to_string_lossy()])
+        .assert()
+        .failure();
+
+    clamber()
+        .args([
+            "config",
+            "check",
+            &dir.path().join("missing.yaml").to_string_lossy(),
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_usage_errors() {
+    stdout(clamber().arg("--help"));
+    clamber().args(["token"]).assert().failure();
+    clamber()
+        .args(["token", "verify", "abc"])
+        .assert()
+        .failure();
+}
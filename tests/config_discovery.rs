@@ -0,0 +1,36 @@
+//! 配置自动发现集成测试（会切换当前工作目录，单独成为一个测试二进制）
+use clamber_core::ClamberError;
+use clamber_core::config::auto_load_config;
+use serde::Deserialize;
+use std::fs;
+use tempfile::tempdir;
+
+#[derive(Debug, Deserialize)]
+struct AppConfig {
+    name: String,
+}
+
+#[test]
+fn test_auto_load_config_reports_probed_paths() {
+    let dir = tempdir().unwrap();
+    std::env::set_current_dir(dir.path()).unwrap();
+
+    // 一个文件都没有：列出全部候选路径
+    match auto_load_config::<AppConfig>("myapp", None) {
+        Err(ClamberError::ConfigFileNotFoundError { path, .. }) => {
+            assert!(path.contains("myapp.yaml"));
+            assert!(path.lines().count() > 1);
+        }
+        other => panic!("expected file not found, got {:?}", other),
+    }
+
+    // 存在一个文件时照常加载
+    fs::create_dir(dir.path().join("config")).unwrap();
+    fs::write(
+        dir.path().join("config").join("myapp.toml"),
+        "name = \"svc\"\n",
+    )
+    .unwrap();
+    let config: AppConfig = auto_load_config("myapp", None).unwrap();
+    assert_eq!(config.name, "svc");
+}
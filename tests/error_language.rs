@@ -0,0 +1,22 @@
+//! 全局错误文案语言切换（修改进程级状态，单独成为一个测试二进制）
+use clamber_core::{ClamberError, Language};
+
+#[test]
+fn test_set_language_switches_display() {
+    let err = ClamberError::JwtMissingFieldError {
+        field: "exp".to_string(),
+    };
+    assert_eq!(Language::current(), Language::Zh);
+    assert_eq!(err.to_string(), "JWT缺少必要字段: exp");
+
+    ClamberError::set_language(Language::En);
+    assert_eq!(Language::current(), Language::En);
+    assert_eq!(err.to_string(), "JWT is missing a required field: exp");
+    assert_eq!(
+        err.to_error_response().message,
+        "JWT is missing a required field: exp"
+    );
+
+    ClamberError::set_language(Language::Zh);
+    assert_eq!(err.to_string(), "JWT缺少必要字段: exp");
+}
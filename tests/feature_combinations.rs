@@ -0,0 +1,96 @@
+//! feature 组合检查：按当前启用的 feature 断言对应的导出与错误转换存在，
+//! `scripts/check-features.sh` 逐个组合编译并运行本文件。
+use clamber_core::{ClamberError, ErrorCategory, Result};
+
+fn assert_into_error<E: Into<ClamberError>>() {}
+
+// 错误类型与基础转换不依赖任何 feature
+const _: fn() = || {
+    assert_into_error::<std::io::Error>();
+    assert_into_error::<serde_json::Error>();
+    assert_into_error::<std::num::ParseIntError>();
+};
+
+#[cfg(feature = "config")]
+const _: fn() = || {
+    assert_into_error::<config::ConfigError>();
+    assert_into_error::<toml::de::Error>();
+    assert_into_error::<serde_yaml::Error>();
+    let _ = clamber_core::ConfigBuilder::new;
+    let _ = clamber_core::load_config::<serde_json::Value, &str>;
+};
+
+#[cfg(feature = "token")]
+const _: fn() = || {
+    assert_into_error::<jwt::Error>();
+    assert_into_error::<hmac::digest::InvalidLength>();
+    let _ = clamber_core::JwtManager::new;
+    let _ = clamber_core::verify_token::<serde_json::Value>;
+};
+
+#[cfg(any(feature = "token", feature = "snowflake", feature = "tracing-logs"))]
+const _: fn() = || {
+    assert_into_error::<chrono::ParseError>();
+};
+
+#[cfg(feature = "snowflake")]
+const _: fn() = || {
+    let _ = clamber_core::SnowflakeManager::new;
+    let _ = clamber_core::snowflake_utils::generate_id;
+};
+
+#[cfg(feature = "tracing-logs")]
+const _: fn() = || {
+    let _ = clamber_core::logger_start_with_config;
+    let _ = clamber_core::LogConfig::default;
+};
+
+#[cfg(feature = "bootstrap")]
+const _: fn() = || {
+    let _ = clamber_core::AppContext::init;
+};
+
+// logging 是 tracing-logs 的别名，bootstrap 依赖全部模块
+#[cfg(all(feature = "logging", not(feature = "tracing-logs")))]
+compile_error!("logging 应启用 tracing-logs");
+#[cfg(all(
+    feature = "bootstrap",
+    not(all(
+        feature = "config",
+        feature = "token",
+        feature = "snowflake",
+        feature = "tracing-logs"
+    ))
+))]
+compile_error!("bootstrap 应启用 config / token / snowflake / tracing-logs");
+
+#[test]
+fn test_error_available_in_every_combination() {
+    let err = ClamberError::ConfigValidationError {
+        details: "port".to_string(),
+    };
+    assert_eq!(err.code(), "CFG-004");
+    assert_eq!(err.category(), ErrorCategory::Config);
+    assert!(err.is_user_error());
+    assert_eq!(err.to_error_response().code, "CFG-004");
+
+    let io: Result<()> = Err(std::io::Error::other("disk").into());
+    assert!(matches!(io, Err(ClamberError::IoError { .. })));
+}
+
+#[test]
+fn test_enabled_modules_work() {
+    #[cfg(feature = "snowflake")]
+    {
+        let manager =
+            clamber_core::SnowflakeManager::new(clamber_core::SnowflakeConfig::new(1).unwrap())
+                .unwrap();
+        manager.health_check().unwrap();
+    }
+    #[cfg(feature = "token")]
+    {
+        let manager = clamber_core::JwtManager::new(clamber_core::JwtConfig::with_secret("s"));
+        let token = manager.generate_token(&"payload").unwrap();
+        assert_eq!(manager.verify_token::<String>(&token).unwrap(), "payload");
+    }
+}
@@ -0,0 +1,53 @@
+//! 默认 JWT 配置的便利函数：默认配置为进程级状态，因此放在独立的测试二进制中顺序执行
+use clamber_core::token::{
+    decode_claims, generate_token, generate_token_default, is_valid_token, set_default_config,
+    verify_token,
+};
+use clamber_core::{ClamberError, JwtConfig};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct User {
+    id: String,
+}
+
+fn user() -> User {
+    User {
+        id: "7".to_string(),
+    }
+}
+
+#[test]
+fn test_default_config_lifecycle() {
+    let builtin = generate_token(&user(), JwtConfig::default()).unwrap();
+
+    // 未设置默认配置
+    #[cfg(feature = "strict-default")]
+    {
+        let err = verify_token::<User>(&builtin).unwrap_err();
+        assert!(matches!(err, ClamberError::JwtKeyError { .. }), "{}", err);
+        assert!(err.to_string().contains("set_default_config"), "{}", err);
+        assert!(generate_token_default(&user()).is_err());
+        assert!(decode_claims(&builtin).is_err());
+        assert!(!is_valid_token(&builtin));
+    }
+    #[cfg(not(feature = "strict-default"))]
+    {
+        assert_eq!(verify_token::<User>(&builtin).unwrap(), user());
+        assert!(is_valid_token(&generate_token_default(&user()).unwrap()));
+    }
+
+    set_default_config(JwtConfig::new("application_secret", 1)).unwrap();
+    let err = set_default_config(JwtConfig::new("another_secret", 1)).unwrap_err();
+    assert!(matches!(err, ClamberError::JwtError { .. }));
+
+    // 之后全部便利函数使用应用配置的密钥
+    let token = generate_token_default(&user()).unwrap();
+    assert_eq!(verify_token::<User>(&token).unwrap(), user());
+    assert!(is_valid_token(&token));
+    assert!(decode_claims(&token).is_ok());
+    assert!(!is_valid_token(&builtin));
+    assert!(!is_valid_token(
+        &generate_token(&user(), JwtConfig::new("another_secret", 1)).unwrap()
+    ));
+}
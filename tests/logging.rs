@@ -0,0 +1,69 @@
+//! 日志初始化集成测试（会设置全局 subscriber 并切换当前工作目录，单独成为一个测试二进制）
+use clamber_core::ClamberError;
+use clamber_core::tracing_logs::{
+    LogConfig, is_initialized, logger_start_once, logger_start_with_config,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+use tracing::metadata::LevelFilter;
+
+fn read_log_files(dir: &Path, prefix: &str) -> String {
+    let mut contents = String::new();
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with(prefix)
+        {
+            contents.push_str(&fs::read_to_string(&path).unwrap());
+        }
+    }
+    contents
+}
+
+#[test]
+fn test_file_level_routes_records() {
+    let dir = tempdir().unwrap();
+    std::env::set_current_dir(dir.path()).unwrap();
+
+    let config = LogConfig::new()
+        .ansi(false)
+        .console_level(LevelFilter::OFF)
+        .file_level(LevelFilter::DEBUG);
+    assert!(!is_initialized());
+    let logger = logger_start_with_config("levels", None, config).unwrap();
+    assert!(is_initialized());
+    tracing::trace!("trace record");
+    tracing::debug!("debug record");
+    tracing::info!("info record");
+    tracing::warn!("warn record");
+    tracing::error!("error record");
+    logger.flush();
+
+    // 重复初始化返回错误而不是 panic
+    match logger_start_with_config("again", None, LogConfig::new()) {
+        Err(ClamberError::LoggingError { message }) => {
+            assert_eq!(message, "global subscriber already set")
+        }
+        other => panic!("expected logging error, got {:?}", other),
+    }
+    let noop = logger_start_once("again", None, LogConfig::new()).unwrap();
+    noop.flush();
+    assert!(!dir.path().join("logs").join("again-info.log").exists());
+
+    let log_dir = dir.path().join("logs");
+    let info = read_log_files(&log_dir, "levels-info.log");
+    for record in ["debug record", "info record", "warn record", "error record"] {
+        assert!(info.contains(record), "{} 应写入 info 文件", record);
+    }
+    assert!(!info.contains("trace record"));
+
+    let error = read_log_files(&log_dir, "levels-error.log");
+    assert!(error.contains("error record"));
+    for record in ["debug record", "info record", "warn record"] {
+        assert!(!error.contains(record), "{} 不应写入 error 文件", record);
+    }
+}
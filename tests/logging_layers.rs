@@ -0,0 +1,49 @@
+//! 注入自定义 layer 的集成测试（会设置全局 subscriber，单独成为一个测试二进制）
+use clamber_core::tracing_logs::{BoxedLayer, LogConfig, logger_start_with_layers};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::tempdir;
+use tracing::Subscriber;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context;
+
+/// 统计收到的事件数，模拟 tracing-opentelemetry 等外部 layer
+struct CountingLayer(Arc<AtomicUsize>);
+
+impl<S: Subscriber> Layer<S> for CountingLayer {
+    fn on_event(&self, _event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_extra_layers_share_registry() {
+    let dir = tempdir().unwrap();
+    let events = Arc::new(AtomicUsize::new(0));
+    let extra: BoxedLayer = Box::new(CountingLayer(events.clone()));
+    let shut_down = Arc::new(AtomicUsize::new(0));
+
+    let config = LogConfig::new().ansi(false).log_dir(dir.path());
+    let mut logger = logger_start_with_layers("layers", config, vec![extra]).unwrap();
+    let counter = shut_down.clone();
+    logger.on_shutdown(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    tracing::info!("seen by both");
+    tracing::warn!("seen by both");
+    logger.shutdown();
+
+    assert_eq!(events.load(Ordering::SeqCst), 2);
+    assert_eq!(shut_down.load(Ordering::SeqCst), 1);
+    let info = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().contains("layers-info.log"))
+        .unwrap();
+    assert!(
+        std::fs::read_to_string(info)
+            .unwrap()
+            .contains("seen by both")
+    );
+}
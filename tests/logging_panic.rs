@@ -0,0 +1,44 @@
+//! panic 钩子集成测试（会设置全局 subscriber 与 panic 钩子，单独成为一个测试二进制）
+use clamber_core::tracing_logs::{LogConfig, logger_start_with_config};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+use tracing::metadata::LevelFilter;
+
+fn read_error_log(dir: &Path, prefix: &str) -> String {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_string_lossy().contains(prefix))
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_panic_recorded_in_error_log() {
+    let dir = tempdir().unwrap();
+    let config = LogConfig::new()
+        .ansi(false)
+        .console_level(LevelFilter::OFF)
+        .log_dir(dir.path());
+    let logger = logger_start_with_config("crash", None, config).unwrap();
+
+    let line = line!() + 1;
+    let result = std::thread::spawn(|| panic!("worker exploded")).join();
+    assert!(result.is_err());
+    logger.flush();
+
+    let error_log = read_error_log(dir.path(), "crash-error.log");
+    assert!(error_log.contains("worker exploded"), "{}", error_log);
+    assert!(
+        error_log.contains(&format!("tests/logging_panic.rs:{}", line)),
+        "{}",
+        error_log
+    );
+
+    // 移除后 panic 不再写入日志
+    logger.remove_panic_hook();
+    let _ = std::thread::spawn(|| panic!("after removal")).join();
+    logger.flush();
+    assert!(!read_error_log(dir.path(), "crash-error.log").contains("after removal"));
+}
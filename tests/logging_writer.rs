@@ -0,0 +1,28 @@
+//! 自定义控制台 writer 的集成测试（会设置全局 subscriber，单独成为一个测试二进制）
+use clamber_core::testing::CapturingLogger;
+use clamber_core::tracing_logs::{ConsoleTarget, LogConfig, logger_start_with_writer};
+use tracing::metadata::LevelFilter;
+
+#[test]
+fn test_custom_writer_replaces_console() {
+    let output = CapturingLogger::new();
+    let config = LogConfig::new()
+        .ansi(false)
+        .file_enabled(false)
+        .console_level(LevelFilter::DEBUG)
+        .console_target(ConsoleTarget::SplitByLevel {
+            error_to_stderr: true,
+        });
+    let logger = logger_start_with_writer("writer", config, output.clone()).unwrap();
+
+    tracing::trace!("below console level");
+    tracing::debug!("debug to writer");
+    tracing::error!("error to writer");
+    logger.flush();
+
+    // console_target 不再生效，全部记录写入同一个 writer
+    let lines = output.lines();
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+    assert!(lines[0].contains("DEBUG") && lines[0].contains("debug to writer"));
+    assert!(lines[1].contains("ERROR") && lines[1].contains("error to writer"));
+}
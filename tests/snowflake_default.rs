@@ -0,0 +1,47 @@
+//! 默认 Snowflake 管理器初始化的集成测试（修改进程级的默认管理器与环境变量，单独成为一个测试二进制）
+use clamber_core::ClamberError;
+use clamber_core::snowflake::{EPOCH_ENV, WORKER_ID_ENV};
+use clamber_core::testing::EnvGuard;
+use clamber_core::{SnowflakeConfig, snowflake_utils};
+
+#[test]
+fn test_init_default_from_env_before_use() {
+    // 签发 token 与创建请求 span 不使用默认管理器，之后仍可初始化
+    #[cfg(feature = "token")]
+    {
+        let manager = clamber_core::JwtManager::new(clamber_core::JwtConfig::with_secret("s"));
+        manager.generate_token(&"payload").unwrap();
+    }
+    #[cfg(feature = "tracing-logs")]
+    drop(clamber_core::RequestSpan::new());
+
+    // 无效值返回错误，默认管理器保持未初始化
+    let env = EnvGuard::new().remove(WORKER_ID_ENV).remove(EPOCH_ENV);
+    assert!(matches!(
+        snowflake_utils::init_default_from_env(),
+        Err(ClamberError::SnowflakeConfigError { .. })
+    ));
+    let env = env.set(WORKER_ID_ENV, "abc");
+    assert!(snowflake_utils::init_default_from_env().is_err());
+    let env = env.set(WORKER_ID_ENV, "1024");
+    assert!(snowflake_utils::init_default_from_env().is_err());
+    let env = env.set(WORKER_ID_ENV, "7").set(EPOCH_ENV, "yesterday");
+    assert!(matches!(
+        snowflake_utils::init_default_from_env(),
+        Err(ClamberError::SnowflakeConfigError { .. })
+    ));
+
+    let _env = env.set(EPOCH_ENV, "1609459200000");
+    snowflake_utils::init_default_from_env().unwrap();
+    let info = snowflake_utils::parse_id(snowflake_utils::generate_id().unwrap()).unwrap();
+    assert_eq!(info.worker_id, 7);
+    // 2021-01-01 纪元下的时间戳大于默认纪元（2024-01-01）下的值
+    assert!(info.timestamp > 100_000_000_000);
+
+    let err = snowflake_utils::init_default(SnowflakeConfig::new(8).unwrap()).unwrap_err();
+    assert!(matches!(err, ClamberError::SnowflakeInitError { .. }));
+    assert!(
+        err.to_string()
+            .contains("default manager already initialized")
+    );
+}